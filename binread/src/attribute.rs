@@ -11,10 +11,12 @@
 //! | [`args_tuple`](#arguments) | struct field, data variant | Like `args`, but specifies a tuple containing the arguments.
 //! | [`assert`](#assert) | struct, field, non-unit enum, data variant | Asserts that a condition is true. Can be used multiple times.
 //! | [`big`](#byte-order) | all except unit variant | Sets the byte order to big-endian.
+//! | [`bits`](#bitfields) | field | Reads a field from N bits instead of whole bytes.
 //! | [`calc`](#calculations) | field | Computes the value of a field instead of reading data.
-//! | [`count`](#count) | field | Sets the length of a vector.
+//! | [`count`](#count) | field | Sets the length of a vector or other collection.
 //! | [`default`](#default) | field | Uses the [`default`](core::default::Default) value for a field instead of reading data.
 //! | [`deref_now`](#postprocessing) | field | An alias for `postprocess_now`.
+//! | [`endian_from`](#byte-order) | struct, data variant | Sets the byte order of the fields after a byte order marker from its value.
 //! | [`if`](#conditional-values) | field | Reads data only if a condition is true.
 //! | [`ignore`](#default) | field | An alias for `default`.
 //! | [`import`](#arguments) | struct, non-unit enum, unit-like enum | Defines extra arguments for a struct or enum.
@@ -22,13 +24,16 @@
 //! | [`is_big`](#byte-order) | field | Conditionally sets the byte order to big-endian.
 //! | [`is_little`](#byte-order) | field | Conditionally set the byte order to little-endian.
 //! | [`little`](#byte-order) | all except unit variant | Sets the byte order to little-endian.
+//! | [`lsb_first`](#bitfields) | struct, non-unit enum, data variant | Reads `bits` fields starting from the least significant bit of each byte.
 //! | [`magic`](#magic) | all | Matches a magic number.
 //! | [`map`](#map) | all except unit variant | Maps a read value to a new value. When used on a struct or enum, the map function must return `Self`.
+//! | [`msb_first`](#bitfields) | struct, non-unit enum, data variant | Reads `bits` fields starting from the most significant bit of each byte. This is the default.
 //! | [`offset`](#offset) | field | Modifies the offset used by a [`FilePtr`](crate::FilePtr).
 //! | [`pad_after`](#padding-and-alignment) | field | Skips N bytes after reading a field.
 //! | [`pad_before`](#padding-and-alignment) | field | Skips N bytes before reading a field.
 //! | [`pad_size_to`](#padding-and-alignment) | field | Ensures the reader is at least N bytes after the starting position for this field.
 //! | [`parse_with`](#custom-parsers) | field | Specifies a custom function for reading a field.
+//! | [`parse_with_async`](#async-parsers) | field | Specifies a custom async function for reading a field. Only used by `BinReadAsync`.
 //! | [`postprocess_now`](#postprocessing) | field | Calls [`after_parse`](crate::BinRead::after_parse) immediately after reading data instead of after all fields have been read.
 //! | [`pre_assert`](#pre-assert) | struct, non-unit enum, unit variant | Like `assert`, but checks the condition before parsing.
 //! | [`repr`](#repr) | unit-like enum | Specifies the underlying type for a unit-like (C-style) enum.
@@ -36,9 +41,13 @@
 //! | [`return_all_errors`](#enum-errors) | non-unit enum | Returns a [`Vec`] containing the error which occurred on each variant of an enum on failure. This is the default.
 //! | [`return_unexpected_error`](#enum-errors) | non-unit enum | Returns a single generic error on failure.
 //! | [`seek_before`](#padding-and-alignment) | field | Moves the reader to a specific position before reading data.
+//! | [`size`](#size) | field | Limits a field to N bytes and skips any of them it does not read.
+//! | [`size_exact`](#size) | field | Like `size`, but returns an error if the field does not read all N bytes.
 //! | [`temp`](#temp) | field | Uses a field as a temporary variable. Only usable with the [`derive_binread`] attribute macro.
 //! | [`try`](#try) | field | Reads data into an [`Option`](core::option::Option), but stores `None` if parsing fails instead of returning an error.
 //! | [`try_map`](#map) | all except unit variant | Like `map`, but returns a [`BinResult`](crate::BinResult).
+//! | [`write_map`](#writing) | all except unit variant | Maps a value back to the type that was read before writing it. Only used by `BinWrite`.
+//! | [`write_with`](#writing) | field | Specifies a custom function for writing a field. Only used by `BinWrite`.
 //!
 //! # Byte order
 //!
@@ -63,16 +72,33 @@
 //! be referenced in the condition. Conditional byte order directives can only
 //! be used on struct fields.
 //!
+//! The `endian_from` directive reads the byte order from a marker in the data
+//! itself, such as a BOM or the `II`/`MM` of a TIFF header:
+//!
+//! ```text
+//! #[br(endian_from = $field:literal, big = $big:expr, little = $little:expr)]
+//! #[br(endian_from($field:ident, big = $big:expr, little = $little:expr))]
+//! ```
+//!
+//! Fields up to and including the marker use the byte order the struct or
+//! variant would otherwise have. Every field after it, and everything those
+//! fields read, uses big-endian if the marker equals `big` or little-endian if
+//! it equals `little`, unless they set their own byte order. Any other marker
+//! returns an [`AssertFail`](crate::Error::AssertFail) error. Byte strings are
+//! compared to byte arrays, like they are for [`magic`](#magic). `BinWrite`
+//! uses the byte order of the marker being written in the same way.
+//!
 //! The order of precedence (from highest to lowest) for determining byte order
 //! within an object is:
 //!
 //! 1. A directive on a field
-//! 2. A directive on an enum variant
-//! 3. A directive on the struct or enum
-//! 4. The [`endian`](crate::ReadOptions::endian) property of the
+//! 2. The marker of an `endian_from` directive, for the fields after it
+//! 3. A directive on an enum variant
+//! 4. A directive on the struct or enum
+//! 5. The [`endian`](crate::ReadOptions::endian) property of the
 //!    [`ReadOptions`](crate::ReadOptions) object passed to
 //!    [`BinRead::read_options`](crate::BinRead::read_options) by the caller
-//! 5. The host machine’s native byte order
+//! 6. The host machine’s native byte order
 //!
 //! However, if a byte order directive is added to a struct or enum, that byte
 //! order will *always* be used, even if the object is embedded in another
@@ -127,6 +153,20 @@
 //! # assert_eq!(MyType::read(&mut Cursor::new(b"\x03\x01\x00")).unwrap(), MyType { val: 3, other_val: 1 });
 //! ```
 //!
+//! ```
+//! # use binread::{prelude::*, io::Cursor};
+//! #[derive(BinRead)]
+//! #[br(endian_from = "bom", big = 0xFEFF, little = 0xFFFE)]
+//! struct Header {
+//!     #[br(big)]
+//!     bom: u16,
+//!     length: u32, // ← little-endian if `bom` is `0xFFFE`
+//! }
+//!
+//! # let header: Header = Cursor::new(b"\xFF\xFE\x02\0\0\0").read_ne().unwrap();
+//! # assert_eq!(header.length, 2);
+//! ```
+//!
 //! # Magic
 //!
 //! The `magic` directive matches [magic numbers](https://en.wikipedia.org/wiki/Magic_number_(programming))
@@ -301,11 +341,18 @@
 //! ```
 //!
 //! Any earlier field or [import](#arguments) can be referenced in `args`.
+//! Arguments only need to implement [`Clone`], so earlier fields can also be
+//! passed by reference. References in `import` which do not name a lifetime
+//! are given the lifetime of the arguments, and at most one named lifetime
+//! may be used.
+//!
+//! A field without `args` is given the [default arguments](crate::DefaultArgs)
+//! of its type, which are `()` for most types.
 //!
 //! ## Examples
 //!
 //! ```
-//! # use binread::prelude::*;
+//! # use binread::{prelude::*, NullString};
 //! #[derive(BinRead)]
 //! #[br(import(val1: u32, val2: &'static str))]
 //! struct ImportTest {
@@ -318,6 +365,71 @@
 //!     #[br(args(val + 3, "test"))]
 //!     test: ImportTest
 //! }
+//!
+//! #[derive(BinRead)]
+//! #[br(import(names: &[NullString]))]
+//! struct BorrowTest {
+//!     #[br(count = names.len())]
+//!     ids: Vec<u8>,
+//! }
+//!
+//! #[derive(BinRead)]
+//! struct BorrowArgsTest {
+//!     #[br(count = 2)]
+//!     names: Vec<NullString>,
+//!     #[br(args(&names))]
+//!     test: BorrowTest
+//! }
+//! ```
+//!
+//! ## Named arguments
+//!
+//! Positional arguments are easy to mix up, and adding one means updating
+//! every use of a type. Using braces instead of parentheses defines and passes
+//! arguments by name instead:
+//!
+//! ```text
+//! #[br(import { $($ident:ident : $ty:ty $(= $default:expr)?),* $(,)? })]
+//! #[br(args { $($ident:ident $(: $value:expr)?),* $(,)? })]
+//! ```
+//!
+//! Named imports generate a `{Type}BinReadArgs` struct (or
+//! `{Type}BinWriteArgs` when deriving `BinWrite`) which is used as
+//! [`BinRead::Args`](crate::BinRead::Args), along with a builder used to
+//! construct it. Arguments with a default value may be omitted, and omitting
+//! any other argument is a compile error. If every argument has a default,
+//! [`read`](crate::BinRead::read) can be used without passing any arguments.
+//! As with `import`, every argument type must be [`Clone`].
+//!
+//! Named arguments are passed to any type whose arguments implement
+//! [`NamedArgs`](crate::NamedArgs). Since the type being read is not known
+//! when `map` or `try_map` are used, named arguments only work with those
+//! directives when combined with `parse_with`.
+//!
+//! ```
+//! # use binread::{prelude::*, io::Cursor};
+//! #[derive(BinRead)]
+//! #[br(import { count: usize, version: u8 = 1 })]
+//! struct Items {
+//!     #[br(count = count, if(version > 1))]
+//!     items: Option<Vec<u8>>,
+//! }
+//!
+//! #[derive(BinRead)]
+//! struct File {
+//!     version: u8,
+//!     count: u8,
+//!     #[br(args { version, count: count.into() })]
+//!     items: Items,
+//!     #[br(args { count: 1 })]
+//!     trailer: Items,
+//! }
+//!
+//! # let file: File = Cursor::new(b"\x02\x01\xFF").read_le().unwrap();
+//! # assert_eq!(file.items.items, Some(vec![0xFF]));
+//! # assert_eq!(file.trailer.items, None);
+//! # let args = ItemsBinReadArgs::builder().count(4).finalize();
+//! # assert_eq!(args.version, 1);
 //! ```
 //!
 //! # Default
@@ -550,7 +662,9 @@
 //! ### Using `map` on a struct to create a bit field
 //!
 //! The [`modular-bitfield`](https://docs.rs/modular-bitfield) crate can be used
-//! along with `map` to create a struct out of raw bits.
+//! along with `map` to create a struct out of raw bits. For packed fields
+//! inside a larger struct, the [`bits`](#bitfields) directive is usually
+//! simpler.
 //!
 //! ```
 //! # use binread::{prelude::*, io::Cursor};
@@ -645,6 +759,22 @@
 //! # assert_eq!(val.some_string.to_string(), "Test");
 //! ```
 //!
+//! ## Async parsers
+//!
+//! When deriving `BinReadAsync`, the `parse_with_async` directive specifies the
+//! function used by the async implementation, which can not call a synchronous
+//! `parse_with` function. It is required on every field using `parse_with`:
+//!
+//! ```text
+//! #[br(parse_with_async = $parse_fn:expr)] or #[br(parse_with_async($parse_fn:expr))]
+//! ```
+//!
+//! The function takes the same arguments as a `parse_with` function, but is
+//! `async` and reads from an `AsyncRead` + `AsyncSeek` reader. Named arguments
+//! can not be passed to it. `helpers::until_eof_async` and `FilePtr::parse_async`
+//! are provided for use with this directive. This directive is only available
+//! with the `async` feature.
+//!
 //! # Calculations
 //!
 //! The `calc` directive computes the value of a field instead of reading data
@@ -674,7 +804,8 @@
 //! # Count
 //!
 //! The `count` directive sets the number of values to read into a repeating
-//! collection type like a [`Vec`]:
+//! collection type like a [`Vec`], or one of the other
+//! [collections](crate::collections) which read a count:
 //!
 //! ```text
 //! #[br(count = $count:expr) or #[br(count($count:expr))]
@@ -688,6 +819,10 @@
 //! Any earlier field or [import](#arguments) can be referenced by the
 //! expression in the directive.
 //!
+//! Since the count usually comes from the data, reading untrusted data should
+//! set a [`max_count`](crate::Limits::max_count) so that a corrupt count can
+//! not make reading allocate huge amounts of memory.
+//!
 //! ## Examples
 //!
 //! ### Using `count` with [`Vec`]
@@ -900,6 +1035,118 @@
 //! If seeking fails, an [`Io`](crate::Error::Io) error is returned and the
 //! reader’s position is reset to where it was before parsing started.
 //!
+//! # Size
+//!
+//! The `size` directive reads a field from a region of the given number of
+//! bytes, like the chunks of RIFF or PNG files. The field can not read past
+//! the end of the region, and the reader is moved to the end of the region
+//! afterwards, skipping any bytes the field did not read. `size_exact` does
+//! the same, but returns an error if the field did not read the whole region:
+//!
+//! ```text
+//! #[br(size = $size:expr)] or #[br(size($size:expr))]
+//! #[br(size_exact = $size:expr)] or #[br(size_exact($size:expr))]
+//! ```
+//!
+//! The field is read through a [`TakeSeek`](crate::io::TakeSeek), so
+//! positions within it are the same as in the rest of the data, and seeking
+//! relative to the end seeks relative to the end of the region. This makes
+//! helpers like [`until_eof`](crate::helpers::until_eof) read up to the end
+//! of the region. With `BinWrite`, the field is padded with zeroes to fill
+//! the region.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! # use binread::{prelude::*, helpers::until_eof, io::Cursor};
+//! #[derive(BinRead)]
+//! #[br(big)]
+//! struct Chunk {
+//!     kind: [u8; 4],
+//!     len: u32,
+//!     #[br(size = len, parse_with = until_eof)]
+//!     data: Vec<u16>,
+//!     crc: u32,
+//! }
+//!
+//! # let chunk: Chunk = Cursor::new(b"IDAT\0\0\0\x05\0\x01\0\x02\xFF\0\0\0\0").read_be().unwrap();
+//! # assert_eq!(chunk.data, [1, 2]);
+//! ```
+//!
+//! ## Errors
+//!
+//! If the field tries to read past the end of the region, an
+//! [`Io`](crate::Error::Io) error is returned. If a field with `size_exact`
+//! does not read the whole region, or a field being written is larger than
+//! its region, an [`AssertFail`](crate::Error::AssertFail) error is returned.
+//!
+//! # Bitfields
+//!
+//! The `bits` directive reads a field from the given number of bits rather
+//! than from whole bytes:
+//!
+//! ```text
+//! #[br(bits = $count:expr)] or #[br(bits($count:expr))]
+//! ```
+//!
+//! Consecutive `bits` fields share a [`BitReader`](crate::bits::BitReader),
+//! so a field can start partway through a byte where the previous one ended.
+//! When a field which reads whole bytes follows a run of `bits` fields, the
+//! unused bits left in the last byte are skipped. Fields which do not read
+//! data, like [`calc`](#calculations) fields, do not end a run.
+//!
+//! The field type must implement [`BitField`](crate::bits::BitField), which
+//! is implemented for the integer types up to 64 bits and for `bool`. Signed
+//! integers are sign-extended. Combine `bits` with [`map`](#map) to read
+//! other types. Since they would move the reader partway through a byte,
+//! `bits` fields can not use the [padding and alignment](#padding-and-alignment)
+//! directives or [`restore_position`](#restore-position).
+//!
+//! The `msb_first` and `lsb_first` directives set the order in which the bits
+//! of each byte are read for a whole struct, enum, or enum variant:
+//!
+//! ```text
+//! #[br(msb_first)]
+//! #[br(lsb_first)]
+//! ```
+//!
+//! With `msb_first`, which is the default, the most significant bit of each
+//! byte is read first and the first bit read is the most significant bit of
+//! the field. With `lsb_first`, both are the least significant bit instead.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use binread::{prelude::*, io::Cursor};
+//! #[derive(BinRead)]
+//! #[br(big)]
+//! struct Header {
+//!     #[br(bits = 3)]
+//!     version: u8,
+//!     #[br(bits = 5)]
+//!     kind: u8,
+//!     #[br(bits = 1)]
+//!     compressed: bool,
+//!     // The 7 remaining bits of the second byte are skipped
+//!     len: u16,
+//! }
+//!
+//! # let header: Header = Cursor::new(b"\x43\x80\x00\x10").read_ne().unwrap();
+//! # assert_eq!(header.version, 2);
+//! # assert_eq!(header.kind, 3);
+//! # assert!(header.compressed);
+//! # assert_eq!(header.len, 16);
+//! ```
+//!
+//! ## Errors
+//!
+//! If the bit count is zero or larger than the field type, an
+//! [`AssertFail`](crate::Error::AssertFail) error is returned. If reading
+//! fails, an [`Io`](crate::Error::Io) error is returned.
+//!
+//! In all cases, the reader’s position is reset to where it was before parsing
+//! started.
+//!
 //! # Repr
 //!
 //! The `repr` directive is used on a unit-like (C-style) enum to specify the
@@ -932,6 +1179,77 @@
 //!
 //! In all cases, the reader’s position is reset to where it was before parsing
 //! started.
+//!
+//! # Writing
+//!
+//! The [`BinWrite`](crate::BinWrite) derive macro reads the same `#[br]`
+//! attributes as [`BinRead`](crate::BinRead) and writes data back out in the
+//! same layout it would be read from:
+//!
+//! * `magic` values are written, and byte order and `args` work the same way.
+//! * Fields using `calc`, `default`, or `ignore` are not written.
+//! * Fields using `if` are only written when the condition is true.
+//! * `pad_before`, `pad_after`, `align_before`, `align_after`, and
+//!   `pad_size_to` write zeroes instead of skipping bytes.
+//! * `seek_before` and `restore_position` move the writer instead of the
+//!   reader.
+//! * `bits` fields are packed with a [`BitWriter`](crate::bits::BitWriter),
+//!   and the unused bits of the last byte of a run are written as zeroes.
+//! * Collections and strings using `count` must have that many items so that
+//!   they read back the same. Writing one with a different length returns an
+//!   [`AssertFail`](crate::Error::AssertFail) error at the position of the
+//!   field, before any of it is written.
+//! * `assert`, `pre_assert`, `offset`, and postprocessing directives have no
+//!   effect.
+//!
+//! Fields are bound by name so that they can be used in directive
+//! expressions, as when reading. Fields which are [`Copy`] are bound by value
+//! and all other fields are bound by reference.
+//!
+//! Since `map`, `try_map`, and `parse_with` can not be reversed automatically,
+//! they must be paired with `write_map` or `write_with` respectively:
+//!
+//! ```text
+//! #[br(write_map = $map_fn:expr)] or #[br(write_map($map_fn:expr))]
+//! #[br(write_with = $write_fn:expr)] or #[br(write_with($write_fn:expr))]
+//! ```
+//!
+//! A `write_map` function receives a reference to the value and returns the
+//! value to write in its place. A `write_with` function has the same signature
+//! as [`BinWrite::write_options`](crate::BinWrite::write_options), except that
+//! it receives a reference to the value as its first parameter.
+//!
+//! `BinWrite` can not be combined with the [`derive_binread`] attribute macro,
+//! since it removes the `#[br]` attributes before other derives see them.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use binread::{prelude::*, io::{Cursor, Seek, Write}, BinResult, WriteOptions};
+//! fn write_flags<W: Write + Seek>(
+//!     flags: &[bool; 2],
+//!     writer: &mut W,
+//!     options: &WriteOptions,
+//!     _: (),
+//! ) -> BinResult<()> {
+//!     (flags[0] as u8 | (flags[1] as u8) << 1).write_options(writer, options, ())
+//! }
+//!
+//! #[derive(BinWrite)]
+//! #[br(big, magic = b"DOG")]
+//! struct Dog {
+//!     #[br(map = |x: u8| x.to_string(), write_map = |x: &String| x.parse::<u8>().unwrap())]
+//!     age: String,
+//!     #[br(parse_with = |_, _, _: ()| Ok([false; 2]), write_with = write_flags)]
+//!     flags: [bool; 2],
+//!     #[br(calc = 4)]
+//!     legs: u8,
+//! }
+//!
+//! let mut writer = Cursor::new(Vec::new());
+//! writer.write_ne(&Dog { age: "3".into(), flags: [true, true], legs: 4 }).unwrap();
+//! assert_eq!(writer.into_inner(), b"DOG\x03\x03");
+//! ```
 
 #![allow(unused_imports)]
 
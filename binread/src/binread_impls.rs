@@ -5,9 +5,9 @@ macro_rules! binread_impl {
     ($($type_name:ty),*$(,)?) => {
         $(
             impl BinRead for $type_name {
-                type Args = ();
+                type Args<'a> = ();
 
-                fn read_options<R: Read + Seek>(reader: &mut R, options: &ReadOptions, _: Self::Args) -> BinResult<Self> {
+                fn read_options<R: Read + Seek>(reader: &mut R, options: &ReadOptions, _: Self::Args<'_>) -> BinResult<Self> {
                     let mut val = [0; core::mem::size_of::<$type_name>()];
                     let pos = reader.stream_pos()?;
 
@@ -59,12 +59,12 @@ macro_rules! binread_impl {
 const DEFAULT_ARGS: () = ();
 
 impl BinRead for char {
-    type Args = ();
+    type Args<'a> = ();
 
     fn read_options<R: Read + Seek>(
         reader: &mut R,
         options: &ReadOptions,
-        _: Self::Args,
+        _: Self::Args<'_>,
     ) -> BinResult<Self> {
         // TODO: somehow do proper unicode handling?
         Ok(<u8>::read_options(reader, options, DEFAULT_ARGS)? as char)
@@ -73,19 +73,140 @@ impl BinRead for char {
 
 binread_impl!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
 
-impl<C: Copy + 'static, B: BinRead<Args = C>> BinRead for Vec<B> {
-    type Args = B::Args;
+/// Internal macro for implementing binread for the variable-length integer
+/// types, which are read one byte at a time until the last byte
+macro_rules! binread_varint_impl {
+    ($($name:ident),*) => {
+        $(
+            impl<T> BinRead for varint::$name<T>
+            where
+                Self: varint::VarInt,
+            {
+                type Args<'a> = ();
+
+                fn read_options<R: Read + Seek>(
+                    reader: &mut R,
+                    _: &ReadOptions,
+                    _: Self::Args<'_>,
+                ) -> BinResult<Self> {
+                    use varint::{Step, VarInt};
+
+                    let pos = reader.stream_pos()?;
+                    let mut state = <Self as VarInt>::State::default();
+                    let result = loop {
+                        let mut byte = [0];
+                        if let Err(err) = reader.read_exact(&mut byte) {
+                            break Err(err.into());
+                        }
+
+                        match Self::decode(&mut state, byte[0]) {
+                            Step::More => {}
+                            Step::Done(value) => break Ok(value),
+                            Step::Overflow => {
+                                break Err(Error::Overflow {
+                                    pos,
+                                    type_name: Self::NAME,
+                                })
+                            }
+                        }
+                    };
+
+                    if result.is_err() {
+                        reader.seek(SeekFrom::Start(pos))?;
+                    }
+
+                    result
+                }
+            }
+        )*
+    };
+}
+
+binread_varint_impl!(Leb128, Vlq, ZigZag);
+
+/// Internal macro for implementing binread for the integers in [`int`], which
+/// are read from their exact number of bytes
+macro_rules! binread_int_impl {
+    ($($type_name:ident),*) => {
+        $(
+            impl BinRead for int::$type_name {
+                type Args<'a> = ();
+
+                fn read_options<R: Read + Seek>(
+                    reader: &mut R,
+                    options: &ReadOptions,
+                    _: Self::Args<'_>,
+                ) -> BinResult<Self> {
+                    let mut bytes = [0; (int::$type_name::BITS / 8) as usize];
+                    let pos = reader.stream_pos()?;
+                    reader.read_exact(&mut bytes).or_else(|e| {
+                        reader.seek(SeekFrom::Start(pos))?;
+                        Err(e)
+                    })?;
+
+                    Ok(int::$type_name::from_bytes(bytes, options.endian))
+                }
+            }
+        )*
+    };
+}
+
+binread_int_impl!(u24, i24, u40, i40, u48, i48, u56, i56);
+
+impl BinRead for float::f16 {
+    type Args<'a> = ();
 
     fn read_options<R: Read + Seek>(
         reader: &mut R,
         options: &ReadOptions,
-        args: Self::Args,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        <u16>::read_options(reader, options, ()).map(Self::from_bits)
+    }
+}
+
+impl BinRead for float::bf16 {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        <u16>::read_options(reader, options, ()).map(Self::from_bits)
+    }
+}
+
+#[cfg(feature = "const_generics")]
+impl<T: for<'a> BinRead<Args<'a> = ()>, const FRAC: u32> BinRead for float::Fixed<T, FRAC> {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        T::read_options(reader, options, ()).map(Self::from_bits)
+    }
+}
+
+impl<B: BinRead> BinRead for Vec<B> {
+    type Args<'a> = B::Args<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
     ) -> BinResult<Self> {
         let mut options = *options;
         let count = match options.count.take() {
             Some(x) => x,
             None => panic!("Missing count for Vec"),
         };
+        options
+            .limits
+            .check_count::<B>(count)
+            .map_err(|limit| error::limit_exceeded(reader, limit))?;
 
         #[cfg(feature = "debug_template")]
         {
@@ -110,7 +231,10 @@ impl<C: Copy + 'static, B: BinRead<Args = C>> BinRead for Vec<B> {
         }
 
         (0..count)
-            .map(|_| B::read_options(reader, &options, args))
+            .map(|i| {
+                B::read_options(reader, &options, args.clone())
+                    .map_err(|e| error::index_context(reader, e, i))
+            })
             .collect()
     }
 
@@ -118,13 +242,14 @@ impl<C: Copy + 'static, B: BinRead<Args = C>> BinRead for Vec<B> {
         &mut self,
         reader: &mut R,
         ro: &ReadOptions,
-        args: Self::Args,
+        args: Self::Args<'_>,
     ) -> BinResult<()>
     where
         R: Read + Seek,
     {
-        for val in self.iter_mut() {
-            val.after_parse(reader, ro, args)?;
+        for (i, val) in self.iter_mut().enumerate() {
+            val.after_parse(reader, ro, args.clone())
+                .map_err(|e| error::index_context(reader, e, i))?;
         }
 
         Ok(())
@@ -135,10 +260,10 @@ impl<C: Copy + 'static, B: BinRead<Args = C>> BinRead for Vec<B> {
 macro_rules! binread_array_impl {
     ($($size:literal),*$(,)?) => {
         $(
-            impl<C: Copy + 'static, B: BinRead<Args = C> + Default> BinRead for [B; $size] {
-                type Args = B::Args;
+            impl<B: BinRead + Default> BinRead for [B; $size] {
+                type Args<'a> = B::Args<'a>;
 
-                fn read_options<R: Read + Seek>(reader: &mut R, options: &ReadOptions, args: Self::Args) -> BinResult<Self> {
+                fn read_options<R: Read + Seek>(reader: &mut R, options: &ReadOptions, args: Self::Args<'_>) -> BinResult<Self> {
                     #[cfg(feature = "debug_template")]
                     {
                         let pos = reader.stream_pos()?;
@@ -160,17 +285,19 @@ macro_rules! binread_array_impl {
                     };
 
                     let mut arr: [B; $size] = Default::default();
-                    for elem in arr.iter_mut() {
-                        *elem = BinRead::read_options(reader, options, args)?;
+                    for (i, elem) in arr.iter_mut().enumerate() {
+                        *elem = BinRead::read_options(reader, options, args.clone())
+                            .map_err(|e| error::index_context(reader, e, i))?;
                     }
                     Ok(arr)
                 }
 
-                fn after_parse<R>(&mut self, reader: &mut R, ro: &ReadOptions, args: B::Args)-> BinResult<()>
+                fn after_parse<R>(&mut self, reader: &mut R, ro: &ReadOptions, args: B::Args<'_>)-> BinResult<()>
                     where R: Read + Seek,
                 {
-                    for val in self.iter_mut() {
-                        val.after_parse(reader, ro, args)?;
+                    for (i, val) in self.iter_mut().enumerate() {
+                        val.after_parse(reader, ro, args.clone())
+                            .map_err(|e| error::index_context(reader, e, i))?;
                     }
 
                     Ok(())
@@ -187,13 +314,13 @@ binread_array_impl!(
 );
 
 #[cfg(feature = "const_generics")]
-impl<C: Copy + 'static, B: BinRead<Args = C>, const N: usize> BinRead for [B; N] {
-    type Args = B::Args;
+impl<B: BinRead, const N: usize> BinRead for [B; N] {
+    type Args<'a> = B::Args<'a>;
 
     fn read_options<R: Read + Seek>(
         reader: &mut R,
         options: &ReadOptions,
-        args: Self::Args,
+        args: Self::Args<'_>,
     ) -> BinResult<Self> {
         #[cfg(feature = "debug_template")]
         {
@@ -213,16 +340,25 @@ impl<C: Copy + 'static, B: BinRead<Args = C>, const N: usize> BinRead for [B; N]
             ..*options
         };
 
-        let arr = array_init::try_array_init(|_| BinRead::read_options(reader, options, args))?;
+        let arr = array_init::try_array_init(|i| {
+            BinRead::read_options(reader, options, args.clone())
+                .map_err(|e| error::index_context(reader, e, i))
+        })?;
         Ok(arr)
     }
 
-    fn after_parse<R>(&mut self, reader: &mut R, ro: &ReadOptions, args: B::Args) -> BinResult<()>
+    fn after_parse<R>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: B::Args<'_>,
+    ) -> BinResult<()>
     where
         R: Read + Seek,
     {
-        for val in self.iter_mut() {
-            val.after_parse(reader, ro, args)?;
+        for (i, val) in self.iter_mut().enumerate() {
+            val.after_parse(reader, ro, args.clone())
+                .map_err(|e| error::index_context(reader, e, i))?;
         }
 
         Ok(())
@@ -234,10 +370,10 @@ impl<C: Copy + 'static, B: BinRead<Args = C>, const N: usize> BinRead for [B; N]
 macro_rules! binread_tuple_impl {
     ($type1:ident $(, $types:ident)*) => {
         #[allow(non_camel_case_types)]
-        impl<$type1: BinRead<Args=()>, $($types: BinRead<Args=()>),*> BinRead for ($type1, $($types),*) {
-            type Args = ();
+        impl<$type1: for<'a> BinRead<Args<'a> = ()>, $($types: for<'a> BinRead<Args<'a> = ()>),*> BinRead for ($type1, $($types),*) {
+            type Args<'a> = ();
 
-            fn read_options<R: Read + Seek>(reader: &mut R, options: &ReadOptions, _: Self::Args) -> BinResult<Self> {
+            fn read_options<R: Read + Seek>(reader: &mut R, options: &ReadOptions, _: Self::Args<'_>) -> BinResult<Self> {
                 Ok((
                     BinRead::read_options(reader, options, ())?,
                     $(
@@ -246,7 +382,7 @@ macro_rules! binread_tuple_impl {
                 ))
             }
 
-            fn after_parse<R: Read + Seek>(&mut self, reader: &mut R, options: &ReadOptions, _: Self::Args) -> BinResult<()> {
+            fn after_parse<R: Read + Seek>(&mut self, reader: &mut R, options: &ReadOptions, _: Self::Args<'_>) -> BinResult<()> {
                 let ($type1, $(
                     $types
                 ),*) = self;
@@ -272,32 +408,36 @@ binread_tuple_impl!(
 );
 
 impl BinRead for () {
-    type Args = ();
+    type Args<'a> = ();
 
-    fn read_options<R: Read + Seek>(_: &mut R, _: &ReadOptions, _: Self::Args) -> BinResult<Self> {
+    fn read_options<R: Read + Seek>(
+        _: &mut R,
+        _: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
         Ok(())
     }
 }
 
 impl<T: BinRead> BinRead for Box<T> {
-    type Args = T::Args;
+    type Args<'a> = T::Args<'a>;
 
     fn read_options<R: Read + Seek>(
         reader: &mut R,
         options: &ReadOptions,
-        args: Self::Args,
+        args: Self::Args<'_>,
     ) -> BinResult<Self> {
         Ok(Box::new(T::read_options(reader, options, args)?))
     }
 }
 
 impl<T: BinRead> BinRead for Option<T> {
-    type Args = T::Args;
+    type Args<'a> = T::Args<'a>;
 
     fn read_options<R: Read + Seek>(
         reader: &mut R,
         options: &ReadOptions,
-        args: Self::Args,
+        args: Self::Args<'_>,
     ) -> BinResult<Self> {
         Ok(Some(T::read_options(reader, options, args)?))
     }
@@ -306,7 +446,7 @@ impl<T: BinRead> BinRead for Option<T> {
         &mut self,
         reader: &mut R,
         ro: &ReadOptions,
-        args: Self::Args,
+        args: Self::Args<'_>,
     ) -> BinResult<()>
     where
         R: Read + Seek,
@@ -319,9 +459,13 @@ impl<T: BinRead> BinRead for Option<T> {
 }
 
 impl<T> BinRead for core::marker::PhantomData<T> {
-    type Args = ();
+    type Args<'a> = ();
 
-    fn read_options<R: Read + Seek>(_: &mut R, _: &ReadOptions, _: Self::Args) -> BinResult<Self> {
+    fn read_options<R: Read + Seek>(
+        _: &mut R,
+        _: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
         Ok(core::marker::PhantomData)
     }
 }
@@ -0,0 +1,307 @@
+use super::*;
+#[cfg(not(feature = "std"))]
+use alloc::format;
+use core::convert::TryFrom;
+
+/// Internal macro for quickly implementing binwrite for types supporting to_bytes api
+macro_rules! binwrite_impl {
+    ($($type_name:ty),*$(,)?) => {
+        $(
+            impl BinWrite for $type_name {
+                type Args<'a> = ();
+
+                fn write_options<W: Write + Seek>(&self, writer: &mut W, options: &WriteOptions, _: Self::Args<'_>) -> BinResult<()> {
+                    let bytes = match options.endian {
+                        Endian::Big => self.to_be_bytes(),
+                        Endian::Little => self.to_le_bytes(),
+                        Endian::Native => self.to_ne_bytes(),
+                    };
+
+                    writer.write_all(&bytes)?;
+                    Ok(())
+                }
+            }
+        )*
+    }
+}
+
+binwrite_impl!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+/// Internal macro for implementing binwrite for the variable-length integer
+/// types, which ignore the byte order
+macro_rules! binwrite_varint_impl {
+    ($($name:ident),*) => {
+        $(
+            impl<T> BinWrite for varint::$name<T>
+            where
+                Self: varint::VarInt,
+            {
+                type Args<'a> = ();
+
+                fn write_options<W: Write + Seek>(
+                    &self,
+                    writer: &mut W,
+                    _: &WriteOptions,
+                    _: Self::Args<'_>,
+                ) -> BinResult<()> {
+                    writer.write_all(&varint::VarInt::encode(self))?;
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+binwrite_varint_impl!(Leb128, Vlq, ZigZag);
+
+/// Internal macro for implementing binwrite for the integers in [`int`]
+macro_rules! binwrite_int_impl {
+    ($($type_name:ident),*) => {
+        $(
+            impl BinWrite for int::$type_name {
+                type Args<'a> = ();
+
+                fn write_options<W: Write + Seek>(
+                    &self,
+                    writer: &mut W,
+                    options: &WriteOptions,
+                    _: Self::Args<'_>,
+                ) -> BinResult<()> {
+                    writer.write_all(&self.to_bytes(options.endian))?;
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+binwrite_int_impl!(u24, i24, u40, i40, u48, i48, u56, i56);
+
+impl BinWrite for float::f16 {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.to_bits().write_options(writer, options, ())
+    }
+}
+
+impl BinWrite for float::bf16 {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.to_bits().write_options(writer, options, ())
+    }
+}
+
+#[cfg(feature = "const_generics")]
+impl<T, const FRAC: u32> BinWrite for float::Fixed<T, FRAC>
+where
+    T: for<'a> BinWrite<Args<'a> = ()>,
+{
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.0.write_options(writer, options, ())
+    }
+}
+
+impl BinWrite for char {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<()> {
+        // Mirrors the `BinRead` implementation, which reads a single byte
+        match u8::try_from(u32::from(*self)) {
+            Ok(byte) => byte.write_options(writer, options, ()),
+            Err(_) => Err(Error::AssertFail {
+                pos: writer.stream_pos()?,
+                message: format!("{:?} does not fit in a byte", self),
+            }),
+        }
+    }
+}
+
+impl<B: BinWrite> BinWrite for Vec<B> {
+    type Args<'a> = B::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.as_slice().write_options(writer, options, args)
+    }
+}
+
+impl<B: BinWrite> BinWrite for [B] {
+    type Args<'a> = B::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        for val in self {
+            val.write_options(writer, options, args.clone())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "const_generics"))]
+macro_rules! binwrite_array_impl {
+    ($($size:literal),*$(,)?) => {
+        $(
+            impl<B: BinWrite> BinWrite for [B; $size] {
+                type Args<'a> = B::Args<'a>;
+
+                fn write_options<W: Write + Seek>(&self, writer: &mut W, options: &WriteOptions, args: Self::Args<'_>) -> BinResult<()> {
+                    self[..].write_options(writer, options, args)
+                }
+            }
+        )*
+    }
+}
+
+#[cfg(not(feature = "const_generics"))]
+binwrite_array_impl!(
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+    26, 27, 28, 29, 30, 31, 32
+);
+
+#[cfg(feature = "const_generics")]
+impl<B: BinWrite, const N: usize> BinWrite for [B; N] {
+    type Args<'a> = B::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self[..].write_options(writer, options, args)
+    }
+}
+
+/// Internal macro to recursively implement BinWrite for every size tuple given
+/// in the invocation
+macro_rules! binwrite_tuple_impl {
+    ($type1:ident $(, $types:ident)*) => {
+        #[allow(non_camel_case_types)]
+        impl<$type1: for<'a> BinWrite<Args<'a> = ()>, $($types: for<'a> BinWrite<Args<'a> = ()>),*> BinWrite for ($type1, $($types),*) {
+            type Args<'a> = ();
+
+            fn write_options<W: Write + Seek>(&self, writer: &mut W, options: &WriteOptions, _: Self::Args<'_>) -> BinResult<()> {
+                let ($type1, $(
+                    $types
+                ),*) = self;
+
+                $type1.write_options(writer, options, ())?;
+                $(
+                    $types.write_options(writer, options, ())?;
+                )*
+
+                Ok(())
+            }
+        }
+
+        binwrite_tuple_impl!($($types),*);
+    };
+
+    () => {};
+}
+
+binwrite_tuple_impl!(
+    b1, b2, b3, b4, b5, b6, b7, b8, b9, b10, b11, b12, b13, b14, b15, b16, b17, b18, b19, b20, b21,
+    b22, b23, b24, b25, b26, b27, b28, b29, b30, b31, b32
+);
+
+impl BinWrite for () {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        _: &mut W,
+        _: &WriteOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<()> {
+        Ok(())
+    }
+}
+
+impl<T: BinWrite + ?Sized> BinWrite for Box<T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        (**self).write_options(writer, options, args)
+    }
+}
+
+impl<T: BinWrite + ?Sized> BinWrite for &T {
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        (**self).write_options(writer, options, args)
+    }
+}
+
+impl<T: BinWrite> BinWrite for Option<T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        match self {
+            Some(val) => val.write_options(writer, options, args),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T> BinWrite for core::marker::PhantomData<T> {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        _: &mut W,
+        _: &WriteOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,399 @@
+//! A module for reading and writing values which are not a whole number of bytes wide.
+//!
+//! These types are what the [`bits`](crate::attribute#bitfields) directive uses to read packed
+//! fields, but they can also be used directly from a custom parser.
+
+use crate::io::{self, Read, Seek, StreamPosition, Write};
+use crate::{BinResult, Error};
+#[cfg(not(feature = "std"))]
+use alloc::format;
+
+/// The order in which the bits of each byte are consumed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BitOrder {
+    /// The most significant bit of each byte comes first, and the first bit read becomes the
+    /// most significant bit of the value. This is the default.
+    #[default]
+    Msb,
+
+    /// The least significant bit of each byte comes first, and the first bit read becomes the
+    /// least significant bit of the value.
+    Lsb,
+}
+
+/// A type which can be read from, or written to, a number of bits.
+///
+/// Signed integers are sign-extended from the highest bit that was read.
+pub trait BitField: Sized {
+    /// The width of the type, in bits.
+    const BITS: u32;
+
+    /// Converts the lowest `count` bits of `raw` into a value.
+    fn from_bits(raw: u64, count: u32) -> Self;
+
+    /// Converts the value into its lowest `count` bits, or returns `None` if it does not fit.
+    fn to_bits(&self, count: u32) -> Option<u64>;
+}
+
+macro_rules! unsigned_bit_field {
+    ($($ty:ty),*) => {
+        $(
+            impl BitField for $ty {
+                const BITS: u32 = <$ty>::BITS;
+
+                fn from_bits(raw: u64, _: u32) -> Self {
+                    raw as $ty
+                }
+
+                fn to_bits(&self, count: u32) -> Option<u64> {
+                    let raw = *self as u64;
+                    if raw & !mask(count) == 0 {
+                        Some(raw)
+                    } else {
+                        None
+                    }
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! signed_bit_field {
+    ($($ty:ty),*) => {
+        $(
+            impl BitField for $ty {
+                const BITS: u32 = <$ty>::BITS;
+
+                fn from_bits(raw: u64, count: u32) -> Self {
+                    let shift = 64 - count;
+                    (((raw << shift) as i64) >> shift) as $ty
+                }
+
+                fn to_bits(&self, count: u32) -> Option<u64> {
+                    let value = *self as i64;
+                    let shift = 64 - count;
+                    if ((value << shift) >> shift) == value {
+                        Some(value as u64 & mask(count))
+                    } else {
+                        None
+                    }
+                }
+            }
+        )*
+    }
+}
+
+unsigned_bit_field!(u8, u16, u32, u64);
+signed_bit_field!(i8, i16, i32, i64);
+
+impl BitField for bool {
+    const BITS: u32 = 1;
+
+    fn from_bits(raw: u64, _: u32) -> Self {
+        raw != 0
+    }
+
+    fn to_bits(&self, _: u32) -> Option<u64> {
+        Some(u64::from(*self))
+    }
+}
+
+/// Reads values a number of bits at a time, keeping track of the bits that have not been used
+/// yet from the last byte read.
+///
+/// The reader itself is passed to every call rather than being held, so it stays available for
+/// reading whole values between runs of bits.
+///
+/// ## Example
+///
+/// ```rust
+/// # use binread::io::Cursor;
+/// use binread::bits::{BitOrder, BitReader};
+///
+/// let mut reader = Cursor::new(b"\xB4\x80");
+/// let mut bits = BitReader::new(BitOrder::Msb);
+/// assert_eq!(bits.read::<_, u8>(&mut reader, 3).unwrap(), 0b101);
+/// assert_eq!(bits.read::<_, u16>(&mut reader, 6).unwrap(), 0b10_1001);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct BitReader {
+    order: BitOrder,
+    byte: u8,
+    remaining: u32,
+}
+
+impl BitReader {
+    /// Creates a bit reader which consumes the bits of each byte in the given order.
+    pub fn new(order: BitOrder) -> Self {
+        Self {
+            order,
+            byte: 0,
+            remaining: 0,
+        }
+    }
+
+    /// Discards the unused bits of the current byte, so the next read starts at a byte boundary.
+    pub fn align(&mut self) {
+        self.remaining = 0;
+    }
+
+    /// Returns `true` if there are no unused bits left from the last byte read.
+    pub fn is_aligned(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Reads `count` bits and converts them into a `T`.
+    pub fn read<R: Read + Seek, T: BitField>(
+        &mut self,
+        reader: &mut R,
+        count: u32,
+    ) -> BinResult<T> {
+        if !count_in_range::<T>(count) {
+            return Err(count_error::<T>(reader.stream_pos()?, count));
+        }
+
+        Ok(T::from_bits(self.read_bits(reader, count)?, count))
+    }
+
+    /// The async version of [`read`](Self::read).
+    #[cfg(feature = "async")]
+    pub async fn read_async<R, T>(&mut self, reader: &mut R, count: u32) -> BinResult<T>
+    where
+        R: crate::io::AsyncRead + crate::io::AsyncSeek + Unpin,
+        T: BitField,
+    {
+        use futures_util::io::AsyncReadExt;
+
+        if !count_in_range::<T>(count) {
+            let pos = crate::__private::stream_pos_async(reader).await?;
+            return Err(count_error::<T>(pos, count));
+        }
+
+        let mut value = 0;
+        let mut done = 0;
+        while done < count {
+            if self.remaining == 0 {
+                let mut byte = [0];
+                reader.read_exact(&mut byte).await?;
+                self.load(byte[0]);
+            }
+
+            self.take(count, &mut done, &mut value);
+        }
+
+        Ok(T::from_bits(value, count))
+    }
+
+    /// Reads `count` bits, which must be no more than 64, into the low bits of an integer.
+    pub fn read_bits<R: Read>(&mut self, reader: &mut R, count: u32) -> io::Result<u64> {
+        let mut value = 0;
+        let mut done = 0;
+        while done < count {
+            if self.remaining == 0 {
+                let mut byte = [0];
+                reader.read_exact(&mut byte)?;
+                self.load(byte[0]);
+            }
+
+            self.take(count, &mut done, &mut value);
+        }
+
+        Ok(value)
+    }
+
+    fn load(&mut self, byte: u8) {
+        self.byte = byte;
+        self.remaining = 8;
+    }
+
+    /// Moves as many of the `count - done` bits still needed as are left in the current byte
+    /// into `value`.
+    fn take(&mut self, count: u32, done: &mut u32, value: &mut u64) {
+        let take = (count - *done).min(self.remaining);
+        match self.order {
+            BitOrder::Msb => {
+                let chunk = u64::from(self.byte >> (self.remaining - take)) & mask(take);
+                *value = shl(*value, take) | chunk;
+            }
+            BitOrder::Lsb => {
+                let chunk = u64::from(self.byte >> (8 - self.remaining)) & mask(take);
+                *value |= chunk << *done;
+            }
+        }
+
+        self.remaining -= take;
+        *done += take;
+    }
+}
+
+/// Writes values a number of bits at a time, collecting them into whole bytes.
+///
+/// Any bits which do not fill a whole byte are only written by [`flush`](Self::flush), with the
+/// rest of the byte set to zero.
+#[derive(Clone, Copy, Debug)]
+pub struct BitWriter {
+    order: BitOrder,
+    byte: u8,
+    filled: u32,
+}
+
+impl BitWriter {
+    /// Creates a bit writer which fills the bits of each byte in the given order.
+    pub fn new(order: BitOrder) -> Self {
+        Self {
+            order,
+            byte: 0,
+            filled: 0,
+        }
+    }
+
+    /// Writes the lowest `count` bits of `value`.
+    pub fn write<W: Write + Seek, T: BitField>(
+        &mut self,
+        writer: &mut W,
+        count: u32,
+        value: &T,
+    ) -> BinResult<()> {
+        if !count_in_range::<T>(count) {
+            return Err(count_error::<T>(writer.stream_pos()?, count));
+        }
+
+        let raw = match value.to_bits(count) {
+            Some(raw) => raw,
+            None => {
+                return Err(Error::AssertFail {
+                    pos: writer.stream_pos()?,
+                    message: format!("value does not fit in {} bits", count),
+                })
+            }
+        };
+
+        Ok(self.write_bits(writer, count, raw)?)
+    }
+
+    /// Writes the lowest `count` bits, which must be no more than 64, of `value`.
+    pub fn write_bits<W: Write>(
+        &mut self,
+        writer: &mut W,
+        count: u32,
+        value: u64,
+    ) -> io::Result<()> {
+        let mut done = 0;
+        while done < count {
+            let take = (count - done).min(8 - self.filled);
+            match self.order {
+                BitOrder::Msb => {
+                    let chunk = (value >> (count - done - take)) & mask(take);
+                    self.byte |= (chunk as u8) << (8 - self.filled - take);
+                }
+                BitOrder::Lsb => {
+                    let chunk = (value >> done) & mask(take);
+                    self.byte |= (chunk as u8) << self.filled;
+                }
+            }
+
+            self.filled += take;
+            done += take;
+            if self.filled == 8 {
+                self.flush(writer)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes out the current partial byte, if there is one.
+    pub fn flush<W: Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        if self.filled != 0 {
+            writer.write_all(&[self.byte])?;
+            self.byte = 0;
+            self.filled = 0;
+        }
+
+        Ok(())
+    }
+}
+
+fn count_in_range<T: BitField>(count: u32) -> bool {
+    count != 0 && count <= T::BITS
+}
+
+fn count_error<T: BitField>(pos: u64, count: u32) -> Error {
+    Error::AssertFail {
+        pos,
+        message: format!(
+            "bit count {} is out of range for a {}-bit value",
+            count,
+            T::BITS
+        ),
+    }
+}
+
+fn mask(count: u32) -> u64 {
+    if count >= 64 {
+        u64::MAX
+    } else {
+        (1 << count) - 1
+    }
+}
+
+fn shl(value: u64, count: u32) -> u64 {
+    if count >= 64 {
+        0
+    } else {
+        value << count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Cursor;
+
+    #[test]
+    fn read_msb_first() {
+        let mut reader = Cursor::new([0b1011_0100, 0b1100_0000]);
+        let mut bits = BitReader::new(BitOrder::Msb);
+        assert_eq!(bits.read_bits(&mut reader, 3).unwrap(), 0b101);
+        assert_eq!(bits.read_bits(&mut reader, 7).unwrap(), 0b101_0011);
+        assert!(!bits.is_aligned());
+        bits.align();
+        assert!(bits.is_aligned());
+    }
+
+    #[test]
+    fn read_lsb_first() {
+        let mut reader = Cursor::new([0b1011_0100, 0b0000_0011]);
+        let mut bits = BitReader::new(BitOrder::Lsb);
+        assert_eq!(bits.read_bits(&mut reader, 3).unwrap(), 0b100);
+        assert_eq!(bits.read_bits(&mut reader, 7).unwrap(), 0b111_0110);
+    }
+
+    #[test]
+    fn read_signed() {
+        let mut reader = Cursor::new([0b1110_0000]);
+        let mut bits = BitReader::new(BitOrder::Msb);
+        assert_eq!(bits.read::<_, i8>(&mut reader, 3).unwrap(), -1);
+        assert!(bits.read::<_, u8>(&mut reader, 9).is_err());
+    }
+
+    #[test]
+    fn write_round_trip() {
+        for &order in &[BitOrder::Msb, BitOrder::Lsb] {
+            let mut writer = Cursor::new(Vec::new());
+            let mut bits = BitWriter::new(order);
+            bits.write(&mut writer, 3, &5u8).unwrap();
+            bits.write(&mut writer, 7, &-20i16).unwrap();
+            bits.write(&mut writer, 1, &true).unwrap();
+            assert!(bits.write(&mut writer, 2, &4u8).is_err());
+            bits.flush(&mut writer).unwrap();
+            assert_eq!(writer.get_ref().len(), 2);
+
+            writer.set_position(0);
+            let mut bits = BitReader::new(order);
+            assert_eq!(bits.read::<_, u8>(&mut writer, 3).unwrap(), 5);
+            assert_eq!(bits.read::<_, i16>(&mut writer, 7).unwrap(), -20);
+            assert!(bits.read::<_, bool>(&mut writer, 1).unwrap());
+        }
+    }
+}
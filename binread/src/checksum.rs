@@ -0,0 +1,343 @@
+//! Checksums which are verified while reading.
+//!
+//! [`Checksummed`] reads a value followed by a stored checksum of the bytes
+//! of that value, and returns a [`BadChecksum`](crate::Error::BadChecksum)
+//! error if they do not match. The bytes are hashed as they are read by a
+//! [`ChecksumStream`](crate::io::ChecksumStream), which can also be used
+//! directly for checksums which cover more than one field:
+//!
+//! ```rust
+//! use binread::{prelude::*, checksum::{Adler32, Checksummed, Crc32}, io::Cursor, NullString};
+//!
+//! #[derive(BinRead, Debug)]
+//! #[br(big)]
+//! struct Record {
+//!     id: u8,
+//!     #[br(count = 3)]
+//!     payload: Checksummed<Vec<u8>, Crc32>,
+//!     name: Checksummed<NullString, Adler32>,
+//! }
+//!
+//! let data = b"\x01abc\x35\x24\x41\xC2hi\0\x02\x0D\x00\xD2";
+//! let record: Record = Cursor::new(data).read_be().unwrap();
+//! assert_eq!(*record.payload, b"abc");
+//!
+//! let bad_data = b"\x01abd\x35\x24\x41\xC2hi\0\x02\x0D\x00\xD2";
+//! let error = Cursor::new(bad_data).read_be::<Record>().unwrap_err();
+//! assert!(matches!(error.root_cause(), binread::Error::BadChecksum { pos: 4, .. }));
+//! ```
+//!
+//! The checksum is stored in the endianness of the reader, and written back
+//! by `BinWrite` after the value.
+
+use crate::io::{ChecksumStream, Read, Seek, SeekFrom, StreamPosition, Write};
+use crate::{BinRead, BinResult, BinWrite, Error, ReadOptions, WriteOptions};
+use core::fmt;
+use core::marker::PhantomData;
+
+/// A checksum algorithm, which is given the bytes being checked in order.
+pub trait Checksum: Default {
+    /// The type of the stored checksum.
+    type Output: for<'a> BinRead<Args<'a> = ()>
+        + for<'a> BinWrite<Args<'a> = ()>
+        + Into<u64>
+        + Copy
+        + PartialEq
+        + 'static;
+
+    /// Adds bytes to the checksum.
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the checksum of the bytes added so far.
+    fn finish(&self) -> Self::Output;
+}
+
+/// The CRC-32 checksum used by zlib, PNG and ZIP files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Crc32(u32);
+
+/// The Adler-32 checksum used by zlib streams.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ 0xEDB8_8320
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self(!0)
+    }
+}
+
+impl Checksum for Crc32 {
+    type Output = u32;
+
+    fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.0 = CRC32_TABLE[((self.0 ^ u32::from(byte)) & 0xFF) as usize] ^ (self.0 >> 8);
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        !self.0
+    }
+}
+
+/// The largest prime below 2^16
+const ADLER32_MOD: u32 = 65521;
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self { a: 1, b: 0 }
+    }
+}
+
+impl Checksum for Adler32 {
+    type Output = u32;
+
+    fn update(&mut self, data: &[u8]) {
+        // The sums can not overflow before being reduced within this many
+        // bytes
+        for chunk in data.chunks(5552) {
+            for &byte in chunk {
+                self.a += u32::from(byte);
+                self.b += self.a;
+            }
+            self.a %= ADLER32_MOD;
+            self.b %= ADLER32_MOD;
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+}
+
+macro_rules! sum_checksum_impl {
+    ($($name:ident($type_name:ty)),*$(,)?) => {
+        $(
+            #[doc = concat!(
+                "The sum of every byte, wrapping around on overflow, stored as a `",
+                stringify!($type_name),
+                "`."
+            )]
+            #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+            pub struct $name($type_name);
+
+            impl Checksum for $name {
+                type Output = $type_name;
+
+                fn update(&mut self, data: &[u8]) {
+                    for &byte in data {
+                        self.0 = self.0.wrapping_add(<$type_name>::from(byte));
+                    }
+                }
+
+                fn finish(&self) -> $type_name {
+                    self.0
+                }
+            }
+        )*
+    }
+}
+
+sum_checksum_impl!(Sum8(u8), Sum16(u16), Sum32(u32));
+
+/// A value followed by a checksum, of type `C`, of the bytes of the value.
+///
+/// Arguments are passed to the value. Reading returns a
+/// [`BadChecksum`](crate::Error::BadChecksum) error if the stored checksum
+/// does not match the bytes which were read. See the [module
+/// documentation](self) for an example.
+pub struct Checksummed<T, C>(pub T, PhantomData<C>);
+
+impl<T, C> Checksummed<T, C> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, C> From<T> for Checksummed<T, C> {
+    fn from(value: T) -> Self {
+        Checksummed(value, PhantomData)
+    }
+}
+
+impl<T: BinRead, C: Checksum> BinRead for Checksummed<T, C> {
+    type Args<'a> = T::Args<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = reader.stream_pos()?;
+        (|| {
+            let mut stream = ChecksumStream::<_, C>::new(&mut *reader);
+            let value = T::read_options(&mut stream, options, args)?;
+            let actual = stream.checksum();
+            let checksum_pos = reader.stream_pos()?;
+            let expected = C::Output::read_options(reader, options, ())?;
+            check_checksum::<C>(checksum_pos, expected, actual).map(|_| Self::from(value))
+        })()
+        .or_else(|err| {
+            reader.seek(SeekFrom::Start(pos))?;
+            Err(err)
+        })
+    }
+
+    fn after_parse<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.0.after_parse(reader, ro, args)
+    }
+}
+
+/// Compares a stored checksum at `pos` against the one computed from the data
+pub(crate) fn check_checksum<C: Checksum>(
+    pos: u64,
+    expected: C::Output,
+    actual: C::Output,
+) -> BinResult<()> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(Error::BadChecksum {
+            pos,
+            expected: expected.into(),
+            actual: actual.into(),
+        })
+    }
+}
+
+impl<T: BinWrite, C: Checksum> BinWrite for Checksummed<T, C> {
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let mut stream = ChecksumStream::<_, C>::new(&mut *writer);
+        self.0.write_options(&mut stream, options, args)?;
+        let checksum = stream.checksum();
+        checksum.write_options(writer, options, ())
+    }
+}
+
+// These are implemented by hand so they do not require the checksum type to
+// implement them as well
+impl<T: fmt::Debug, C> fmt::Debug for Checksummed<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Checksummed({:?})", self.0)
+    }
+}
+
+impl<T: Clone, C> Clone for Checksummed<T, C> {
+    fn clone(&self) -> Self {
+        self.0.clone().into()
+    }
+}
+
+impl<T: PartialEq, C> PartialEq for Checksummed<T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Default, C> Default for Checksummed<T, C> {
+    fn default() -> Self {
+        T::default().into()
+    }
+}
+
+impl<T, C> core::ops::Deref for Checksummed<T, C> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, C> core::ops::DerefMut for Checksummed<T, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Cursor;
+    use crate::{BinReaderExt, BinWriterExt};
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    fn checksum<C: Checksum>(data: &[u8]) -> C::Output {
+        let mut checksum = C::default();
+        checksum.update(data);
+        checksum.finish()
+    }
+
+    #[test]
+    fn algorithms() {
+        assert_eq!(checksum::<Crc32>(b"123456789"), 0xCBF4_3926);
+        assert_eq!(checksum::<Crc32>(b""), 0);
+        assert_eq!(checksum::<Adler32>(b"Wikipedia"), 0x11E6_0398);
+        assert_eq!(checksum::<Adler32>(&[0xFF; 10000]), 0xB623_EB2B);
+        assert_eq!(checksum::<Sum8>(&[0x80, 0x81]), 0x01);
+        assert_eq!(checksum::<Sum16>(&[0x80, 0x81]), 0x0101);
+        assert_eq!(checksum::<Sum32>(&[0xFF; 4]), 0x03FC);
+    }
+
+    #[test]
+    fn checksummed() {
+        let data = b"\x01\x02\x03\x00\x06";
+        let mut reader = Cursor::new(data);
+        let value: Checksummed<[u8; 3], Sum16> = reader.read_be().unwrap();
+        assert_eq!(*value, [1, 2, 3]);
+
+        let mut writer = Cursor::new(Vec::new());
+        writer.write_be(&value).unwrap();
+        assert_eq!(writer.into_inner(), data);
+
+        let mut reader = Cursor::new(b"\x01\x02\x03\x06\x00");
+        let error = reader.read_be::<Checksummed<[u8; 3], Sum16>>().unwrap_err();
+        assert!(matches!(
+            error,
+            Error::BadChecksum {
+                pos: 3,
+                expected: 0x0600,
+                actual: 0x0006
+            }
+        ));
+        assert_eq!(reader.position(), 0);
+    }
+}
@@ -0,0 +1,421 @@
+//! Reading and writing collections other than [`Vec`].
+//!
+//! Like `Vec`, these read the number of values given by the
+//! [`count`](crate::attribute#count) directive:
+//!
+//! * [`VecDeque`] passes its arguments to each value.
+//! * [`String`] reads `count` bytes of UTF-8.
+//! * Maps read `count` key-value pairs, with the key first, and sets read
+//!   `count` values. Their arguments are the [`DuplicateKeys`] policy, which
+//!   defaults to returning an error, and their keys and values can not take
+//!   arguments.
+//!
+//! ```rust
+//! use binread::{prelude::*, collections::DuplicateKeys, io::Cursor};
+//! use std::collections::{BTreeMap, HashSet};
+//!
+//! #[derive(BinRead)]
+//! struct Index {
+//!     len: u8,
+//!     #[br(count = len)]
+//!     offsets: BTreeMap<u8, u16>,
+//!     #[br(count = 3, args_tuple = DuplicateKeys::FirstWins)]
+//!     flags: HashSet<u8>,
+//! }
+//!
+//! let index: Index = Cursor::new(b"\x02\x01\x00\x10\x02\x00\x20\x05\x06\x05").read_be().unwrap();
+//! assert_eq!(index.offsets[&2], 0x20);
+//! assert_eq!(index.flags.len(), 2);
+//!
+//! let duplicate = Cursor::new(b"\x02\x01\x00\x10\x01\x00\x20\x05\x06\x07").read_be::<Index>();
+//! assert!(matches!(
+//!     duplicate.map(|_| ()).unwrap_err().root_cause(),
+//!     binread::Error::AssertFail { pos: 4, .. }
+//! ));
+//! ```
+//!
+//! Maps and sets are written in their iteration order, so a
+//! [`HashMap`](std::collections::HashMap) may not be written back in the
+//! order it was read.
+
+use crate::alloc::collections::{btree_map, BTreeMap, BTreeSet, VecDeque};
+use crate::error::{index_context, limit_exceeded};
+use crate::io::{Read, Seek, SeekFrom, StreamPosition, Write};
+use crate::strings::{decode_error, Utf8};
+use crate::{BinRead, BinResult, BinWrite, DefaultArgs, Error, ReadOptions, WriteOptions};
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::{
+    collections::{hash_map, HashMap, HashSet},
+    hash::{BuildHasher, Hash},
+};
+
+/// What to do when a map or set reads a key which it already contains.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    /// Return an [`AssertFail`](crate::Error::AssertFail) error at the
+    /// position of the duplicate entry.
+    Error,
+    /// Keep the entry which was read first.
+    FirstWins,
+    /// Replace the earlier entry with the one read last.
+    LastWins,
+}
+
+impl DefaultArgs for DuplicateKeys {
+    fn default_args() -> Self {
+        DuplicateKeys::Error
+    }
+}
+
+/// A map or set which is read one entry at a time
+pub(crate) trait Collection: Default {
+    type Item: for<'a> BinRead<Args<'a> = ()>;
+
+    /// Adds an item, following the policy if its key is already present, and
+    /// returns whether it was a duplicate
+    fn insert_item(&mut self, item: Self::Item, policy: DuplicateKeys) -> bool;
+}
+
+fn read_collection<R: Read + Seek, C: Collection>(
+    reader: &mut R,
+    options: &ReadOptions,
+    policy: DuplicateKeys,
+    type_name: &str,
+) -> BinResult<C> {
+    let mut options = *options;
+    let count = match options.count.take() {
+        Some(x) => x,
+        None => panic!("Missing count for {}", type_name),
+    };
+    options
+        .limits
+        .check_count::<C::Item>(count)
+        .map_err(|limit| limit_exceeded(reader, limit))?;
+
+    let mut collection = C::default();
+    for i in 0..count {
+        let pos = reader.stream_pos()?;
+        // Keys can not be changed once they are in the collection, so each
+        // entry is finished before it is inserted
+        let item = C::Item::read_options(reader, &options, ())
+            .and_then(|mut item| {
+                item.after_parse(reader, &options, ())?;
+                Ok(item)
+            })
+            .map_err(|e| index_context(reader, e, i))?;
+
+        if collection.insert_item(item, policy) && policy == DuplicateKeys::Error {
+            return Err(index_context(
+                reader,
+                duplicate_key_error(pos, type_name),
+                i,
+            ));
+        }
+    }
+
+    Ok(collection)
+}
+
+pub(crate) fn duplicate_key_error(pos: u64, type_name: &str) -> Error {
+    Error::AssertFail {
+        pos,
+        message: format!("duplicate key in {}", type_name),
+    }
+}
+
+macro_rules! map_impl {
+    ($map:ident, $entry:ident, [$($bounds:tt)*] $(, $hasher:ident: [$($hasher_bounds:tt)*])?) => {
+        impl<K, V $(, $hasher)?> Collection for $map<K, V $(, $hasher)?>
+        where
+            K: for<'a> BinRead<Args<'a> = ()> + $($bounds)*,
+            V: for<'a> BinRead<Args<'a> = ()>,
+            $($hasher: $($hasher_bounds)*,)?
+        {
+            type Item = (K, V);
+
+            fn insert_item(&mut self, (key, value): (K, V), policy: DuplicateKeys) -> bool {
+                match self.entry(key) {
+                    $entry::Entry::Occupied(mut entry) => {
+                        if policy == DuplicateKeys::LastWins {
+                            entry.insert(value);
+                        }
+                        true
+                    }
+                    $entry::Entry::Vacant(entry) => {
+                        entry.insert(value);
+                        false
+                    }
+                }
+            }
+        }
+
+        impl<K, V $(, $hasher)?> BinRead for $map<K, V $(, $hasher)?>
+        where
+            K: for<'a> BinRead<Args<'a> = ()> + $($bounds)*,
+            V: for<'a> BinRead<Args<'a> = ()>,
+            $($hasher: $($hasher_bounds)*,)?
+        {
+            type Args<'a> = DuplicateKeys;
+
+            fn read_options<R: Read + Seek>(
+                reader: &mut R,
+                options: &ReadOptions,
+                policy: Self::Args<'_>,
+            ) -> BinResult<Self> {
+                read_collection(reader, options, policy, stringify!($map))
+            }
+        }
+
+        impl<K, V $(, $hasher)?> BinWrite for $map<K, V $(, $hasher)?>
+        where
+            K: for<'a> BinWrite<Args<'a> = ()>,
+            V: for<'a> BinWrite<Args<'a> = ()>,
+        {
+            type Args<'a> = DuplicateKeys;
+
+            fn write_options<W: Write + Seek>(
+                &self,
+                writer: &mut W,
+                options: &WriteOptions,
+                _: Self::Args<'_>,
+            ) -> BinResult<()> {
+                for (key, value) in self {
+                    key.write_options(writer, options, ())?;
+                    value.write_options(writer, options, ())?;
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+macro_rules! set_impl {
+    ($set:ident, [$($bounds:tt)*] $(, $hasher:ident: [$($hasher_bounds:tt)*])?) => {
+        impl<T $(, $hasher)?> Collection for $set<T $(, $hasher)?>
+        where
+            T: for<'a> BinRead<Args<'a> = ()> + $($bounds)*,
+            $($hasher: $($hasher_bounds)*,)?
+        {
+            type Item = T;
+
+            fn insert_item(&mut self, item: T, policy: DuplicateKeys) -> bool {
+                if !self.contains(&item) {
+                    self.insert(item);
+                    false
+                } else {
+                    if policy == DuplicateKeys::LastWins {
+                        self.replace(item);
+                    }
+                    true
+                }
+            }
+        }
+
+        impl<T $(, $hasher)?> BinRead for $set<T $(, $hasher)?>
+        where
+            T: for<'a> BinRead<Args<'a> = ()> + $($bounds)*,
+            $($hasher: $($hasher_bounds)*,)?
+        {
+            type Args<'a> = DuplicateKeys;
+
+            fn read_options<R: Read + Seek>(
+                reader: &mut R,
+                options: &ReadOptions,
+                policy: Self::Args<'_>,
+            ) -> BinResult<Self> {
+                read_collection(reader, options, policy, stringify!($set))
+            }
+        }
+
+        impl<T $(, $hasher)?> BinWrite for $set<T $(, $hasher)?>
+        where
+            T: for<'a> BinWrite<Args<'a> = ()>,
+        {
+            type Args<'a> = DuplicateKeys;
+
+            fn write_options<W: Write + Seek>(
+                &self,
+                writer: &mut W,
+                options: &WriteOptions,
+                _: Self::Args<'_>,
+            ) -> BinResult<()> {
+                for item in self {
+                    item.write_options(writer, options, ())?;
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+map_impl!(BTreeMap, btree_map, [Ord]);
+set_impl!(BTreeSet, [Ord]);
+#[cfg(feature = "std")]
+map_impl!(HashMap, hash_map, [Eq + Hash], S: [BuildHasher + Default]);
+#[cfg(feature = "std")]
+set_impl!(HashSet, [Eq + Hash], S: [BuildHasher + Default]);
+
+impl<B: BinRead> BinRead for VecDeque<B> {
+    type Args<'a> = B::Args<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Vec::<B>::read_options(reader, options, args).map(VecDeque::from)
+    }
+
+    fn after_parse<R>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()>
+    where
+        R: Read + Seek,
+    {
+        for (i, val) in self.iter_mut().enumerate() {
+            val.after_parse(reader, ro, args.clone())
+                .map_err(|e| index_context(reader, e, i))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<B: BinWrite> BinWrite for VecDeque<B> {
+    type Args<'a> = B::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        for val in self {
+            val.write_options(writer, options, args.clone())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl BinRead for String {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let count = match options.count {
+            Some(x) => x,
+            None => panic!("Missing count for String"),
+        };
+        options
+            .limits
+            .check_count::<u8>(count)
+            .map_err(|limit| limit_exceeded(reader, limit))?;
+
+        let pos = reader.stream_pos()?;
+        let mut bytes = vec![0; count];
+        reader
+            .read_exact(&mut bytes)
+            .map_err(Error::from)
+            .and_then(|_| String::from_utf8(bytes).map_err(|_| decode_error::<Utf8>(pos)))
+            .or_else(|err| {
+                reader.seek(SeekFrom::Start(pos))?;
+                Err(err)
+            })
+    }
+}
+
+impl BinWrite for String {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.as_bytes().write_options(writer, options, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Cursor;
+    use crate::BinWriterExt;
+
+    fn options(count: usize) -> ReadOptions {
+        ReadOptions {
+            count: Some(count),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        let data = b"\x01\x0A\x02\x0B\x01\x0C";
+        let read =
+            |policy| BTreeMap::<u8, u8>::read_options(&mut Cursor::new(data), &options(3), policy);
+
+        let first = read(DuplicateKeys::FirstWins).unwrap();
+        assert_eq!(
+            first.into_iter().collect::<Vec<_>>(),
+            [(1, 0x0A), (2, 0x0B)]
+        );
+        let last = read(DuplicateKeys::LastWins).unwrap();
+        assert_eq!(last.into_iter().collect::<Vec<_>>(), [(1, 0x0C), (2, 0x0B)]);
+        let error = read(DuplicateKeys::Error).unwrap_err();
+        assert!(matches!(
+            error.root_cause(),
+            Error::AssertFail { pos: 4, .. }
+        ));
+
+        let set = BTreeSet::<u8>::read_options(
+            &mut Cursor::new(data),
+            &options(6),
+            DuplicateKeys::LastWins,
+        )
+        .unwrap();
+        assert_eq!(
+            set.into_iter().collect::<Vec<_>>(),
+            [1, 2, 0x0A, 0x0B, 0x0C]
+        );
+    }
+
+    #[test]
+    fn string() {
+        let mut reader = Cursor::new(b"abc\xFF");
+        let value = String::read_options(&mut reader, &options(3), ()).unwrap();
+        assert_eq!(value, "abc");
+
+        let mut reader = Cursor::new(b"ab\xFF");
+        let error = String::read_options(&mut reader, &options(3), ()).unwrap_err();
+        assert!(matches!(error, Error::BadEncoding { pos: 0, .. }));
+        assert_eq!(reader.position(), 0);
+
+        let mut writer = Cursor::new(Vec::new());
+        writer.write_le(&value).unwrap();
+        assert_eq!(writer.into_inner(), b"abc");
+    }
+
+    #[test]
+    fn vec_deque() {
+        let data = b"\x01\x00\x02\x00";
+        let value = VecDeque::<u16>::read_options(&mut Cursor::new(data), &options(2), ()).unwrap();
+        assert_eq!(value, [1, 2]);
+
+        let mut writer = Cursor::new(Vec::new());
+        writer.write_le(&value).unwrap();
+        assert_eq!(writer.into_inner(), data);
+    }
+}
@@ -1,13 +1,45 @@
-//! An enum to represent what endianness to read as
+//! An enum to represent what endianness to read as, and wrappers which always
+//! read in one endianness.
+//!
+//! [`Be`] and [`Le`] ignore the endianness they are read with, which is useful
+//! for the few big-endian values of a mostly little-endian format, or the
+//! other way around, without an attribute on each field:
+//!
+//! ```rust
+//! use binread::{prelude::*, endian::{Be, Le}, io::Cursor};
+//!
+//! #[derive(BinRead, BinWrite)]
+//! struct Packet {
+//!     length: u16,
+//!     #[br(count = 2)]
+//!     addresses: Vec<Be<u32>>,
+//! }
+//!
+//! let data = b"\x08\x00\x7F\x00\x00\x01\xC0\xA8\x00\x01";
+//! let packet: Packet = Cursor::new(data).read_le().unwrap();
+//! assert_eq!(packet.length, 8);
+//! assert_eq!(*packet.addresses[0], 0x7F00_0001);
+//! assert_eq!(packet.addresses[1].into_inner(), 0xC0A8_0001);
+//!
+//! let mut writer = Cursor::new(Vec::new());
+//! writer.write_le(&packet).unwrap();
+//! assert_eq!(writer.into_inner(), data);
+//!
+//! let value: Le<u16> = Cursor::new(b"\x01\x00").read_be().unwrap();
+//! assert_eq!(*value, 1);
+//! ```
 
+use crate::io::{Read, Seek, Write};
+use crate::{BinRead, BinResult, BinWrite, ReadOptions, WriteOptions};
 #[cfg(not(feature = "std"))]
 use alloc::string::String;
 
 /// An enum to represent what endianness to read as
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub enum Endian {
     Big,
     Little,
+    #[default]
     Native,
 }
 
@@ -23,15 +55,80 @@ impl From<&Endian> for String {
     }
 }
 
-impl Default for Endian {
-    fn default() -> Endian {
-        Endian::Native
-    }
+macro_rules! fixed_endian_impl {
+    ($($name:ident($endian:ident, $desc:literal)),*$(,)?) => {
+        $(
+            #[doc = concat!(
+                "A value which is always read and written ", $desc,
+                ", whatever the endianness of the reader or writer."
+            )]
+            ///
+            /// Arguments are passed to the value, and values inside it are read
+            /// in the same endianness. See the [module documentation](self) for
+            /// an example.
+            #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            pub struct $name<T>(pub T);
+
+            impl<T> $name<T> {
+                /// The endianness the value is read and written in
+                pub const ENDIAN: Endian = Endian::$endian;
+
+                pub(crate) fn fixed_options(options: &ReadOptions) -> ReadOptions {
+                    let mut options = *options;
+                    options.endian = Self::ENDIAN;
+                    options
+                }
+            }
+
+            impl<T: BinRead> BinRead for $name<T> {
+                type Args<'a> = T::Args<'a>;
+
+                fn read_options<R: Read + Seek>(
+                    reader: &mut R,
+                    options: &ReadOptions,
+                    args: Self::Args<'_>,
+                ) -> BinResult<Self> {
+                    T::read_options(reader, &Self::fixed_options(options), args).map($name)
+                }
+
+                fn after_parse<R: Read + Seek>(
+                    &mut self,
+                    reader: &mut R,
+                    options: &ReadOptions,
+                    args: Self::Args<'_>,
+                ) -> BinResult<()> {
+                    self.0.after_parse(reader, &Self::fixed_options(options), args)
+                }
+            }
+
+            impl<T: BinWrite> BinWrite for $name<T> {
+                type Args<'a> = T::Args<'a>;
+
+                fn write_options<W: Write + Seek>(
+                    &self,
+                    writer: &mut W,
+                    options: &WriteOptions,
+                    args: Self::Args<'_>,
+                ) -> BinResult<()> {
+                    let mut options = *options;
+                    options.endian = Self::ENDIAN;
+                    self.0.write_options(writer, &options, args)
+                }
+            }
+        )*
+    };
 }
 
+fixed_endian_impl!(Be(Big, "big-endian"), Le(Little, "little-endian"));
+wrapper_impl!(Be, Le);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::io::Cursor;
+    use crate::{BinReaderExt, BinWriterExt, FilePtr8};
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
 
     #[test]
     fn endian_to_string() {
@@ -46,4 +143,23 @@ mod tests {
             assert_eq!(converted, string)
         }
     }
+
+    #[test]
+    fn fixed_endian() {
+        let data = b"\x00\x01\x00\x02\x03\x00";
+        let mut reader = Cursor::new(data);
+        let values: [Be<u16>; 2] = reader.read_le().unwrap();
+        assert_eq!(values, [Be(1), Be(2)]);
+        assert_eq!(reader.read_be::<Le<u16>>().unwrap().into_inner(), 3);
+
+        let mut writer = Cursor::new(Vec::new());
+        writer.write_le(&values).unwrap();
+        writer.write_be(&Le(3u16)).unwrap();
+        assert_eq!(writer.into_inner(), data);
+
+        // Values pointed to are read in the same endianness
+        let mut reader = Cursor::new(b"\x01\x02\x00");
+        let ptr: Le<FilePtr8<u16>> = reader.read_be().unwrap();
+        assert_eq!(**ptr, 2);
+    }
 }
@@ -1,13 +1,24 @@
 //! Error types and internal error handling functions
 use super::*;
+use crate::options::LimitError;
+use core::any::Any;
+
+mod report;
+
+pub use report::Report;
 
 /// An error while parsing a BinRead type
+///
+/// Use [`report`](Error::report) or [`report_from`](Error::report_from) to
+/// render the error along with a hex dump of the data where it occurred.
 #[non_exhaustive]
 pub enum Error {
     /// The magic value did not match the provided one
     BadMagic {
         // Position in number of bytes from the start of the reader
         pos: u64,
+        // The value expected. Use [`Any::downcast_ref`](core::any::Any::downcast_ref) to access
+        expected: Box<dyn Any + Sync + Send>,
         // The value found. Use [`Any::downcast_ref`](core::any::Any::downcast_ref) to access
         found: Box<dyn Any + Sync + Send>,
     },
@@ -20,24 +31,149 @@ pub enum Error {
         pos: u64,
         err: Box<dyn Any + Sync + Send>,
     },
+    /// A string was not valid in its encoding, or could not be represented in
+    /// it when writing
+    BadEncoding {
+        pos: u64,
+        /// The name of the encoding, e.g. `"Shift_JIS"`
+        encoding: &'static str,
+    },
+    /// A stored checksum did not match the checksum of the data it covers
+    BadChecksum {
+        pos: u64,
+        /// The checksum which was stored
+        expected: u64,
+        /// The checksum of the data which was read
+        actual: u64,
+    },
+    /// An integer in the data was too large for the type it was read into,
+    /// such as a [variable-length integer](crate::varint) with too many bytes
+    Overflow {
+        pos: u64,
+        /// The name of the type, e.g. `"Leb128<u32>"`
+        type_name: &'static str,
+    },
+    /// A value was over one of the [`Limits`] set for reading
+    LimitExceeded {
+        pos: u64,
+        /// The name of the limit, e.g. `"max_count"`
+        limit: &'static str,
+        /// The value of the limit
+        max: u64,
+    },
+    /// A [`SharedFilePtr`](crate::file_ptr::SharedFilePtr) led back to a value
+    /// which was still being read, such as a cycle in a linked list
+    PointerCycle { pos: u64 },
+    /// A [`SharedFilePtr`](crate::file_ptr::SharedFilePtr) led to a value which
+    /// was already read with a different byte order, count or arguments
+    PointerConflict { pos: u64 },
     /// No variant in the enum was successful in parsing the data
     NoVariantMatch { pos: u64 },
     EnumErrors {
         pos: u64,
         variant_errors: Vec<(/*variant name*/ &'static str, Error)>,
     },
+    /// An error along with the fields and elements that were being read when it occurred
+    Backtrace(Backtrace),
+}
+
+/// An error along with context describing where in the data it occurred.
+///
+/// Frames are added as the error propagates out of each field and collection
+/// being read, so the first frame is the innermost one. When displayed, the
+/// frames are printed as a path followed by the original error:
+///
+/// ```text
+/// Header.entries[12].name @ 0x3F0: UnexpectedEof
+/// ```
+pub struct Backtrace {
+    /// The original error
+    pub error: Box<Error>,
+    /// The context frames, from innermost to outermost
+    pub frames: Vec<ContextFrame>,
+}
+
+/// A single frame of context in a [`Backtrace`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContextFrame {
+    /// A field of a struct or enum variant
+    Field {
+        /// The name of the type containing the field, e.g. `Header` or `Command::Move`
+        type_name: &'static str,
+        /// The name of the field
+        field: &'static str,
+        /// The position of the reader when the error occurred
+        pos: u64,
+        /// The source line of the field's `#[br]` attribute
+        line: u32,
+    },
+    /// An element of a collection
+    Index {
+        /// The index of the element
+        index: usize,
+        /// The position of the reader when the error occurred
+        pos: u64,
+    },
+}
+
+impl ContextFrame {
+    /// The position of the reader when the error occurred
+    pub fn pos(&self) -> u64 {
+        match self {
+            Self::Field { pos, .. } | Self::Index { pos, .. } => *pos,
+        }
+    }
 }
 
 impl Error {
     /// Gets a custom error of type T from the Error. Returns `None` if the error type is not
     /// custom or if the contained error is not of the desired type.
     pub fn custom_err<T: Any>(&self) -> Option<&T> {
-        if let Error::Custom { err, .. } = self {
+        if let Error::Custom { err, .. } = self.root_cause() {
             err.downcast_ref()
         } else {
             None
         }
     }
+
+    /// Adds a frame of context to the error, wrapping it in a
+    /// [`Backtrace`](Error::Backtrace) if it does not have one yet.
+    pub fn with_context(self, frame: ContextFrame) -> Self {
+        match self {
+            Error::Backtrace(mut backtrace) => {
+                backtrace.frames.push(frame);
+                Error::Backtrace(backtrace)
+            }
+            error => Error::Backtrace(Backtrace {
+                error: Box::new(error),
+                frames: crate::alloc::vec![frame],
+            }),
+        }
+    }
+
+    /// Returns the original error, without any [`Backtrace`](Error::Backtrace)
+    /// context.
+    pub fn root_cause(&self) -> &Error {
+        match self {
+            Error::Backtrace(backtrace) => &backtrace.error,
+            error => error,
+        }
+    }
+}
+
+/// Adds the current position and index of an element to an error from reading
+/// a collection
+pub(crate) fn index_context<R: io::Seek>(reader: &mut R, error: Error, index: usize) -> Error {
+    // The position is only used for display, so a failure to get it should not
+    // hide the original error
+    let pos = reader.stream_pos().unwrap_or_default();
+    error.with_context(ContextFrame::Index { index, pos })
+}
+
+/// Creates an error at the current position for a value over one of the
+/// [`Limits`](crate::Limits)
+pub(crate) fn limit_exceeded<R: io::Seek>(reader: &mut R, limit: LimitError) -> Error {
+    limit.at(reader.stream_pos().unwrap_or_default())
 }
 
 impl From<io::Error> for Error {
@@ -60,6 +196,32 @@ impl fmt::Debug for Error {
             }
             Self::Io(err) => write!(f, "Io({:?})", err),
             Self::Custom { pos, err } => write!(f, "Custom {{ pos: 0x{:X}, err: {:?} }}", pos, err),
+            Self::BadEncoding { pos, encoding } => write!(
+                f,
+                "BadEncoding {{ pos: 0x{:X}, encoding: {:?} }}",
+                pos, encoding
+            ),
+            Self::BadChecksum {
+                pos,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "BadChecksum {{ pos: 0x{:X}, expected: 0x{:X}, actual: 0x{:X} }}",
+                pos, expected, actual
+            ),
+            Self::Overflow { pos, type_name } => write!(
+                f,
+                "Overflow {{ pos: 0x{:X}, type_name: {:?} }}",
+                pos, type_name
+            ),
+            Self::LimitExceeded { pos, limit, max } => write!(
+                f,
+                "LimitExceeded {{ pos: 0x{:X}, limit: {:?}, max: {} }}",
+                pos, limit, max
+            ),
+            Self::PointerCycle { pos } => write!(f, "PointerCycle {{ pos: 0x{:X} }}", pos),
+            Self::PointerConflict { pos } => write!(f, "PointerConflict {{ pos: 0x{:X} }}", pos),
             Self::NoVariantMatch { pos } => write!(f, "NoVariantMatch {{ pos: 0x{:X} }}", pos),
             Self::EnumErrors {
                 pos,
@@ -69,35 +231,96 @@ impl fmt::Debug for Error {
                 "EnumErrors {{ pos: 0x{:X}, variant_errors: {:?} }}",
                 pos, variant_errors
             ),
+            Self::Backtrace(backtrace) => fmt::Debug::fmt(backtrace, f),
         }
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Debug::fmt(self, f)
+        match self {
+            Self::Backtrace(backtrace) => fmt::Display::fmt(backtrace, f),
+            _ => fmt::Debug::fmt(self, f),
+        }
+    }
+}
+
+impl Backtrace {
+    /// Writes the path of the frames and the position of the innermost one,
+    /// e.g. `Header.entries[12].name @ 0x3F0`
+    fn fmt_path(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ContextFrame::Field { type_name, .. }) = self.frames.last() {
+            write!(f, "{}", type_name)?;
+        }
+
+        for frame in self.frames.iter().rev() {
+            match frame {
+                ContextFrame::Field { field, .. } => write!(f, ".{}", field)?,
+                ContextFrame::Index { index, .. } => write!(f, "[{}]", index)?,
+            }
+        }
+
+        if let Some(frame) = self.frames.first() {
+            write!(f, " @ 0x{:X}", frame.pos())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_path(f)?;
+        match &*self.error {
+            Error::Io(err) => write!(f, ": {:?}", err.kind()),
+            error => write!(f, ": {:?}", error),
+        }
+    }
+}
+
+impl fmt::Debug for Backtrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)?;
+        for frame in &self.frames {
+            if let ContextFrame::Field {
+                type_name,
+                field,
+                pos,
+                line,
+            } = frame
+            {
+                write!(
+                    f,
+                    "\n    in {}.{} at 0x{:X} (line {})",
+                    type_name, field, pos, line
+                )?;
+            }
+        }
+
+        Ok(())
     }
 }
 
 /// Read a value then check if it is the expected value
 pub fn magic<R, B>(reader: &mut R, expected: B, options: &ReadOptions) -> BinResult<()>
 where
-    B: BinRead<Args = ()> + PartialEq + Sync + Send + 'static,
+    B: for<'a> BinRead<Args<'a> = ()> + PartialEq + Sync + Send + 'static,
     R: io::Read + io::Seek,
 {
     let pos = reader.stream_pos()?;
     #[cfg(feature = "debug_template")]
-    let options = {
+    let options = &{
         let mut options = *options;
         options.variable_name = Some("magic");
         options
     };
-    let val = B::read_options(reader, &options, ())?;
+    let val = B::read_options(reader, options, ())?;
     if val == expected {
         Ok(())
     } else {
         Err(Error::BadMagic {
             pos,
+            expected: Box::new(expected) as _,
             found: Box::new(val) as _,
         })
     }
@@ -131,17 +354,16 @@ where
     }
 }
 
-pub fn read_options_then_after_parse<Args, T, R>(
+pub fn read_options_then_after_parse<T, R>(
     reader: &mut R,
     ro: &ReadOptions,
-    args: T::Args,
+    args: T::Args<'_>,
 ) -> BinResult<T>
 where
-    Args: Copy + 'static,
-    T: BinRead<Args = Args>,
+    T: BinRead,
     R: Read + Seek,
 {
-    let mut val = T::read_options(reader, ro, args)?;
+    let mut val = T::read_options(reader, ro, args.clone())?;
     val.after_parse(reader, ro, args)?;
     Ok(val)
 }
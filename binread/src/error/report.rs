@@ -0,0 +1,362 @@
+//! Annotated hex dumps of the data around an error
+
+use super::*;
+use core::fmt::Write as _;
+
+/// The number of bytes shown on each line of the hex dump
+const LINE_LEN: u64 = 16;
+
+/// The number of lines shown before and after the line containing the error
+const CONTEXT_LINES: u64 = 2;
+
+/// A human-readable report of an [`Error`] along with a hex dump of the data
+/// around where it occurred.
+///
+/// Created by [`Error::report`] or [`Error::report_from`]. The report is
+/// rendered by its [`Display`](core::fmt::Display) implementation:
+///
+/// ```text
+/// Header.magic @ 0x10: bad magic at 0x10
+///   expected: b"TEST"
+///   found:    b"TEXT"
+///
+///   00000000  00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00  |................|
+/// > 00000010  54 45 58 54 01 00 00 00                          |TEXT....|
+///             ^^ ^^ ^^ ^^
+/// ```
+pub struct Report<'a> {
+    error: &'a Error,
+    start: u64,
+    data: Vec<u8>,
+}
+
+impl Error {
+    /// Returns the position of the error, if it has one. For errors with a
+    /// [`Backtrace`](Error::Backtrace) but no position of their own, this is
+    /// the position of the innermost frame.
+    pub fn pos(&self) -> Option<u64> {
+        match self {
+            Error::BadMagic { pos, .. }
+            | Error::AssertFail { pos, .. }
+            | Error::Custom { pos, .. }
+            | Error::BadEncoding { pos, .. }
+            | Error::BadChecksum { pos, .. }
+            | Error::Overflow { pos, .. }
+            | Error::LimitExceeded { pos, .. }
+            | Error::PointerCycle { pos }
+            | Error::PointerConflict { pos }
+            | Error::NoVariantMatch { pos }
+            | Error::EnumErrors { pos, .. } => Some(*pos),
+            Error::Io(_) => None,
+            Error::Backtrace(backtrace) => backtrace
+                .error
+                .pos()
+                .or_else(|| backtrace.frames.first().map(ContextFrame::pos)),
+        }
+    }
+
+    /// Creates a report of the error with a hex dump of `data`, which should
+    /// be all of the data that was being read.
+    ///
+    /// ```rust
+    /// # use binread::{prelude::*, io::Cursor};
+    /// #[derive(BinRead, Debug)]
+    /// #[br(magic = b"TEST")]
+    /// struct Test {
+    ///     value: u32,
+    /// }
+    ///
+    /// let data = b"TEXT\x01\0\0\0";
+    /// let error = Cursor::new(data).read_le::<Test>().unwrap_err();
+    /// let report = error.report(data).to_string();
+    /// assert!(report.contains("expected: b\"TEST\""));
+    /// assert!(report.contains("found:    b\"TEXT\""));
+    /// ```
+    pub fn report(&self, data: &[u8]) -> Report<'_> {
+        let (start, end) = self.window();
+        let len = data.len() as u64;
+        let data = if start < len {
+            data[start as usize..end.min(len) as usize].to_vec()
+        } else {
+            Vec::new()
+        };
+
+        Report {
+            error: self,
+            start,
+            data,
+        }
+    }
+
+    /// Creates a report of the error with a hex dump of the data around the
+    /// error in `reader`. The position of the reader is restored afterwards.
+    pub fn report_from<R: Read + Seek>(&self, reader: &mut R) -> io::Result<Report<'_>> {
+        let (start, end) = self.window();
+        let saved_pos = reader.stream_pos()?;
+        reader.seek(SeekFrom::Start(start))?;
+
+        let mut data = crate::alloc::vec![0; (end - start) as usize];
+        let mut filled = 0;
+        let result = loop {
+            match reader.read(&mut data[filled..]) {
+                Ok(0) => break Ok(()),
+                Ok(n) => {
+                    filled += n;
+                    if filled == data.len() {
+                        break Ok(());
+                    }
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => break Err(err),
+            }
+        };
+
+        reader.seek(SeekFrom::Start(saved_pos))?;
+        result?;
+        data.truncate(filled);
+
+        Ok(Report {
+            error: self,
+            start,
+            data,
+        })
+    }
+
+    /// The range of the data shown in a report
+    fn window(&self) -> (u64, u64) {
+        match self.pos() {
+            Some(pos) => {
+                let line = pos - pos % LINE_LEN;
+                let start = line.saturating_sub(CONTEXT_LINES * LINE_LEN);
+                (start, line.saturating_add((CONTEXT_LINES + 1) * LINE_LEN))
+            }
+            None => (0, 0),
+        }
+    }
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_summary(f, self.error)?;
+        write_details(f, self.error, 1)?;
+
+        let pos = match self.error.pos() {
+            Some(pos) => pos,
+            None => return Ok(()),
+        };
+
+        writeln!(f)?;
+        if self.data.is_empty() {
+            return write!(f, "\n  no data at 0x{:X}", pos);
+        }
+
+        let highlight_len = highlight_len(self.error).max(1);
+        for (i, line) in self.data.chunks(LINE_LEN as usize).enumerate() {
+            let line_start = self.start + i as u64 * LINE_LEN;
+            let line_end = line_start.saturating_add(LINE_LEN);
+            let has_error = (line_start..line_end).contains(&pos);
+
+            write!(
+                f,
+                "\n{} {:08X}  ",
+                if has_error { '>' } else { ' ' },
+                line_start
+            )?;
+            for offset in 0..LINE_LEN as usize {
+                match line.get(offset) {
+                    Some(byte) => write!(f, "{:02X} ", byte)?,
+                    None => f.write_str("   ")?,
+                }
+            }
+
+            f.write_str(" |")?;
+            for &byte in line {
+                let c = if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
+                } else {
+                    '.'
+                };
+                f.write_char(c)?;
+            }
+            f.write_char('|')?;
+
+            if has_error {
+                f.write_str("\n            ")?;
+                let first = (pos - line_start) as usize;
+                let last = (first + highlight_len).min(line.len().max(first + 1));
+                for offset in 0..last {
+                    if offset != 0 {
+                        f.write_char(' ')?;
+                    }
+                    f.write_str(if offset < first { "  " } else { "^^" })?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a single line describing an error
+fn write_summary(f: &mut fmt::Formatter<'_>, error: &Error) -> fmt::Result {
+    match error {
+        Error::BadMagic { pos, .. } => write!(f, "bad magic at 0x{:X}", pos),
+        Error::AssertFail { pos, .. } => write!(f, "assertion failed at 0x{:X}", pos),
+        Error::Io(err) => write!(f, "io error: {:?}", err.kind()),
+        Error::Custom { pos, .. } => write!(f, "custom error at 0x{:X}", pos),
+        Error::BadEncoding { pos, encoding } => {
+            write!(f, "invalid {} string at 0x{:X}", encoding, pos)
+        }
+        Error::BadChecksum { pos, .. } => write!(f, "bad checksum at 0x{:X}", pos),
+        Error::Overflow { pos, type_name } => {
+            write!(f, "{} overflowed at 0x{:X}", type_name, pos)
+        }
+        Error::LimitExceeded { pos, limit, .. } => write!(f, "{} exceeded at 0x{:X}", limit, pos),
+        Error::PointerCycle { pos } => write!(f, "pointer cycle at 0x{:X}", pos),
+        Error::PointerConflict { pos } => {
+            write!(f, "pointer read with different options at 0x{:X}", pos)
+        }
+        Error::NoVariantMatch { pos } | Error::EnumErrors { pos, .. } => {
+            write!(f, "no variant matched at 0x{:X}", pos)
+        }
+        Error::Backtrace(backtrace) => {
+            backtrace.fmt_path(f)?;
+            f.write_str(": ")?;
+            write_summary(f, &backtrace.error)
+        }
+    }
+}
+
+/// Writes the details of an error on separate lines, indented by `depth`
+fn write_details(f: &mut fmt::Formatter<'_>, error: &Error, depth: usize) -> fmt::Result {
+    let indent = depth * 2;
+    match error {
+        Error::BadMagic {
+            expected, found, ..
+        } => {
+            write!(f, "\n{:indent$}expected: ", "", indent = indent)?;
+            write_magic(f, &**expected)?;
+            write!(f, "\n{:indent$}found:    ", "", indent = indent)?;
+            write_magic(f, &**found)
+        }
+        Error::AssertFail { message, .. } => {
+            write!(f, "\n{:indent$}message: {}", "", message, indent = indent)
+        }
+        Error::BadChecksum {
+            expected, actual, ..
+        } => write!(
+            f,
+            "\n{:indent$}expected: 0x{:X}\n{:indent$}actual:   0x{:X}",
+            "",
+            expected,
+            "",
+            actual,
+            indent = indent
+        ),
+        Error::LimitExceeded { limit, max, .. } => {
+            write!(f, "\n{:indent$}{}: {}", "", limit, max, indent = indent)
+        }
+        #[cfg(feature = "std")]
+        Error::Io(err) => write!(f, "\n{:indent$}{}", "", err, indent = indent),
+        #[cfg(not(feature = "std"))]
+        Error::Io(_) => Ok(()),
+        Error::Custom { err, .. } => write!(f, "\n{:indent$}{:?}", "", err, indent = indent),
+        Error::BadEncoding { .. }
+        | Error::Overflow { .. }
+        | Error::PointerCycle { .. }
+        | Error::PointerConflict { .. }
+        | Error::NoVariantMatch { .. } => Ok(()),
+        Error::EnumErrors { variant_errors, .. } => {
+            for (variant, error) in variant_errors {
+                write!(f, "\n{:indent$}{}: ", "", variant, indent = indent)?;
+                write_summary(f, error)?;
+                write_details(f, error, depth + 1)?;
+            }
+
+            Ok(())
+        }
+        Error::Backtrace(backtrace) => write_details(f, &backtrace.error, depth),
+    }
+}
+
+macro_rules! downcast_magic {
+    ($f:ident, $value:ident; ints: $($int:ty),*; floats: $($float:ty),*; arrays: $($len:literal),*) => {
+        $(
+            if let Some(value) = $value.downcast_ref::<$int>() {
+                return write!(
+                    $f,
+                    "0x{:0width$X} ({})",
+                    value,
+                    stringify!($int),
+                    width = core::mem::size_of::<$int>() * 2
+                );
+            }
+        )*
+        $(
+            if let Some(value) = $value.downcast_ref::<$float>() {
+                return write!($f, "{:?} ({})", value, stringify!($float));
+            }
+        )*
+        $(
+            if let Some(value) = $value.downcast_ref::<[u8; $len]>() {
+                return write_byte_str($f, value);
+            }
+        )*
+    };
+}
+
+/// Writes a magic value if it is one of the built-in magic types
+fn write_magic(f: &mut fmt::Formatter<'_>, value: &(dyn Any + Sync + Send)) -> fmt::Result {
+    downcast_magic!(
+        f, value;
+        ints: u8, u16, u32, u64, u128, i8, i16, i32, i64, i128;
+        floats: f32, f64;
+        arrays: 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16,
+            17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
+    );
+
+    f.write_str("<unknown type>")
+}
+
+fn write_byte_str(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    f.write_str("b\"")?;
+    for &byte in bytes {
+        for c in core::ascii::escape_default(byte) {
+            f.write_char(c as char)?;
+        }
+    }
+    f.write_char('"')
+}
+
+/// The number of bytes to highlight in the hex dump, which is the size of the
+/// magic value for magic errors
+fn highlight_len(error: &Error) -> usize {
+    match error {
+        Error::BadMagic { found, .. } => magic_len(&**found),
+        Error::Backtrace(backtrace) => highlight_len(&backtrace.error),
+        _ => 1,
+    }
+}
+
+macro_rules! magic_len {
+    ($value:ident; $($ty:ty),*) => {
+        $(
+            if $value.is::<$ty>() {
+                return core::mem::size_of::<$ty>();
+            }
+        )*
+    };
+}
+
+fn magic_len(value: &(dyn Any + Sync + Send)) -> usize {
+    magic_len!(
+        value;
+        u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64,
+        [u8; 1], [u8; 2], [u8; 3], [u8; 4], [u8; 5], [u8; 6], [u8; 7], [u8; 8],
+        [u8; 9], [u8; 10], [u8; 11], [u8; 12], [u8; 13], [u8; 14], [u8; 15], [u8; 16],
+        [u8; 17], [u8; 18], [u8; 19], [u8; 20], [u8; 21], [u8; 22], [u8; 23], [u8; 24],
+        [u8; 25], [u8; 26], [u8; 27], [u8; 28], [u8; 29], [u8; 30], [u8; 31], [u8; 32]
+    );
+
+    1
+}
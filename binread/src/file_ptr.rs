@@ -25,8 +25,17 @@
 //! ```
 //!
 //! Use `offset` to change what the pointer is relative to (default: beginning of reader).
+//!
+//! A [`LazyFilePtr`] reads only the pointer, and reads the value when it is first needed.
+//! Pointers which are relative to their own position use the [`Relative`] pointer type, and
+//! pointers where a sentinel such as zero means there is no value use [`NullableFilePtr`].
+//! A `SharedFilePtr` shares one value between every pointer to the same data.
+//!
+//! Pointers which lead back to themselves return a [`LimitExceeded`](crate::Error::LimitExceeded)
+//! error once more than [`max_depth`](crate::Limits::max_depth) pointers have been followed.
 use super::*;
 use core::fmt;
+use core::marker::PhantomData;
 use core::ops::{Deref, DerefMut};
 
 /// A wrapper type for representing a layer of indirection within a file.
@@ -72,13 +81,13 @@ pub type FilePtr64<T> = FilePtr<u64, T>;
 /// Type alias for 128-bit pointers
 pub type FilePtr128<T> = FilePtr<u128, T>;
 
-impl<Ptr: BinRead<Args = ()> + IntoSeekFrom, BR: BinRead> BinRead for FilePtr<Ptr, BR> {
-    type Args = BR::Args;
+impl<Ptr: for<'a> BinRead<Args<'a> = ()> + IntoSeekFrom, BR: BinRead> BinRead for FilePtr<Ptr, BR> {
+    type Args<'a> = BR::Args<'a>;
 
     fn read_options<R: Read + Seek>(
         reader: &mut R,
         options: &ReadOptions,
-        _: Self::Args,
+        _: Self::Args<'_>,
     ) -> BinResult<Self> {
         #[cfg(feature = "debug_template")]
         let options = &{
@@ -107,16 +116,22 @@ impl<Ptr: BinRead<Args = ()> + IntoSeekFrom, BR: BinRead> BinRead for FilePtr<Pt
         })
     }
 
-    fn after_parse<R>(&mut self, reader: &mut R, ro: &ReadOptions, args: BR::Args) -> BinResult<()>
+    fn after_parse<R>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: BR::Args<'_>,
+    ) -> BinResult<()>
     where
         R: Read + Seek,
     {
         let relative_to = ro.offset;
         let before = reader.stream_pos()?;
+        let ro = &ro.follow_pointer(before)?;
         reader.seek(SeekFrom::Start(relative_to))?;
         reader.seek(self.ptr.into_seek_from())?;
 
-        let mut inner: BR = BinRead::read_options(reader, ro, args)?;
+        let mut inner: BR = BinRead::read_options(reader, ro, args.clone())?;
 
         inner.after_parse(reader, ro, args)?;
 
@@ -127,21 +142,43 @@ impl<Ptr: BinRead<Args = ()> + IntoSeekFrom, BR: BinRead> BinRead for FilePtr<Pt
     }
 }
 
-impl<Ptr: BinRead<Args = ()> + IntoSeekFrom, BR: BinRead> FilePtr<Ptr, BR> {
+impl<Ptr: for<'a> BinRead<Args<'a> = ()> + IntoSeekFrom, BR: BinRead> FilePtr<Ptr, BR> {
     /// Custom parser designed for use with the `parse_with` attribute ([example](crate::attribute#custom-parsers))
     /// that reads a [`FilePtr`](FilePtr) then immediately dereferences it into an owned value
     pub fn parse<R: Read + Seek>(
         reader: &mut R,
         options: &ReadOptions,
-        args: BR::Args,
+        args: BR::Args<'_>,
     ) -> BinResult<BR> {
-        let mut ptr: Self = Self::read_options(reader, options, args)?;
+        let mut ptr: Self = Self::read_options(reader, options, args.clone())?;
         let saved_pos = reader.stream_pos()?;
         ptr.after_parse(reader, options, args)?;
         reader.seek(SeekFrom::Start(saved_pos))?;
         Ok(ptr.into_inner())
     }
 
+    /// The async version of [`parse`](Self::parse), for use with the `parse_with_async`
+    /// attribute when deriving [`BinReadAsync`](crate::BinReadAsync)
+    #[cfg(feature = "async")]
+    pub async fn parse_async<R>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: BR::Args<'_>,
+    ) -> BinResult<BR>
+    where
+        R: crate::io::AsyncRead + crate::io::AsyncSeek + Unpin,
+        Ptr: crate::BinReadAsync,
+        BR: crate::BinReadAsync,
+    {
+        use crate::read_async::{after_parse, read_options};
+
+        let mut ptr: Self = read_options(reader, options, args.clone()).await?;
+        let saved_pos = crate::__private::stream_pos_async(reader).await?;
+        after_parse(&mut ptr, reader, options, args).await?;
+        crate::__private::seek_async(reader, SeekFrom::Start(saved_pos)).await?;
+        Ok(ptr.into_inner())
+    }
+
     /// Consume the pointer and return the inner type
     ///
     /// # Panics
@@ -153,6 +190,9 @@ impl<Ptr: BinRead<Args = ()> + IntoSeekFrom, BR: BinRead> FilePtr<Ptr, BR> {
 }
 
 /// Used to allow any convert any type castable to i64 into a [`SeekFrom::Current`](io::SeekFrom::Current)
+///
+/// The seek is made after seeking to the [`offset`](crate::attribute#offset), so
+/// `SeekFrom::Current` is relative to it.
 pub trait IntoSeekFrom: Copy {
     fn into_seek_from(self) -> SeekFrom;
 }
@@ -171,6 +211,87 @@ macro_rules! impl_into_seek_from {
 
 impl_into_seek_from!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
 
+/// A pointer which is relative to its own position instead of the start of the
+/// reader, for use as the pointer type of a [`FilePtr`] or [`LazyFilePtr`].
+///
+/// The [`offset`](crate::attribute#offset) is still added, and signed pointer
+/// types can point backwards. The [`RelativeFilePtr`] aliases use signed
+/// pointers.
+///
+/// ```rust
+/// use binread::{prelude::*, io::Cursor, file_ptr::RelativeFilePtr8};
+///
+/// #[derive(BinRead)]
+/// struct Test {
+///     value: u8,
+///     backwards: RelativeFilePtr8<u8>,
+///     forwards: RelativeFilePtr8<u8>,
+/// }
+///
+/// let test: Test = Cursor::new(b"\x01\xFF\x01\x03").read_be().unwrap();
+/// assert_eq!(test.backwards.ptr.pos, 1);
+/// assert_eq!(*test.backwards, 1);
+/// assert_eq!(*test.forwards, 3);
+/// ```
+///
+/// A pointer relative to the start of the enclosing struct can be read by
+/// storing the position of the struct and passing it as the `offset`:
+///
+/// ```rust
+/// # use binread::{prelude::*, io::Cursor, FilePtr8, PosValue};
+/// #[derive(BinRead)]
+/// struct Inner {
+///     start: PosValue<()>,
+///     #[br(offset = start.pos)]
+///     value: FilePtr8<u8>,
+/// }
+///
+/// let inner: (u8, Inner) = Cursor::new(b"\0\x01\x03").read_be().unwrap();
+/// assert_eq!(*inner.1.value, 3);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Relative<Ptr> {
+    pub ptr: Ptr,
+    /// The position the pointer was read from
+    pub pos: u64,
+}
+
+impl<Ptr: for<'a> BinRead<Args<'a> = ()>> BinRead for Relative<Ptr> {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = reader.stream_pos()?;
+        Ok(Relative {
+            ptr: Ptr::read_options(reader, options, ())?,
+            pos,
+        })
+    }
+}
+
+impl<Ptr: IntoSeekFrom> IntoSeekFrom for Relative<Ptr> {
+    fn into_seek_from(self) -> SeekFrom {
+        match self.ptr.into_seek_from() {
+            SeekFrom::Current(ptr) => SeekFrom::Current((self.pos as i64).wrapping_add(ptr)),
+            seek => seek,
+        }
+    }
+}
+
+/// A [`FilePtr`] with a pointer relative to its own position
+pub type RelativeFilePtr<Ptr, BR> = FilePtr<Relative<Ptr>, BR>;
+/// Type alias for signed 8-bit relative pointers
+pub type RelativeFilePtr8<T> = RelativeFilePtr<i8, T>;
+/// Type alias for signed 16-bit relative pointers
+pub type RelativeFilePtr16<T> = RelativeFilePtr<i16, T>;
+/// Type alias for signed 32-bit relative pointers
+pub type RelativeFilePtr32<T> = RelativeFilePtr<i32, T>;
+/// Type alias for signed 64-bit relative pointers
+pub type RelativeFilePtr64<T> = RelativeFilePtr<i64, T>;
+
 /// ## Panics
 /// Will panic if the FilePtr has not been read yet using [`BinRead::after_parse`](BinRead::after_parse)
 impl<Ptr: IntoSeekFrom, BR: BinRead> Deref for FilePtr<Ptr, BR> {
@@ -201,7 +322,7 @@ impl<Ptr: IntoSeekFrom, BR: BinRead> DerefMut for FilePtr<Ptr, BR> {
 
 impl<Ptr, BR> fmt::Debug for FilePtr<Ptr, BR>
 where
-    Ptr: BinRead<Args = ()> + IntoSeekFrom,
+    Ptr: for<'a> BinRead<Args<'a> = ()> + IntoSeekFrom,
     BR: BinRead + fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -215,7 +336,713 @@ where
 
 impl<Ptr, BR> PartialEq<FilePtr<Ptr, BR>> for FilePtr<Ptr, BR>
 where
-    Ptr: BinRead<Args = ()> + IntoSeekFrom,
+    Ptr: for<'a> BinRead<Args<'a> = ()> + IntoSeekFrom,
+    BR: BinRead + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.deref() == other.deref()
+    }
+}
+
+/// A pointer which is read without reading the value it points to, so that the
+/// value is only read if it is needed.
+///
+/// The byte order, [`offset`](crate::attribute#offset), `count` and arguments
+/// are stored when the pointer is read, and used to read the value later with
+/// [`get`](Self::get) (which keeps the value) or [`read_value`](Self::read_value)
+/// (which does not). Since the arguments are stored, they can not borrow from
+/// the parent.
+///
+/// ```rust
+/// use binread::{prelude::*, io::Cursor, LazyFilePtr, NullString};
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Entry {
+///     id: u8,
+///     name: LazyFilePtr<u8, NullString>,
+/// }
+///
+/// let mut reader = Cursor::new(b"\x01\x04\x02\x08abc\0de\0");
+/// let (first, mut second): (Entry, Entry) = reader.read_be().unwrap();
+/// assert_eq!(first.name.value(), None);
+/// assert_eq!(second.name.get(&mut reader).unwrap().to_string(), "de");
+/// assert_eq!(first.name.read_value(&mut reader).unwrap().to_string(), "abc");
+/// assert_eq!(reader.position(), 4);
+/// ```
+pub struct LazyFilePtr<Ptr: IntoSeekFrom, BR: BinRead> {
+    pub ptr: Ptr,
+    options: ReadOptions,
+    args: BR::Args<'static>,
+    value: Option<BR>,
+}
+
+impl<Ptr: for<'a> BinRead<Args<'a> = ()> + IntoSeekFrom, BR: BinRead> BinRead
+    for LazyFilePtr<Ptr, BR>
+{
+    type Args<'a> = BR::Args<'static>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let ptr = Ptr::read_options(reader, options, ())?;
+        Ok(LazyFilePtr::new(ptr, options, args))
+    }
+}
+
+impl<Ptr: IntoSeekFrom, BR: BinRead> LazyFilePtr<Ptr, BR> {
+    pub(crate) fn new(ptr: Ptr, options: &ReadOptions, args: BR::Args<'static>) -> Self {
+        LazyFilePtr {
+            ptr,
+            options: *options,
+            args,
+            value: None,
+        }
+    }
+
+    /// Reads the value the pointer points to from `reader`, without keeping
+    /// it. The position of the reader is restored afterwards.
+    pub fn read_value<R: Read + Seek>(&self, reader: &mut R) -> BinResult<BR> {
+        let before = reader.stream_pos()?;
+        let options = self.options.follow_pointer(before)?;
+        let result = (|| {
+            reader.seek(SeekFrom::Start(options.offset))?;
+            reader.seek(self.ptr.into_seek_from())?;
+
+            let mut inner = BR::read_options(reader, &options, self.args.clone())?;
+            inner.after_parse(reader, &options, self.args.clone())?;
+            Ok(inner)
+        })();
+
+        reader.seek(SeekFrom::Start(before))?;
+        result
+    }
+
+    /// Returns the value the pointer points to, reading it from `reader` the
+    /// first time and keeping it for later calls.
+    pub fn get<R: Read + Seek>(&mut self, reader: &mut R) -> BinResult<&mut BR> {
+        if self.value.is_none() {
+            self.value = Some(self.read_value(reader)?);
+        }
+
+        Ok(self.value.as_mut().unwrap())
+    }
+
+    /// The async version of [`read_value`](Self::read_value)
+    #[cfg(feature = "async")]
+    pub async fn read_value_async<R>(&self, reader: &mut R) -> BinResult<BR>
+    where
+        R: crate::io::AsyncRead + crate::io::AsyncSeek + Unpin,
+        BR: crate::BinReadAsync,
+    {
+        use crate::__private::{seek_async, stream_pos_async};
+        use crate::read_async::{after_parse, read_options};
+
+        let before = stream_pos_async(reader).await?;
+        let options = self.options.follow_pointer(before)?;
+        let result = async {
+            seek_async(reader, SeekFrom::Start(options.offset)).await?;
+            seek_async(reader, self.ptr.into_seek_from()).await?;
+
+            let mut inner: BR = read_options(reader, &options, self.args.clone()).await?;
+            after_parse(&mut inner, reader, &options, self.args.clone()).await?;
+            Ok(inner)
+        }
+        .await;
+
+        seek_async(reader, SeekFrom::Start(before)).await?;
+        result
+    }
+
+    /// The async version of [`get`](Self::get)
+    #[cfg(feature = "async")]
+    pub async fn get_async<R>(&mut self, reader: &mut R) -> BinResult<&mut BR>
+    where
+        R: crate::io::AsyncRead + crate::io::AsyncSeek + Unpin,
+        BR: crate::BinReadAsync,
+    {
+        if self.value.is_none() {
+            self.value = Some(self.read_value_async(reader).await?);
+        }
+
+        Ok(self.value.as_mut().unwrap())
+    }
+
+    /// Returns the value if it has already been read by [`get`](Self::get)
+    pub fn value(&self) -> Option<&BR> {
+        self.value.as_ref()
+    }
+
+    /// Consumes the pointer and returns the value, if it has been read
+    pub fn into_value(self) -> Option<BR> {
+        self.value
+    }
+}
+
+impl<Ptr, BR> fmt::Debug for LazyFilePtr<Ptr, BR>
+where
+    Ptr: IntoSeekFrom + fmt::Debug,
+    BR: BinRead + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyFilePtr")
+            .field("ptr", &self.ptr)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<Ptr: IntoSeekFrom, BR: BinRead + Clone> Clone for LazyFilePtr<Ptr, BR> {
+    fn clone(&self) -> Self {
+        LazyFilePtr {
+            ptr: self.ptr,
+            options: self.options,
+            args: self.args.clone(),
+            value: self.value.clone(),
+        }
+    }
+}
+
+/// A [`FilePtr`] where a sentinel pointer value, such as zero, means there is
+/// no value.
+///
+/// The sentinel is chosen by the type `N`, which is [`Zero`] by default. When
+/// the pointer is the sentinel, nothing is read and the value is `None`.
+/// Otherwise the value is read in the same way as a `FilePtr`.
+///
+/// ```rust
+/// use binread::{prelude::*, io::Cursor, file_ptr::Max, NullableFilePtr};
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Node {
+///     value: u8,
+///     next: NullableFilePtr<u16, Box<Node>>,
+///     name: NullableFilePtr<u16, u8, Max>,
+/// }
+///
+/// let node: Node = Cursor::new(b"\x01\x00\x05\xFF\xFF\x02\x00\x00\x00\x00").read_be().unwrap();
+/// assert_eq!(node.value, 1);
+/// assert_eq!(node.name.value, None);
+/// let next = node.next.as_ref().unwrap();
+/// assert_eq!(next.value, 2);
+/// assert!(next.next.is_null());
+/// assert_eq!(*next.name, Some(1));
+/// ```
+pub struct NullableFilePtr<Ptr: IntoSeekFrom, BR: BinRead, N = Zero> {
+    pub ptr: Ptr,
+    pub value: Option<BR>,
+    sentinel: PhantomData<N>,
+}
+
+/// Type alias for nullable 8-bit pointers
+pub type NullableFilePtr8<T> = NullableFilePtr<u8, T>;
+/// Type alias for nullable 16-bit pointers
+pub type NullableFilePtr16<T> = NullableFilePtr<u16, T>;
+/// Type alias for nullable 32-bit pointers
+pub type NullableFilePtr32<T> = NullableFilePtr<u32, T>;
+/// Type alias for nullable 64-bit pointers
+pub type NullableFilePtr64<T> = NullableFilePtr<u64, T>;
+
+/// A pointer value which means a [`NullableFilePtr`] has no value.
+pub trait Sentinel<Ptr> {
+    /// Returns whether `ptr` is the sentinel
+    fn is_null(ptr: &Ptr) -> bool;
+}
+
+/// The sentinel for pointers which are null when they are zero
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Zero {}
+
+/// The sentinel for pointers which are null when every bit is set, such as
+/// `0xFFFFFFFF` for a `u32` or `-1` for an `i32`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Max {}
+
+macro_rules! impl_sentinel {
+    ($($t:ty),*) => {
+        $(
+            impl Sentinel<$t> for Zero {
+                fn is_null(ptr: &$t) -> bool {
+                    *ptr == 0
+                }
+            }
+
+            impl Sentinel<$t> for Max {
+                fn is_null(ptr: &$t) -> bool {
+                    *ptr == !0
+                }
+            }
+        )*
+    };
+}
+
+impl_sentinel!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+impl<Ptr> Sentinel<Relative<Ptr>> for Zero
+where
+    Zero: Sentinel<Ptr>,
+{
+    fn is_null(ptr: &Relative<Ptr>) -> bool {
+        Self::is_null(&ptr.ptr)
+    }
+}
+
+impl<Ptr> Sentinel<Relative<Ptr>> for Max
+where
+    Max: Sentinel<Ptr>,
+{
+    fn is_null(ptr: &Relative<Ptr>) -> bool {
+        Self::is_null(&ptr.ptr)
+    }
+}
+
+impl<Ptr, BR, N> BinRead for NullableFilePtr<Ptr, BR, N>
+where
+    Ptr: for<'a> BinRead<Args<'a> = ()> + IntoSeekFrom,
+    BR: BinRead,
+    N: Sentinel<Ptr>,
+{
+    type Args<'a> = BR::Args<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let ptr = FilePtr::<Ptr, BR>::read_options(reader, options, args)?;
+        Ok(ptr.into())
+    }
+
+    fn after_parse<R>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: BR::Args<'_>,
+    ) -> BinResult<()>
+    where
+        R: Read + Seek,
+    {
+        if !self.is_null() {
+            let mut ptr = FilePtr::<Ptr, BR> {
+                ptr: self.ptr,
+                value: None,
+            };
+            ptr.after_parse(reader, ro, args)?;
+            self.value = ptr.value;
+        }
+
+        Ok(())
+    }
+}
+
+impl<Ptr: IntoSeekFrom, BR: BinRead, N: Sentinel<Ptr>> NullableFilePtr<Ptr, BR, N> {
+    /// Returns whether the pointer is the sentinel, so there is no value
+    pub fn is_null(&self) -> bool {
+        N::is_null(&self.ptr)
+    }
+}
+
+impl<Ptr: IntoSeekFrom, BR: BinRead, N> NullableFilePtr<Ptr, BR, N> {
+    /// Consume the pointer and return the value, or `None` if the pointer was
+    /// null
+    pub fn into_inner(self) -> Option<BR> {
+        self.value
+    }
+}
+
+impl<Ptr: IntoSeekFrom, BR: BinRead, N> From<FilePtr<Ptr, BR>> for NullableFilePtr<Ptr, BR, N> {
+    fn from(ptr: FilePtr<Ptr, BR>) -> Self {
+        NullableFilePtr {
+            ptr: ptr.ptr,
+            value: ptr.value,
+            sentinel: PhantomData,
+        }
+    }
+}
+
+impl<Ptr: IntoSeekFrom, BR: BinRead, N> Deref for NullableFilePtr<Ptr, BR, N> {
+    type Target = Option<BR>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<Ptr: IntoSeekFrom, BR: BinRead, N> DerefMut for NullableFilePtr<Ptr, BR, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<Ptr, BR, N> fmt::Debug for NullableFilePtr<Ptr, BR, N>
+where
+    Ptr: IntoSeekFrom,
+    BR: BinRead + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+impl<Ptr, BR, N> PartialEq for NullableFilePtr<Ptr, BR, N>
+where
+    Ptr: IntoSeekFrom,
+    BR: BinRead + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+/// A [`FilePtr`] whose value is shared with every other `SharedFilePtr` to the
+/// same position and type, so aliased data is only read once.
+///
+/// Values are cached for the whole of a read started with [`BinReaderExt`] or
+/// [`BinRead::read`], and for the whole of a call to [`with_shared_cache`],
+/// which is needed to share values between separate reads. A pointer to a
+/// cached value with a different byte order, count or arguments returns a
+/// [`PointerConflict`](crate::Error::PointerConflict) error, so the value and
+/// its arguments must be comparable and owned, and `Send` and `Sync` so they
+/// can be shared between threads.
+///
+/// A pointer to a value which is still being read, such as a cycle in a linked
+/// list, returns a [`PointerCycle`](crate::Error::PointerCycle) error instead
+/// of reading forever.
+///
+/// The cache is kept per thread, and this type is only available with the `std`
+/// feature.
+///
+/// ```rust
+/// use binread::{prelude::*, io::Cursor, SharedFilePtr, NullString};
+/// use std::sync::Arc;
+///
+/// #[derive(BinRead)]
+/// struct Entry {
+///     name: SharedFilePtr<u8, NullString>,
+/// }
+///
+/// let mut reader = Cursor::new(b"\x03\x03\x07abc\0de\0");
+/// let entries: (Entry, Entry, Entry) = reader.read_be().unwrap();
+/// assert_eq!(entries.0.name.to_string(), "abc");
+/// assert_eq!(entries.2.name.to_string(), "de");
+/// assert!(Arc::ptr_eq(entries.0.name.value.as_ref().unwrap(), entries.1.name.value.as_ref().unwrap()));
+/// ```
+#[cfg(feature = "std")]
+pub struct SharedFilePtr<Ptr: IntoSeekFrom, BR: BinRead> {
+    pub ptr: Ptr,
+    pub value: Option<std::sync::Arc<BR>>,
+}
+
+/// Runs `f` with a cache shared by every [`SharedFilePtr`] read inside it, so
+/// that values are shared between separate reads.
+///
+/// Calls can be nested, in which case the outermost cache is used. Values are
+/// cached by position only, so every read inside one call should be from the
+/// same reader.
+///
+/// ```rust
+/// use binread::{prelude::*, io::Cursor, file_ptr::{with_shared_cache, SharedFilePtr}};
+/// use std::sync::Arc;
+///
+/// let mut reader = Cursor::new(b"\x02\x02\xFF");
+/// let (a, b) = with_shared_cache(|| -> BinResult<_> {
+///     let a: SharedFilePtr<u8, u8> = reader.read_be()?;
+///     let b: SharedFilePtr<u8, u8> = reader.read_be()?;
+///     Ok((a, b))
+/// }).unwrap();
+/// assert!(Arc::ptr_eq(a.value.as_ref().unwrap(), b.value.as_ref().unwrap()));
+/// ```
+#[cfg(feature = "std")]
+pub fn with_shared_cache<T>(f: impl FnOnce() -> T) -> T {
+    let _scope = shared::Scope::enter();
+    f()
+}
+
+/// Runs a top level read, giving any [`SharedFilePtr`] inside it a cache
+pub(crate) fn read_scope<T>(f: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "std")]
+    let _scope = shared::Scope::enter();
+    f()
+}
+
+/// Runs a top level async read, giving any [`SharedFilePtr`] inside it a cache
+#[cfg(feature = "async")]
+pub(crate) fn read_scope_async<F: core::future::Future>(
+    future: F,
+) -> impl core::future::Future<Output = F::Output> {
+    shared::AsyncScope::new(future)
+}
+
+#[cfg(feature = "std")]
+pub(crate) mod shared {
+    use crate::{BinResult, Endian, Error, ReadOptions};
+    use std::any::{Any, TypeId};
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    /// A cached value, and the options and arguments it was read with. These
+    /// are `Send` so that async reads holding the cache can be sent between
+    /// threads.
+    struct Entry {
+        endian: Endian,
+        count: Option<usize>,
+        args: Box<dyn Any + Send + Sync>,
+        /// The value, or `None` while it is being read
+        value: Option<Box<dyn Any + Send + Sync>>,
+    }
+
+    type Cache = HashMap<(u64, TypeId), Entry>;
+
+    thread_local! {
+        static CACHE: RefCell<Option<Cache>> = const { RefCell::new(None) };
+    }
+
+    /// Keeps the cache alive, and removes it when dropped if it was created by
+    /// this scope
+    pub(crate) struct Scope {
+        owned: bool,
+    }
+
+    impl Scope {
+        pub(crate) fn enter() -> Self {
+            let owned = CACHE.with(|cache| {
+                let mut cache = cache.borrow_mut();
+                let owned = cache.is_none();
+                cache.get_or_insert_with(HashMap::new);
+                owned
+            });
+
+            Scope { owned }
+        }
+    }
+
+    impl Drop for Scope {
+        fn drop(&mut self) {
+            if self.owned {
+                CACHE.with(|cache| cache.borrow_mut().take());
+            }
+        }
+    }
+
+    /// A future which owns a cache, and puts it in place of the thread's cache
+    /// whenever it is polled, so that the cache is not kept across awaits
+    #[cfg(feature = "async")]
+    pub(crate) struct AsyncScope<F> {
+        future: core::pin::Pin<Box<F>>,
+        cache: Option<Cache>,
+    }
+
+    #[cfg(feature = "async")]
+    impl<F> AsyncScope<F> {
+        pub(crate) fn new(future: F) -> Self {
+            AsyncScope {
+                future: Box::pin(future),
+                cache: Some(HashMap::new()),
+            }
+        }
+    }
+
+    #[cfg(feature = "async")]
+    impl<F: core::future::Future> core::future::Future for AsyncScope<F> {
+        type Output = F::Output;
+
+        fn poll(
+            mut self: core::pin::Pin<&mut Self>,
+            cx: &mut core::task::Context<'_>,
+        ) -> core::task::Poll<F::Output> {
+            let this = &mut *self;
+
+            // Polled from inside another scope, whose cache is used instead
+            if CACHE.with(|cache| cache.borrow().is_some()) {
+                return this.future.as_mut().poll(cx);
+            }
+
+            CACHE.with(|cache| *cache.borrow_mut() = this.cache.take());
+            let poll = this.future.as_mut().poll(cx);
+            this.cache = CACHE.with(|cache| cache.borrow_mut().take());
+            poll
+        }
+    }
+
+    fn with_cache<R>(f: impl FnOnce(&mut Cache) -> R) -> R {
+        CACHE.with(|cache| {
+            f(cache
+                .borrow_mut()
+                .as_mut()
+                .expect("no shared pointer cache"))
+        })
+    }
+
+    /// Looks up the value at `pos`, marking it as being read if it is missing
+    pub(crate) fn lookup<T, A>(
+        pos: u64,
+        options: &ReadOptions,
+        args: &A,
+    ) -> BinResult<Option<Arc<T>>>
+    where
+        T: Send + Sync + 'static,
+        A: Clone + PartialEq + Send + Sync + 'static,
+    {
+        with_cache(|cache| match cache.get(&(pos, TypeId::of::<T>())) {
+            Some(entry)
+                if entry.endian != options.endian
+                    || entry.count != options.count
+                    || entry.args.downcast_ref::<A>() != Some(args) =>
+            {
+                Err(Error::PointerConflict { pos })
+            }
+            Some(Entry {
+                value: Some(value), ..
+            }) => Ok(Some(Arc::clone(value.downcast_ref().unwrap()))),
+            Some(Entry { value: None, .. }) => Err(Error::PointerCycle { pos }),
+            None => {
+                let entry = Entry {
+                    endian: options.endian,
+                    count: options.count,
+                    args: Box::new(args.clone()),
+                    value: None,
+                };
+                cache.insert((pos, TypeId::of::<T>()), entry);
+                Ok(None)
+            }
+        })
+    }
+
+    /// Stores the value at `pos`, or forgets it if it could not be read
+    pub(crate) fn finish<T: Send + Sync + 'static>(pos: u64, value: Option<Arc<T>>) {
+        with_cache(|cache| {
+            let key = (pos, TypeId::of::<T>());
+            match value {
+                Some(value) => {
+                    if let Some(entry) = cache.get_mut(&key) {
+                        entry.value = Some(Box::new(value));
+                    }
+                }
+                None => {
+                    cache.remove(&key);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Ptr, BR> BinRead for SharedFilePtr<Ptr, BR>
+where
+    Ptr: for<'a> BinRead<Args<'a> = ()> + IntoSeekFrom,
+    BR: BinRead + Send + Sync + 'static,
+    for<'a> BR::Args<'a>: PartialEq + Send + Sync + 'static,
+{
+    type Args<'a> = BR::Args<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(SharedFilePtr {
+            ptr: Ptr::read_options(reader, options, ())?,
+            value: None,
+        })
+    }
+
+    fn after_parse<R>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: BR::Args<'_>,
+    ) -> BinResult<()>
+    where
+        R: Read + Seek,
+    {
+        use std::sync::Arc;
+
+        let _scope = shared::Scope::enter();
+        let before = reader.stream_pos()?;
+        let ro = &ro.follow_pointer(before)?;
+        reader.seek(SeekFrom::Start(ro.offset))?;
+        let pos = reader.seek(self.ptr.into_seek_from())?;
+
+        let value = match shared::lookup::<BR, _>(pos, ro, &args) {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => {
+                let value = BR::read_options(reader, ro, args.clone()).and_then(|mut inner| {
+                    inner.after_parse(reader, ro, args)?;
+                    Ok(Arc::new(inner))
+                });
+                shared::finish(pos, value.as_ref().ok().cloned());
+                value
+            }
+            Err(error) => Err(error),
+        };
+
+        reader.seek(SeekFrom::Start(before))?;
+        self.value = Some(value?);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Ptr: IntoSeekFrom, BR: BinRead> SharedFilePtr<Ptr, BR> {
+    /// Consume the pointer and return the shared value
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the file pointer hasn't been properly postprocessed
+    pub fn into_inner(self) -> std::sync::Arc<BR> {
+        self.value.unwrap()
+    }
+}
+
+/// ## Panics
+/// Will panic if the SharedFilePtr has not been read yet using [`BinRead::after_parse`](BinRead::after_parse)
+#[cfg(feature = "std")]
+impl<Ptr: IntoSeekFrom, BR: BinRead> Deref for SharedFilePtr<Ptr, BR> {
+    type Target = BR;
+
+    fn deref(&self) -> &Self::Target {
+        match self.value.as_ref() {
+            Some(x) => x,
+            None => panic!(
+                "Deref'd SharedFilePtr before reading (make sure to use SharedFilePtr::after_parse first)"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Ptr: IntoSeekFrom, BR: BinRead> Clone for SharedFilePtr<Ptr, BR> {
+    fn clone(&self) -> Self {
+        SharedFilePtr {
+            ptr: self.ptr,
+            value: self.value.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Ptr, BR> fmt::Debug for SharedFilePtr<Ptr, BR>
+where
+    Ptr: IntoSeekFrom,
+    BR: BinRead + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref value) = self.value {
+            fmt::Debug::fmt(value, f)
+        } else {
+            write!(f, "UnreadPointer")
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Ptr, BR> PartialEq for SharedFilePtr<Ptr, BR>
+where
+    Ptr: IntoSeekFrom,
     BR: BinRead + PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
@@ -0,0 +1,301 @@
+//! Half-precision floats and fixed-point numbers, as used by GPU and game
+//! asset formats.
+//!
+//! [`f16`](struct@f16) and [`bf16`] are stored as 16 bits in the byte order
+//! of the reader, and convert to `f32` and `f64` without losing precision:
+//!
+//! ```rust
+//! use binread::{prelude::*, io::Cursor, float::{bf16, f16}};
+//!
+//! #[derive(BinRead)]
+//! struct Vertex {
+//!     x: f16,
+//!     y: f16,
+//!     weight: bf16,
+//! }
+//!
+//! let vertex: Vertex = Cursor::new(b"\x3C\x00\xC1\x00\x3F\x40").read_be().unwrap();
+//! assert_eq!(vertex.x.to_f32(), 1.0);
+//! assert_eq!(vertex.y.to_f32(), -2.5);
+//! assert_eq!(f64::from(vertex.weight), 0.75);
+//! ```
+//!
+//! With the `const_generics` feature, `Fixed` reads fixed-point numbers
+//! with any number of fractional bits.
+#![allow(non_camel_case_types)]
+
+use core::cmp::Ordering;
+use core::fmt;
+
+/// An IEEE 754 half-precision float, with 5 exponent bits and 10 mantissa
+/// bits.
+#[derive(Clone, Copy, Default)]
+pub struct f16(u16);
+
+/// A bfloat16 float, which is the top 16 bits of an `f32`.
+#[derive(Clone, Copy, Default)]
+pub struct bf16(u16);
+
+impl f16 {
+    /// Creates the float from its bits
+    pub const fn from_bits(bits: u16) -> Self {
+        f16(bits)
+    }
+
+    /// Returns the bits of the float
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Converts the float to an `f32`, which can represent every `f16`
+    /// exactly.
+    pub fn to_f32(self) -> f32 {
+        let sign = u32::from(self.0 & 0x8000) << 16;
+        let exponent = u32::from(self.0 >> 10) & 0x1F;
+        let mantissa = u32::from(self.0) & 0x3FF;
+
+        let bits = match exponent {
+            0 if mantissa == 0 => sign,
+            // Subnormals are normal in an `f32`, so the mantissa is shifted up
+            // until its leading bit becomes the implicit one
+            0 => {
+                let shift = mantissa.leading_zeros() - 21;
+                let mantissa = (mantissa << shift) & 0x3FF;
+                sign | ((113 - shift) << 23) | (mantissa << 13)
+            }
+            0x1F => sign | 0x7F80_0000 | (mantissa << 13),
+            _ => sign | ((exponent + 112) << 23) | (mantissa << 13),
+        };
+
+        f32::from_bits(bits)
+    }
+
+    /// Converts the float to an `f64`, which can represent every `f16`
+    /// exactly.
+    pub fn to_f64(self) -> f64 {
+        self.to_f32().into()
+    }
+}
+
+impl bf16 {
+    /// Creates the float from its bits
+    pub const fn from_bits(bits: u16) -> Self {
+        bf16(bits)
+    }
+
+    /// Returns the bits of the float
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// Converts the float to an `f32`, which can represent every `bf16`
+    /// exactly.
+    pub fn to_f32(self) -> f32 {
+        f32::from_bits(u32::from(self.0) << 16)
+    }
+
+    /// Converts the float to an `f64`, which can represent every `bf16`
+    /// exactly.
+    pub fn to_f64(self) -> f64 {
+        self.to_f32().into()
+    }
+}
+
+macro_rules! half_impl {
+    ($($name:ident),*) => {
+        $(
+            impl From<$name> for f32 {
+                fn from(value: $name) -> Self {
+                    value.to_f32()
+                }
+            }
+
+            impl From<$name> for f64 {
+                fn from(value: $name) -> Self {
+                    value.to_f64()
+                }
+            }
+
+            // Compared as floats, so that zeros are equal and NaNs are not
+            impl PartialEq for $name {
+                fn eq(&self, other: &Self) -> bool {
+                    self.to_f32() == other.to_f32()
+                }
+            }
+
+            impl PartialOrd for $name {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    self.to_f32().partial_cmp(&other.to_f32())
+                }
+            }
+
+            impl fmt::Debug for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Debug::fmt(&self.to_f32(), f)
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(&self.to_f32(), f)
+                }
+            }
+        )*
+    };
+}
+
+half_impl!(f16, bf16);
+
+/// A fixed-point number stored as the integer `T`, with `FRAC` of its bits
+/// after the point.
+///
+/// The integer is read in the byte order of the reader. `T` can be any 8, 16
+/// or 32-bit integer, and signed integers store signed numbers. `to_f64` is
+/// exact for all of them, and `to_f32` is available where it is exact too,
+/// for 8 and 16-bit integers.
+///
+/// ```rust
+/// use binread::{prelude::*, io::Cursor, float::{Fixed, I16F16, U8F8}};
+///
+/// #[derive(BinRead, BinWrite)]
+/// struct Glyph {
+///     advance: I16F16,
+///     scale: U8F8,
+///     skew: Fixed<i8, 6>,
+/// }
+///
+/// let data = b"\xFF\xFE\x80\x00\x01\x80\xE0";
+/// let glyph: Glyph = Cursor::new(data).read_be().unwrap();
+/// assert_eq!(glyph.advance.to_f64(), -1.5);
+/// assert_eq!(glyph.scale.to_f32(), 1.5);
+/// assert_eq!(glyph.skew.to_f32(), -0.5);
+///
+/// let mut writer = Cursor::new(Vec::new());
+/// writer.write_be(&glyph).unwrap();
+/// assert_eq!(writer.into_inner(), data);
+/// ```
+#[cfg(feature = "const_generics")]
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed<T, const FRAC: u32>(pub T);
+
+/// A signed fixed-point number with 8 integer and 8 fractional bits
+#[cfg(feature = "const_generics")]
+pub type I8F8 = Fixed<i16, 8>;
+/// An unsigned fixed-point number with 8 integer and 8 fractional bits
+#[cfg(feature = "const_generics")]
+pub type U8F8 = Fixed<u16, 8>;
+/// A signed fixed-point number with 16 integer and 16 fractional bits
+#[cfg(feature = "const_generics")]
+pub type I16F16 = Fixed<i32, 16>;
+/// An unsigned fixed-point number with 16 integer and 16 fractional bits
+#[cfg(feature = "const_generics")]
+pub type U16F16 = Fixed<u32, 16>;
+
+#[cfg(feature = "const_generics")]
+impl<T, const FRAC: u32> Fixed<T, FRAC> {
+    /// The value of the lowest bit, which fails to compile if there are more
+    /// fractional bits than fit in the shift
+    const SCALE: f64 = 1.0 / (1u64 << FRAC) as f64;
+
+    /// Creates the number from the integer which stores it
+    pub const fn from_bits(bits: T) -> Self {
+        Fixed(bits)
+    }
+
+    /// Returns the integer which stores the number
+    pub fn to_bits(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(feature = "const_generics")]
+macro_rules! fixed_impl {
+    ($($type_name:ty),*; exact_f32: $($small:ty),*) => {
+        $(
+            impl<const FRAC: u32> Fixed<$type_name, FRAC> {
+                /// Converts the number to an `f64` exactly
+                pub fn to_f64(self) -> f64 {
+                    f64::from(self.0) * Self::SCALE
+                }
+            }
+
+            impl<const FRAC: u32> From<Fixed<$type_name, FRAC>> for f64 {
+                fn from(value: Fixed<$type_name, FRAC>) -> Self {
+                    value.to_f64()
+                }
+            }
+
+            impl<const FRAC: u32> fmt::Debug for Fixed<$type_name, FRAC> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Debug::fmt(&self.to_f64(), f)
+                }
+            }
+
+            impl<const FRAC: u32> fmt::Display for Fixed<$type_name, FRAC> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(&self.to_f64(), f)
+                }
+            }
+        )*
+        $(
+            impl<const FRAC: u32> Fixed<$small, FRAC> {
+                /// Converts the number to an `f32` exactly
+                pub fn to_f32(self) -> f32 {
+                    f32::from(self.0) * Self::SCALE as f32
+                }
+            }
+
+            impl<const FRAC: u32> From<Fixed<$small, FRAC>> for f32 {
+                fn from(value: Fixed<$small, FRAC>) -> Self {
+                    value.to_f32()
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "const_generics")]
+fixed_impl!(i8, u8, i16, u16, i32, u32; exact_f32: i8, u8, i16, u16);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f16_to_f32() {
+        assert_eq!(f16::from_bits(0x3C00).to_f32(), 1.0);
+        assert_eq!(f16::from_bits(0xC000).to_f32(), -2.0);
+        assert_eq!(f16::from_bits(0x7BFF).to_f32(), 65504.0);
+        assert_eq!(f16::from_bits(0x0400).to_f32(), 1.0 / 16384.0);
+        assert_eq!(f16::from_bits(0x0001).to_f64(), 1.0 / 16_777_216.0);
+        assert_eq!(f16::from_bits(0x83FF).to_f64(), -1023.0 / 16_777_216.0);
+        assert_eq!(f16::from_bits(0x7C00).to_f32(), f32::INFINITY);
+        assert!(f16::from_bits(0x7E00).to_f32().is_nan());
+        assert_eq!(f16::from_bits(0x8000), f16::from_bits(0));
+        assert!(f16::from_bits(0x3C00) < f16::from_bits(0x4000));
+    }
+
+    #[test]
+    fn bf16_to_f32() {
+        assert_eq!(bf16::from_bits(0x3F80).to_f32(), 1.0);
+        assert_eq!(bf16::from_bits(0xC0A0).to_f64(), -5.0);
+        assert_eq!(bf16::from_bits(0x7F80).to_f32(), f32::INFINITY);
+    }
+
+    #[cfg(feature = "const_generics")]
+    #[test]
+    fn fixed() {
+        assert_eq!(I16F16::from_bits(0x0001_8000).to_f64(), 1.5);
+        assert_eq!(I16F16::from_bits(-0x0000_4000).to_f64(), -0.25);
+        assert_eq!(
+            U16F16::from_bits(0xFFFF_FFFF).to_f64(),
+            65535.0 + 65535.0 / 65536.0
+        );
+        assert_eq!(I8F8::from_bits(-0x0180).to_f32(), -1.5);
+        assert_eq!(Fixed::<u8, 0>::from_bits(200).to_f32(), 200.0);
+        assert_eq!(
+            Fixed::<u32, 32>::from_bits(1).to_f64(),
+            1.0 / 4_294_967_296.0
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use crate::{
+    error::limit_exceeded,
     io::{ErrorKind::UnexpectedEof, Read, Seek},
-    BinRead, BinReaderExt, BinResult, ReadOptions,
+    options::LimitError,
+    BinRead, BinResult, ReadOptions,
 };
 #[cfg(not(feature = "std"))]
 use alloc::{vec, vec::Vec};
@@ -30,6 +32,10 @@ pub fn read_bytes<R: Read + Seek>(
         Some(x) => x,
         None => panic!("Missing count for read_bytes"),
     };
+    options
+        .limits
+        .check_count::<u8>(count)
+        .map_err(|limit| limit_exceeded(reader, limit))?;
     let mut buf = vec![0; count];
     reader.read_exact(&mut buf)?;
 
@@ -52,26 +58,27 @@ pub fn read_bytes<R: Read + Seek>(
 /// # let x: NullTerminated = x.read_be().unwrap();
 /// # assert_eq!(x.data, &[1, 2, 3, 4, 0]);
 /// ```
-pub fn until<Reader, T, CondFn, Arg, Ret>(
+pub fn until<'a, Reader, T, CondFn, Arg, Ret>(
     cond: CondFn,
 ) -> impl Fn(&mut Reader, &ReadOptions, Arg) -> BinResult<Ret>
 where
-    T: BinRead<Args = Arg>,
+    T: BinRead<Args<'a> = Arg>,
     Reader: Read + Seek,
     CondFn: Fn(&T) -> bool,
     Arg: Clone,
     Ret: core::iter::FromIterator<T>,
 {
     move |reader, ro, args| {
+        let options = item_options(ro);
         let mut result = Vec::new();
-        let mut last = reader.read_type_args(ro.endian, args.clone())?;
+        let mut last = read_item(reader, &options, args.clone())?;
 
         while !cond(&last) {
-            result.push(last);
-            last = reader.read_type_args(ro.endian, args.clone())?;
+            push_item(reader, ro, &mut result, last)?;
+            last = read_item(reader, &options, args.clone())?;
         }
 
-        result.push(last);
+        push_item(reader, ro, &mut result, last)?;
 
         Ok(result.into_iter().collect())
     }
@@ -93,23 +100,24 @@ where
 /// # let x: NullTerminated = x.read_be().unwrap();
 /// # assert_eq!(x.data, &[1, 2, 3, 4]);
 /// ```
-pub fn until_exclusive<Reader, T, CondFn, Arg, Ret>(
+pub fn until_exclusive<'a, Reader, T, CondFn, Arg, Ret>(
     cond: CondFn,
 ) -> impl Fn(&mut Reader, &ReadOptions, Arg) -> BinResult<Ret>
 where
-    T: BinRead<Args = Arg>,
+    T: BinRead<Args<'a> = Arg>,
     Reader: Read + Seek,
     CondFn: Fn(&T) -> bool,
     Arg: Clone,
     Ret: core::iter::FromIterator<T>,
 {
     move |reader, ro, args| {
+        let options = item_options(ro);
         let mut result = Vec::new();
-        let mut last = reader.read_type_args(ro.endian, args.clone())?;
+        let mut last = read_item(reader, &options, args.clone())?;
 
         while !cond(&last) {
-            result.push(last);
-            last = reader.read_type_args(ro.endian, args.clone())?;
+            push_item(reader, ro, &mut result, last)?;
+            last = read_item(reader, &options, args.clone())?;
         }
 
         Ok(result.into_iter().collect())
@@ -132,22 +140,24 @@ where
 /// # let x: EntireFile = x.read_be().unwrap();
 /// # assert_eq!(x.data, &[1, 2, 3, 4]);
 /// ```
-pub fn until_eof<R, T, Arg, Ret>(reader: &mut R, ro: &ReadOptions, args: Arg) -> BinResult<Ret>
+pub fn until_eof<'a, R, T, Arg, Ret>(reader: &mut R, ro: &ReadOptions, args: Arg) -> BinResult<Ret>
 where
-    T: BinRead<Args = Arg>,
+    T: BinRead<Args<'a> = Arg>,
     R: Read + Seek,
     Arg: Clone,
     Ret: core::iter::FromIterator<T>,
 {
+    let options = item_options(ro);
     let mut result = Vec::new();
-    let mut last = reader.read_type_args(ro.endian, args.clone());
+    let mut last = read_item(reader, &options, args.clone());
 
-    while !matches!(&last, Err(crate::Error::Io(err)) if err.kind() == UnexpectedEof) {
+    loop {
         last = match last {
             Ok(x) => {
-                result.push(x);
-                reader.read_type_args(ro.endian, args.clone())
+                push_item(reader, ro, &mut result, x)?;
+                read_item(reader, &options, args.clone())
             }
+            Err(err) if is_eof(&err) => break,
             Err(err) => return Err(err),
         }
     }
@@ -155,6 +165,112 @@ where
     Ok(result.into_iter().collect())
 }
 
+/// The async version of [`until_eof`], for use with the `parse_with_async` attribute when
+/// deriving [`BinReadAsync`](crate::BinReadAsync).
+///
+/// # Examples
+///
+/// ```
+/// # use binread::{BinRead, BinReadAsync, BinReaderAsyncExt, helpers::{until_eof, until_eof_async}};
+/// # use futures_util::io::Cursor;
+/// #[derive(BinRead, BinReadAsync)]
+/// struct EntireFile {
+///     #[br(parse_with = until_eof, parse_with_async = until_eof_async)]
+///     data: Vec<u8>,
+/// }
+///
+/// # futures_executor::block_on(async {
+/// # let mut x = Cursor::new(b"\x01\x02\x03\x04");
+/// # let x: EntireFile = x.read_be_async().await.unwrap();
+/// # assert_eq!(x.data, &[1, 2, 3, 4]);
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub async fn until_eof_async<'a, R, T, Arg, Ret>(
+    reader: &mut R,
+    ro: &ReadOptions,
+    args: Arg,
+) -> BinResult<Ret>
+where
+    T: crate::BinReadAsync<Args<'a> = Arg>,
+    R: crate::io::AsyncRead + crate::io::AsyncSeek + Unpin,
+    Arg: Clone,
+    Ret: core::iter::FromIterator<T>,
+{
+    let options = item_options(ro);
+    let mut result = Vec::new();
+    loop {
+        match read_item_async(reader, &options, args.clone()).await {
+            Ok(x) => {
+                if let Err(limit) = push_within_limits(ro, &mut result, x) {
+                    return Err(crate::read_async::limit_exceeded(reader, limit).await);
+                }
+            }
+            Err(err) if is_eof(&err) => break,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(result.into_iter().collect())
+}
+
+/// The options for reading each item of a helper, which are reset except for
+/// the byte order and the limits
+fn item_options(ro: &ReadOptions) -> ReadOptions {
+    ReadOptions {
+        endian: ro.endian,
+        limits: ro.limits,
+        pointer_depth: ro.pointer_depth,
+        ..Default::default()
+    }
+}
+
+fn read_item<R: Read + Seek, T: BinRead>(
+    reader: &mut R,
+    options: &ReadOptions,
+    args: T::Args<'_>,
+) -> BinResult<T> {
+    let mut item = T::read_options(reader, options, args.clone())?;
+    item.after_parse(reader, options, args)?;
+    Ok(item)
+}
+
+#[cfg(feature = "async")]
+async fn read_item_async<R, T>(
+    reader: &mut R,
+    options: &ReadOptions,
+    args: T::Args<'_>,
+) -> BinResult<T>
+where
+    R: crate::io::AsyncRead + crate::io::AsyncSeek + Unpin,
+    T: crate::BinReadAsync,
+{
+    let mut item = crate::read_async::read_options(reader, options, args.clone()).await?;
+    crate::read_async::after_parse(&mut item, reader, options, args).await?;
+    Ok(item)
+}
+
+/// Adds an item to the result of a helper, if it is within the limits
+fn push_item<R: Seek, T>(
+    reader: &mut R,
+    ro: &ReadOptions,
+    result: &mut Vec<T>,
+    item: T,
+) -> BinResult<()> {
+    push_within_limits(ro, result, item).map_err(|limit| limit_exceeded(reader, limit))
+}
+
+/// Adds an item to the result of a helper, returning the limit it went over
+/// if there are too many items
+fn push_within_limits<T>(ro: &ReadOptions, result: &mut Vec<T>, item: T) -> Result<(), LimitError> {
+    result.push(item);
+    ro.limits.check_count::<T>(result.len())
+}
+
+fn is_eof(err: &crate::Error) -> bool {
+    matches!(err.root_cause(), crate::Error::Io(err) if err.kind() == UnexpectedEof)
+}
+
 /// A helper similar to `#[br(count = N)]` which can be used with any collection.
 ///
 /// # Examples
@@ -174,16 +290,21 @@ where
 /// # let x: CountBytes = x.read_be().unwrap();
 /// # assert_eq!(x.data, &[1, 2, 3]);
 /// ```
-pub fn count<R, T, Arg, Ret>(n: usize) -> impl Fn(&mut R, &ReadOptions, Arg) -> BinResult<Ret>
+pub fn count<'a, R, T, Arg, Ret>(n: usize) -> impl Fn(&mut R, &ReadOptions, Arg) -> BinResult<Ret>
 where
-    T: BinRead<Args = Arg>,
+    T: BinRead<Args<'a> = Arg>,
     R: Read + Seek,
     Arg: Clone,
     Ret: core::iter::FromIterator<T>,
 {
     move |reader, ro, args| {
+        ro.limits
+            .check_count::<T>(n)
+            .map_err(|limit| limit_exceeded(reader, limit))?;
+
+        let options = item_options(ro);
         (0..n)
-            .map(|_| reader.read_type_args(ro.endian, args.clone()))
+            .map(|_| read_item(reader, &options, args.clone()))
             .collect()
     }
 }
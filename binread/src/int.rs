@@ -0,0 +1,187 @@
+//! Integers with a number of bytes which is not a power of two, such as the
+//! 24-bit samples of audio formats.
+//!
+//! Each type is read from exactly its number of bytes in the byte order of
+//! the reader, with signed types sign extended, and is stored in the next
+//! larger primitive, which it converts to with [`From`]:
+//!
+//! ```rust
+//! use binread::{prelude::*, io::Cursor, int::{i24, u48}};
+//!
+//! #[derive(BinRead, BinWrite)]
+//! struct Sample {
+//!     left: i24,
+//!     right: i24,
+//!     time: u48,
+//! }
+//!
+//! let data = b"\xFF\xFF\xFF\x00\x00\x01\x00\x00\x00\x01\x02\x03";
+//! let sample: Sample = Cursor::new(data).read_be().unwrap();
+//! assert_eq!(i32::from(sample.left), -1);
+//! assert_eq!(sample.right.get(), 1);
+//! assert_eq!(u64::from(sample.time), 0x01_0203);
+//!
+//! let mut writer = Cursor::new(Vec::new());
+//! writer.write_be(&sample).unwrap();
+//! assert_eq!(writer.into_inner(), data);
+//!
+//! assert_eq!(u48::new(1 << 48), None);
+//! ```
+#![allow(non_camel_case_types)]
+
+use crate::Endian;
+use core::fmt;
+
+fn is_big(endian: Endian) -> bool {
+    match endian {
+        Endian::Big => true,
+        Endian::Little => false,
+        Endian::Native => cfg!(target_endian = "big"),
+    }
+}
+
+macro_rules! int_impl {
+    ($($name:ident($larger:ty, $bytes:literal) from $smaller:ty),*$(,)?) => {
+        $(
+            #[doc = concat!(
+                "A ", stringify!($bytes), "-byte integer, stored as a `",
+                stringify!($larger), "`."
+            )]
+            #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            pub struct $name($larger);
+
+            impl $name {
+                /// The number of bits in the integer
+                pub const BITS: u32 = $bytes * 8;
+
+                /// The number of unused bits in the larger primitive
+                const SHIFT: u32 = <$larger>::BITS - Self::BITS;
+
+                /// The smallest value of the integer
+                pub const MIN: Self = $name(<$larger>::MIN >> Self::SHIFT);
+
+                /// The largest value of the integer
+                pub const MAX: Self = $name(<$larger>::MAX >> Self::SHIFT);
+
+                /// Creates the integer, or returns `None` if the value does
+                /// not fit in it.
+                pub const fn new(value: $larger) -> Option<Self> {
+                    if value < Self::MIN.0 || value > Self::MAX.0 {
+                        None
+                    } else {
+                        Some($name(value))
+                    }
+                }
+
+                /// Returns the value as the larger primitive
+                pub const fn get(self) -> $larger {
+                    self.0
+                }
+
+                pub(crate) fn from_bytes(bytes: [u8; $bytes], endian: Endian) -> Self {
+                    let mut buf = [0; core::mem::size_of::<$larger>()];
+                    let value = if is_big(endian) {
+                        buf[..$bytes].copy_from_slice(&bytes);
+                        <$larger>::from_be_bytes(buf)
+                    } else {
+                        buf[Self::SHIFT as usize / 8..].copy_from_slice(&bytes);
+                        <$larger>::from_le_bytes(buf)
+                    };
+
+                    // Moves the value back down, extending the sign of signed
+                    // types
+                    $name(value >> Self::SHIFT)
+                }
+
+                pub(crate) fn to_bytes(self, endian: Endian) -> [u8; $bytes] {
+                    let mut bytes = [0; $bytes];
+                    if is_big(endian) {
+                        bytes.copy_from_slice(&self.0.to_be_bytes()[Self::SHIFT as usize / 8..]);
+                    } else {
+                        bytes.copy_from_slice(&self.0.to_le_bytes()[..$bytes]);
+                    }
+
+                    bytes
+                }
+            }
+
+            impl From<$name> for $larger {
+                fn from(value: $name) -> Self {
+                    value.0
+                }
+            }
+
+            impl From<$smaller> for $name {
+                fn from(value: $smaller) -> Self {
+                    $name(value.into())
+                }
+            }
+
+            impl fmt::Debug for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Debug::fmt(&self.0, f)
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(&self.0, f)
+                }
+            }
+        )*
+    };
+}
+
+int_impl!(
+    u24(u32, 3) from u16,
+    i24(i32, 3) from i16,
+    u40(u64, 5) from u32,
+    i40(i64, 5) from i32,
+    u48(u64, 6) from u32,
+    i48(i64, 6) from i32,
+    u56(u64, 7) from u32,
+    i56(i64, 7) from i32,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Cursor;
+    use crate::{BinReaderExt, BinWriterExt};
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    #[test]
+    fn limits() {
+        assert_eq!(u24::MAX.get(), 0xFF_FFFF);
+        assert_eq!(i24::MIN.get(), -0x80_0000);
+        assert_eq!(i56::MAX.get(), 0x7F_FFFF_FFFF_FFFF);
+        assert_eq!(u40::new(0xFF_FFFF_FFFF), Some(u40(0xFF_FFFF_FFFF)));
+        assert_eq!(u40::new(0x100_0000_0000), None);
+        assert_eq!(i24::new(-0x80_0001), None);
+        assert_eq!(i24::from(-5i16).get(), -5);
+    }
+
+    #[test]
+    fn read_write() {
+        let data = b"\x80\x00\x01\x01\x02\x03\x04\x05";
+        let mut reader = Cursor::new(data);
+        assert_eq!(reader.read_be::<i24>().unwrap().get(), -0x7F_FFFF);
+        assert_eq!(reader.read_le::<u40>().unwrap().get(), 0x05_0403_0201);
+        assert!(reader.read_be::<u24>().is_err());
+        assert_eq!(reader.position(), 8);
+
+        let mut reader = Cursor::new(data);
+        assert_eq!(reader.read_le::<i24>().unwrap().get(), 0x01_0080);
+        assert_eq!(reader.read_be::<i40>().unwrap().get(), 0x01_0203_0405);
+
+        let mut writer = Cursor::new(Vec::new());
+        writer.write_be(&i24(-0x7F_FFFF)).unwrap();
+        writer.write_le(&u40(0x05_0403_0201)).unwrap();
+        assert_eq!(writer.into_inner(), data);
+
+        let mut writer = Cursor::new(Vec::new());
+        writer.write_le(&i48::MIN).unwrap();
+        assert_eq!(writer.into_inner(), b"\0\0\0\0\0\x80");
+    }
+}
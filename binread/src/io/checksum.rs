@@ -0,0 +1,114 @@
+use super::{Read, Result, Seek, SeekFrom, Write};
+use crate::checksum::Checksum;
+
+/// A reader or writer which adds every byte read or written through it to a
+/// [`Checksum`].
+///
+/// This is used by [`Checksummed`](crate::checksum::Checksummed), and can be
+/// used directly for checksums covering several values. Seeking is passed
+/// through to the inner stream without changing the checksum, so any bytes
+/// skipped are not included and any bytes read again are included twice.
+///
+/// ```rust
+/// # use binread::{BinReaderExt, checksum::Crc32, io::{ChecksumStream, Cursor}};
+/// let mut stream = ChecksumStream::<_, Crc32>::new(Cursor::new(b"12345678"));
+/// stream.read_be::<u32>().unwrap();
+/// stream.read_be::<u32>().unwrap();
+/// assert_eq!(stream.checksum(), 0x9AE0_DAAF);
+/// ```
+#[derive(Debug)]
+pub struct ChecksumStream<T, C> {
+    inner: T,
+    checksum: C,
+}
+
+impl<T, C: Checksum> ChecksumStream<T, C> {
+    /// Wraps `inner` with an empty checksum.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            checksum: C::default(),
+        }
+    }
+
+    /// Returns the checksum of the bytes read or written so far.
+    pub fn checksum(&self) -> C::Output {
+        self.checksum.finish()
+    }
+}
+
+impl<T, C> ChecksumStream<T, C> {
+    /// Gets a reference to the inner stream.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner stream. Bytes read or written
+    /// through it directly are not added to the checksum.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns the inner stream.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Read, C: Checksum> Read for ChecksumStream<T, C> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.checksum.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<T: Write, C: Checksum> Write for ChecksumStream<T, C> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.checksum.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<T: Seek, C> Seek for ChecksumStream<T, C> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impls {
+    use super::{ChecksumStream, Result, SeekFrom};
+    use crate::checksum::Checksum;
+    use crate::io::{AsyncRead, AsyncSeek};
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+
+    impl<T: AsyncRead + Unpin, C: Checksum + Unpin> AsyncRead for ChecksumStream<T, C> {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<Result<usize>> {
+            let this = self.get_mut();
+            let n = futures_util::ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+            this.checksum.update(&buf[..n]);
+            Poll::Ready(Ok(n))
+        }
+    }
+
+    impl<T: AsyncSeek + Unpin, C: Unpin> AsyncSeek for ChecksumStream<T, C> {
+        fn poll_seek(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            pos: SeekFrom,
+        ) -> Poll<Result<u64>> {
+            Pin::new(&mut self.get_mut().inner).poll_seek(cx, pos)
+        }
+    }
+}
@@ -82,6 +82,39 @@ impl<T: AsRef<[u8]>> Seek for Cursor<T> {
     }
 }
 
+impl Write for Cursor<&mut [u8]> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let pos = u64::min(self.pos, self.inner.len() as u64) as usize;
+        let amt = usize::min(self.inner.len() - pos, buf.len());
+        self.inner[pos..pos + amt].copy_from_slice(&buf[..amt]);
+        self.pos = (pos + amt) as u64;
+        Ok(amt)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Write for Cursor<crate::alloc::vec::Vec<u8>> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let pos = self.pos as usize;
+        let vec = &mut self.inner;
+        if vec.len() < pos {
+            vec.resize(pos, 0);
+        }
+        let overlap = usize::min(vec.len() - pos, buf.len());
+        vec[pos..pos + overlap].copy_from_slice(&buf[..overlap]);
+        vec.extend_from_slice(&buf[overlap..]);
+        self.pos += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
 #[cfg(all(test, feature = "std"))]
 mod test {
     use super::*;
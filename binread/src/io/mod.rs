@@ -1,20 +1,44 @@
 //! A swappable version of [std::io](std::io) that works in `no_std + alloc` environments.
 //! If the feature flag `std` is enabled (as it is by default), this will just re-export types from `std::io`.
+//!
+//! [`StreamReader`] adapts readers which can not seek, like network streams, so they can be used
+//! with [`BinRead`](crate::BinRead). [`SliceReader`] reads from a byte slice and is used by
+//! [`BinReadBorrowed`](crate::BinReadBorrowed) to borrow data from it. [`TakeSeek`] limits how far
+//! a reader can read, like [`Read::take`], while keeping it seekable. [`ChecksumStream`] computes a
+//! checksum of the data read or written through it.
+//!
+//! With the `async` feature, the `AsyncRead` and `AsyncSeek` traits used by `BinReadAsync` are
+//! re-exported here as well.
 
+mod checksum;
 pub mod error;
 pub mod prelude;
+mod read_limit;
+mod slice;
+mod stream;
+mod take_seek;
 
 #[cfg(any(not(feature = "std"), test))]
 pub mod cursor;
 
 #[cfg(any(not(feature = "std"), test))]
+#[cfg_attr(feature = "std", allow(dead_code))]
 mod no_std;
 
 #[cfg(not(feature = "std"))]
 pub use no_std::*;
 
+pub use checksum::ChecksumStream;
+pub(crate) use read_limit::ReadLimit;
+pub use slice::SliceReader;
+pub use stream::StreamReader;
+pub use take_seek::TakeSeek;
+
+#[cfg(feature = "async")]
+pub use futures_io::{AsyncRead, AsyncSeek};
+
 #[cfg(feature = "std")]
-pub use std::io::{Bytes, Cursor, Error, ErrorKind, Read, Result, Seek, SeekFrom};
+pub use std::io::{Bytes, Cursor, Error, ErrorKind, Read, Result, Seek, SeekFrom, Write};
 
 pub trait StreamPosition {
     fn stream_pos(&mut self) -> Result<u64>;
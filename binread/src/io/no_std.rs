@@ -79,6 +79,67 @@ impl<R: Read> Iterator for Bytes<R> {
     }
 }
 
+/// A simplified version of [std::io::Write](std::io::Write) for use in no_std environments
+pub trait Write {
+    fn write(&mut self, buf: &[u8]) -> Result<usize>;
+
+    fn flush(&mut self) -> Result<()>;
+
+    fn write_all(&mut self, mut buf: &[u8]) -> Result<()> {
+        while !buf.is_empty() {
+            match self.write(buf) {
+                Ok(0) => {
+                    return Err(Error::new(
+                        ErrorKind::WriteZero,
+                        "failed to write whole buffer",
+                    ))
+                }
+                Ok(n) => buf = &buf[n..],
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    fn by_ref(&mut self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+}
+
+impl<W: Write + ?Sized> Write for &mut W {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        (**self).write(buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        (**self).flush()
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        (**self).write_all(buf)
+    }
+}
+
+impl Write for crate::alloc::vec::Vec<u8> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum SeekFrom {
     Start(u64),
@@ -156,6 +217,21 @@ mod tests {
         assert_eq!(x.next().unwrap().unwrap(), 0);
     }
 
+    #[test]
+    fn write_all() {
+        let mut out = Cursor::new(crate::alloc::vec![0u8; 2]);
+        out.write_all(b"ABCD").unwrap();
+        assert_eq!(out.get_ref().as_slice(), b"ABCD");
+
+        let mut buf = [0u8; 2];
+        let mut out = Cursor::new(&mut buf[..]);
+        assert_eq!(
+            out.write_all(b"ABCD").unwrap_err().kind(),
+            ErrorKind::WriteZero
+        );
+        assert_eq!(&buf, b"AB");
+    }
+
     #[test]
     fn return_error() {
         struct ReturnError(Option<Error>);
@@ -0,0 +1,127 @@
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom, StreamPosition};
+use core::convert::TryFrom;
+
+/// A reader which counts the bytes read through it and fails once more than a
+/// maximum have been read, for [`Limits::max_bytes_read`](crate::Limits).
+///
+/// Unlike [`TakeSeek`](super::TakeSeek), seeking back does not allow bytes to
+/// be read again, so a file can not be made to read the same data forever.
+pub(crate) struct ReadLimit<T> {
+    inner: T,
+    remaining: u64,
+    /// The position where the limit was exceeded
+    exceeded_at: Option<u64>,
+}
+
+impl<T> ReadLimit<T> {
+    pub(crate) fn new(inner: T, max: u64) -> Self {
+        Self {
+            inner,
+            remaining: max,
+            exceeded_at: None,
+        }
+    }
+
+    /// Returns the position where the limit was exceeded, if it was
+    pub(crate) fn exceeded_at(&self) -> Option<u64> {
+        self.exceeded_at
+    }
+
+    /// Returns how much of a buffer of `len` bytes can be read
+    fn readable_len(&self, len: usize) -> usize {
+        usize::try_from(self.remaining).map_or(len, |max| max.min(len))
+    }
+}
+
+fn limit_error() -> Error {
+    // The no_std error has no `Error::other`
+    #[allow(clippy::io_other_error)]
+    Error::new(ErrorKind::Other, "read limit exceeded")
+}
+
+impl<T: Read + Seek> Read for ReadLimit<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.remaining == 0 {
+            self.exceeded_at = Some(self.inner.stream_pos()?);
+            return Err(limit_error());
+        }
+
+        let len = self.readable_len(buf.len());
+        let n = self.inner.read(&mut buf[..len])?;
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+}
+
+impl<T: Seek> Seek for ReadLimit<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impls {
+    use super::{limit_error, ReadLimit, Result, SeekFrom};
+    use crate::io::{AsyncRead, AsyncSeek};
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+
+    impl<T: AsyncRead + AsyncSeek + Unpin> AsyncRead for ReadLimit<T> {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<Result<usize>> {
+            let this = self.get_mut();
+            if buf.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+
+            if this.remaining == 0 {
+                let pos = futures_util::ready!(
+                    Pin::new(&mut this.inner).poll_seek(cx, SeekFrom::Current(0))
+                )?;
+                this.exceeded_at = Some(pos);
+                return Poll::Ready(Err(limit_error()));
+            }
+
+            let len = this.readable_len(buf.len());
+            let n = futures_util::ready!(Pin::new(&mut this.inner).poll_read(cx, &mut buf[..len]))?;
+            this.remaining -= n as u64;
+            Poll::Ready(Ok(n))
+        }
+    }
+
+    impl<T: AsyncSeek + Unpin> AsyncSeek for ReadLimit<T> {
+        fn poll_seek(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            pos: SeekFrom,
+        ) -> Poll<Result<u64>> {
+            Pin::new(&mut self.get_mut().inner).poll_seek(cx, pos)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Cursor;
+
+    #[test]
+    fn read_limit() {
+        let mut reader = ReadLimit::new(Cursor::new(b"0123456789"), 6);
+
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf).unwrap();
+        reader.seek(SeekFrom::Start(0)).unwrap();
+        assert_eq!(reader.read(&mut buf).unwrap(), 2);
+        assert_eq!(reader.exceeded_at(), None);
+        assert_eq!(reader.read(&mut buf).unwrap_err().kind(), ErrorKind::Other);
+        assert_eq!(reader.exceeded_at(), Some(2));
+    }
+}
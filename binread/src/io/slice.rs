@@ -0,0 +1,142 @@
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom};
+use core::convert::TryFrom;
+
+/// A reader over a byte slice which can hand out parts of the slice itself
+/// instead of copying them.
+///
+/// This is the reader used by [`BinReadBorrowed`](crate::BinReadBorrowed),
+/// which allows types to hold `&[u8]` and `&str` slices of the data being
+/// read. Since it also implements [`Read`] and [`Seek`], it can be used
+/// anywhere a [`Cursor`](crate::io::Cursor) over a slice can.
+///
+/// ```rust
+/// # use binread::io::SliceReader;
+/// let mut reader = SliceReader::new(b"\x02hi!");
+/// let len = reader.read_slice(1).unwrap()[0] as usize;
+/// assert_eq!(reader.read_slice(len).unwrap(), b"hi");
+/// assert_eq!(reader.remaining(), b"!");
+/// ```
+#[derive(Clone, Debug)]
+pub struct SliceReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> SliceReader<'a> {
+    /// Creates a reader positioned at the start of `data`.
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    /// Gets the whole slice being read, regardless of the current position.
+    pub fn get_ref(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns the current position of the reader.
+    pub fn position(&self) -> u64 {
+        self.pos as u64
+    }
+
+    /// Returns the data after the current position.
+    pub fn remaining(&self) -> &'a [u8] {
+        // Seeking may move the position past the end of the data
+        self.data.get(self.pos..).unwrap_or_default()
+    }
+
+    /// Returns a copy of the reader which can not read more than `len` bytes
+    /// past the current position, like [`TakeSeek`](super::TakeSeek).
+    pub fn limit(&self, len: u64) -> Self {
+        let end = usize::try_from(len)
+            .ok()
+            .and_then(|len| self.pos.checked_add(len))
+            .map_or(self.data.len(), |end| end.min(self.data.len()));
+        Self {
+            data: &self.data[..end],
+            pos: self.pos,
+        }
+    }
+
+    /// Returns the next `len` bytes and moves the position past them, or
+    /// returns an [`UnexpectedEof`](ErrorKind::UnexpectedEof) error without
+    /// moving if there are not enough bytes left.
+    pub fn read_slice(&mut self, len: usize) -> Result<&'a [u8]> {
+        let remaining = self.remaining();
+        if len <= remaining.len() {
+            self.pos += len;
+            Ok(&remaining[..len])
+        } else {
+            Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "failed to read the requested number of bytes",
+            ))
+        }
+    }
+}
+
+impl Read for SliceReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let remaining = self.remaining();
+        let n = remaining.len().min(buf.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl Seek for SliceReader<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(target) => (0, i64::try_from(target).unwrap_or(i64::MAX)),
+            SeekFrom::End(offset) => (self.data.len(), offset),
+            SeekFrom::Current(offset) => (self.pos, offset),
+        };
+
+        let target = i64::try_from(base)
+            .ok()
+            .and_then(|base| base.checked_add(offset))
+            .and_then(|target| usize::try_from(target).ok())
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                )
+            })?;
+
+        self.pos = target;
+        Ok(target as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_slice_and_seek() {
+        let mut reader = SliceReader::new(b"0123456789");
+        assert_eq!(reader.read_slice(3).unwrap(), b"012");
+        assert_eq!(reader.seek(SeekFrom::End(-2)).unwrap(), 8);
+        assert_eq!(
+            reader.read_slice(3).unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+        assert_eq!(reader.position(), 8);
+        assert_eq!(reader.seek(SeekFrom::Current(-8)).unwrap(), 0);
+        assert_eq!(
+            reader.seek(SeekFrom::Current(-1)).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+
+        reader.seek(SeekFrom::Start(20)).unwrap();
+        assert_eq!(reader.remaining(), b"");
+        let mut buf = [0; 2];
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+
+        reader.seek(SeekFrom::Start(2)).unwrap();
+        let mut limited = reader.limit(3);
+        assert_eq!(limited.remaining(), b"234");
+        assert_eq!(limited.seek(SeekFrom::End(0)).unwrap(), 5);
+        assert_eq!(reader.limit(100).remaining(), b"23456789");
+    }
+}
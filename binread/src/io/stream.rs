@@ -0,0 +1,252 @@
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom};
+use crate::alloc::{collections::VecDeque, format};
+
+/// The rewind buffer size used by [`StreamReader::new`]
+const DEFAULT_REWIND_CAPACITY: usize = 4096;
+
+/// The number of bytes read at a time when skipping forward
+const SKIP_CHUNK_LEN: usize = 256;
+
+/// An adapter which makes a source that can only be read, like a network
+/// stream, a pipe, or a decompressor, usable where [`Seek`] is required.
+///
+/// Seeking forward reads and discards data until the new position is reached.
+/// The most recently read bytes are kept in a rewind buffer of a fixed size,
+/// so seeking backward works as long as the data is still in the buffer.
+/// Seeking back further than that, for example with `restore_position` or a
+/// [`FilePtr`](crate::FilePtr) pointing to an earlier part of the data,
+/// returns an error. Seeking relative to the end of the data is not
+/// supported.
+///
+/// ```rust
+/// # use binread::{prelude::*, io::StreamReader};
+/// #[derive(BinRead)]
+/// struct Message {
+///     len: u8,
+///     #[br(restore_position)]
+///     kind: u8,
+///     #[br(count = len)]
+///     body: Vec<u8>,
+/// }
+///
+/// // Any type which only implements `Read` works here
+/// let source: &[u8] = b"\x03\x01\x02\x03";
+/// let message: Message = StreamReader::new(source).read_be().unwrap();
+/// assert_eq!(message.kind, 1);
+/// assert_eq!(message.body, [1, 2, 3]);
+/// ```
+#[derive(Debug)]
+pub struct StreamReader<R> {
+    inner: R,
+    /// The most recently read bytes, ending at `end`
+    buffer: VecDeque<u8>,
+    capacity: usize,
+    pos: u64,
+    /// The number of bytes read from `inner` so far
+    end: u64,
+}
+
+impl<R: Read> StreamReader<R> {
+    /// Creates an adapter with a rewind buffer of 4KiB.
+    pub fn new(inner: R) -> Self {
+        Self::with_capacity(inner, DEFAULT_REWIND_CAPACITY)
+    }
+
+    /// Creates an adapter which can seek backward by up to `capacity` bytes
+    /// from the furthest position read so far.
+    pub fn with_capacity(inner: R, capacity: usize) -> Self {
+        Self {
+            inner,
+            buffer: VecDeque::with_capacity(capacity),
+            capacity,
+            pos: 0,
+            end: 0,
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Consumes the adapter, returning the underlying reader. Any data in the
+    /// rewind buffer after the current position is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// The position of the first byte which can still be sought to
+    fn buffer_start(&self) -> u64 {
+        self.end - self.buffer.len() as u64
+    }
+
+    fn remember(&mut self, data: &[u8]) {
+        if data.len() >= self.capacity {
+            self.buffer.clear();
+            self.buffer.extend(&data[data.len() - self.capacity..]);
+        } else {
+            let overflow = (self.buffer.len() + data.len()).saturating_sub(self.capacity);
+            self.buffer.drain(..overflow);
+            self.buffer.extend(data);
+        }
+
+        self.end += data.len() as u64;
+    }
+
+    fn read_inner(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.remember(&buf[..n]);
+        Ok(n)
+    }
+
+    fn skip_to(&mut self, target: u64) -> Result<()> {
+        let mut chunk = [0; SKIP_CHUNK_LEN];
+        while self.end < target {
+            let len = (target - self.end).min(SKIP_CHUNK_LEN as u64) as usize;
+            match self.read_inner(&mut chunk[..len]) {
+                Ok(0) => {
+                    return Err(Error::new(
+                        ErrorKind::UnexpectedEof,
+                        format!(
+                            "cannot seek to 0x{:X}: the stream ended at 0x{:X}",
+                            target, self.end
+                        ),
+                    ))
+                }
+                Ok(_) => {}
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for StreamReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.pos < self.end {
+            let offset = (self.pos - self.buffer_start()) as usize;
+            let (front, back) = self.buffer.as_slices();
+            let available = if offset < front.len() {
+                &front[offset..]
+            } else {
+                &back[offset - front.len()..]
+            };
+
+            let n = available.len().min(buf.len());
+            buf[..n].copy_from_slice(&available[..n]);
+            self.pos += n as u64;
+            Ok(n)
+        } else {
+            let n = self.read_inner(buf)?;
+            self.pos += n as u64;
+            Ok(n)
+        }
+    }
+}
+
+impl<R: Read> Seek for StreamReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(target) => Some(target),
+            SeekFrom::Current(offset) => {
+                if offset < 0 {
+                    self.pos.checked_sub(offset.unsigned_abs())
+                } else {
+                    self.pos.checked_add(offset as u64)
+                }
+            }
+            SeekFrom::End(_) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "cannot seek relative to the end of a stream",
+                ))
+            }
+        };
+
+        let target = target.ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        if target < self.buffer_start() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "cannot seek back to 0x{:X}: data before 0x{:X} has already been discarded \
+                     (the rewind buffer holds {} bytes)",
+                    target,
+                    self.buffer_start(),
+                    self.capacity
+                ),
+            ));
+        }
+
+        self.skip_to(target)?;
+        self.pos = target;
+        Ok(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::StreamPosition;
+
+    /// A reader which returns at most 3 bytes per read and can not seek
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            let n = buf.len().min(self.0.len()).min(3);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    const DATA: &[u8] = b"0123456789ABCDEF";
+
+    fn read_byte<R: Read>(reader: &mut R) -> u8 {
+        let mut byte = [0];
+        reader.read_exact(&mut byte).unwrap();
+        byte[0]
+    }
+
+    #[test]
+    fn seek_forward_and_back() {
+        let mut reader = StreamReader::with_capacity(Trickle(DATA), 4);
+        assert_eq!(reader.seek(SeekFrom::Start(5)).unwrap(), 5);
+        assert_eq!(read_byte(&mut reader), b'5');
+        assert_eq!(reader.seek(SeekFrom::Current(-4)).unwrap(), 2);
+        let mut buf = [0; 6];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"234567");
+        assert_eq!(reader.stream_pos().unwrap(), 8);
+    }
+
+    #[test]
+    fn seek_past_buffer() {
+        let mut reader = StreamReader::with_capacity(Trickle(DATA), 4);
+        reader.seek(SeekFrom::Start(10)).unwrap();
+        assert_eq!(reader.seek(SeekFrom::Start(6)).unwrap(), 6);
+        let error = reader.seek(SeekFrom::Start(5)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(read_byte(&mut reader), b'6');
+    }
+
+    #[test]
+    fn seek_errors() {
+        let mut reader = StreamReader::new(Trickle(DATA));
+        let error = reader.seek(SeekFrom::End(0)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        let error = reader.seek(SeekFrom::Current(-1)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        let error = reader.seek(SeekFrom::Start(17)).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(reader.seek(SeekFrom::Start(16)).unwrap(), 16);
+    }
+}
@@ -0,0 +1,174 @@
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom, StreamPosition};
+use core::convert::TryFrom;
+
+/// A reader which stops reading at a fixed position of the reader it wraps,
+/// like [`Read::take`], while still implementing [`Seek`].
+///
+/// Positions are those of the inner reader, so [`FilePtr`](crate::FilePtr)
+/// offsets and error positions are the same as without the limit. Seeking
+/// relative to the end seeks relative to the limit.
+///
+/// This is the reader used by the [`size`](crate::attribute#size) directive.
+///
+/// ```rust
+/// # use binread::{io::{Cursor, Read, Seek, SeekFrom, TakeSeek}};
+/// let mut cursor = Cursor::new(b"\x01\x02\x03\x04\x05");
+/// cursor.seek(SeekFrom::Start(1)).unwrap();
+/// let mut chunk = TakeSeek::new(&mut cursor, 2).unwrap();
+/// let mut data = Vec::new();
+/// chunk.read_to_end(&mut data).unwrap();
+/// assert_eq!(data, [2, 3]);
+/// assert_eq!(chunk.seek(SeekFrom::End(-1)).unwrap(), 2);
+/// ```
+#[derive(Debug)]
+pub struct TakeSeek<T> {
+    inner: T,
+    pos: u64,
+    end: u64,
+}
+
+impl<T: Seek> TakeSeek<T> {
+    /// Wraps `inner` so that it can read at most `limit` bytes past its
+    /// current position.
+    pub fn new(mut inner: T, limit: u64) -> Result<Self> {
+        let pos = inner.stream_pos()?;
+        Ok(Self::with_pos(inner, pos, limit))
+    }
+}
+
+impl<T> TakeSeek<T> {
+    /// Wraps `inner`, whose current position is `pos`, so that it can read
+    /// at most `limit` bytes past it.
+    pub fn with_pos(inner: T, pos: u64, limit: u64) -> Self {
+        Self {
+            inner,
+            pos,
+            end: pos.saturating_add(limit),
+        }
+    }
+
+    /// Returns the number of bytes which can still be read.
+    pub fn limit(&self) -> u64 {
+        self.end.saturating_sub(self.pos)
+    }
+
+    /// Gets a reference to the inner reader.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the inner reader. Moving the inner reader
+    /// will leave the limit wrong until the next seek.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Converts a seek relative to the end to one relative to the limit
+    fn resolve(&self, pos: SeekFrom) -> Result<SeekFrom> {
+        match pos {
+            SeekFrom::End(offset) => i64::try_from(self.end)
+                .ok()
+                .and_then(|end| end.checked_add(offset))
+                .and_then(|target| u64::try_from(target).ok())
+                .map(SeekFrom::Start)
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        "invalid seek to a negative or overflowing position",
+                    )
+                }),
+            pos => Ok(pos),
+        }
+    }
+
+    /// Returns the length of the part of a buffer of `len` bytes which can be
+    /// read without passing the limit
+    fn readable_len(&self, len: usize) -> usize {
+        usize::try_from(self.limit()).map_or(len, |limit| limit.min(len))
+    }
+}
+
+impl<T: Read> Read for TakeSeek<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let len = self.readable_len(buf.len());
+        let n = self.inner.read(&mut buf[..len])?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<T: Seek> Seek for TakeSeek<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let pos = self.resolve(pos)?;
+        self.pos = self.inner.seek(pos)?;
+        Ok(self.pos)
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impls {
+    use super::{Result, SeekFrom, TakeSeek};
+    use crate::io::{AsyncRead, AsyncSeek};
+    use core::pin::Pin;
+    use core::task::{Context, Poll};
+
+    impl<T: AsyncRead + Unpin> AsyncRead for TakeSeek<T> {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<Result<usize>> {
+            let this = self.get_mut();
+            let len = this.readable_len(buf.len());
+            let n = futures_util::ready!(Pin::new(&mut this.inner).poll_read(cx, &mut buf[..len]))?;
+            this.pos += n as u64;
+            Poll::Ready(Ok(n))
+        }
+    }
+
+    impl<T: AsyncSeek + Unpin> AsyncSeek for TakeSeek<T> {
+        fn poll_seek(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            pos: SeekFrom,
+        ) -> Poll<Result<u64>> {
+            let this = self.get_mut();
+            let pos = this.resolve(pos)?;
+            this.pos = futures_util::ready!(Pin::new(&mut this.inner).poll_seek(cx, pos))?;
+            Poll::Ready(Ok(this.pos))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Cursor;
+
+    #[test]
+    fn read_and_seek_within_limit() {
+        let mut cursor = Cursor::new(b"0123456789");
+        cursor.seek(SeekFrom::Start(2)).unwrap();
+        let mut reader = TakeSeek::new(&mut cursor, 4).unwrap();
+
+        let mut buf = [0; 8];
+        assert_eq!(reader.read(&mut buf).unwrap(), 4);
+        assert_eq!(&buf[..4], b"2345");
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+        assert_eq!(reader.limit(), 0);
+
+        assert_eq!(reader.seek(SeekFrom::End(-1)).unwrap(), 5);
+        assert_eq!(reader.read(&mut buf).unwrap(), 1);
+        assert_eq!(reader.seek(SeekFrom::Start(0)).unwrap(), 0);
+        assert_eq!(reader.limit(), 6);
+        assert_eq!(
+            reader.seek(SeekFrom::End(-7)).unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+    }
+}
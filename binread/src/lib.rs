@@ -95,6 +95,31 @@
 //!
 //! For a list of attributes see the [`attribute`](attribute) module
 //!
+//! # Writing
+//!
+//! The [`BinWrite`](BinWrite) trait is the inverse of `BinRead`, and is implemented for the
+//! same primitives and collections. It can be derived using the same `#[br]` attributes, so
+//! a single type definition can be used both to parse data and to write it back out:
+//!
+//! ```rust
+//! # use binread::{prelude::*, io::Cursor};
+//! #[derive(BinRead, BinWrite)]
+//! #[br(big, magic = b"DOG")]
+//! struct Dog {
+//!     bone_pile_count: u8,
+//!
+//!     #[br(count = bone_pile_count)]
+//!     bone_piles: Vec<u16>,
+//! }
+//!
+//! let mut writer = Cursor::new(Vec::new());
+//! writer.write_ne(&Dog { bone_pile_count: 1, bone_piles: vec![0x12] }).unwrap();
+//! assert_eq!(writer.into_inner(), b"DOG\x01\x00\x12");
+//! ```
+//!
+//! See the [`attribute`](attribute::index.html#writing) module for how each directive
+//! behaves when writing.
+//!
 //! ## Generics
 //! The BinRead derive macro also allows for generic parsing. That way you can build up
 //! higher-level parsers that can have their type swapped out to allow greater reuse of code.
@@ -102,7 +127,7 @@
 //! ```rust
 //! # use binread::{prelude::*, io::Cursor};
 //! #[derive(BinRead)]
-//! struct U32CountVec<T: BinRead<Args=()>> {
+//! struct U32CountVec<T: for<'a> BinRead<Args<'a> = ()>> {
 //!     count: u32,
 //!     #[br(count = count)]
 //!     data: Vec<T>,
@@ -110,12 +135,43 @@
 //! ```
 //!
 //! In order to parse generically, we have to (in some way) bound `Args`. The easiest way to do
-//! this is to bound `<T as BinRead>::Args` to `()` (no arguments), however it is also possible to
-//! either accept a specific set of arguments or be generic over the given arguments.
+//! this is to bound `<T as BinRead>::Args<'a>` to `()` (no arguments) for every lifetime `'a`,
+//! however it is also possible to either accept a specific set of arguments or be generic over
+//! the given arguments.
+//!
+//! ## Borrowed arguments
+//! Arguments may borrow from the caller, which allows passing a field that was read earlier
+//! in the same struct to a child without copying it. Any references in `import` which don't
+//! name a lifetime are given the lifetime of the arguments:
+//!
+//! ```rust
+//! # use binread::{prelude::*, io::Cursor};
+//! #[derive(BinRead)]
+//! #[br(import(offsets: &[u32]))]
+//! struct Entries {
+//!     #[br(count = offsets.len())]
+//!     sizes: Vec<u8>,
+//! }
+//!
+//! #[derive(BinRead)]
+//! struct Header {
+//!     count: u8,
+//!     #[br(count = count)]
+//!     offsets: Vec<u32>,
+//!     #[br(args(&offsets))]
+//!     entries: Entries,
+//! }
+//!
+//! let header: Header = Cursor::new(b"\x01\0\0\0\x05\x07").read_be().unwrap();
+//! assert_eq!(header.entries.sizes, [7]);
+//! ```
 //!
 //! ## Features
 //!
-//! * `const_generics` - Change array [`BinRead`] implementation to use const generics
+//! * `async` - Enable reading from async readers with `BinReadAsync`
+//! * `const_generics` - Change array [`BinRead`] implementation to use const generics, and enable
+//!   `FixedString`
+//! * `legacy_encodings` - Enable the Shift-JIS and Windows-1252 string encodings
 //! * `std` - Disable this feature to enable `no_std` support, on by default
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(rust_2018_idioms)]
@@ -129,108 +185,181 @@ extern crate alloc;
 #[cfg(not(feature = "std"))]
 use alloc::{boxed::Box, string::String, vec::Vec};
 
+/// Implements `into_inner`, `From`, `Deref` and `DerefMut` for tuple structs
+/// which wrap a single value, such as [`Leb128`](varint::Leb128) and
+/// [`Be`](endian::Be)
+macro_rules! wrapper_impl {
+    ($($name:ident),*) => {
+        $(
+            impl<T> $name<T> {
+                /// Returns the value
+                pub fn into_inner(self) -> T {
+                    self.0
+                }
+            }
+
+            impl<T> From<T> for $name<T> {
+                fn from(value: T) -> Self {
+                    $name(value)
+                }
+            }
+
+            impl<T> core::ops::Deref for $name<T> {
+                type Target = T;
+
+                fn deref(&self) -> &T {
+                    &self.0
+                }
+            }
+
+            impl<T> core::ops::DerefMut for $name<T> {
+                fn deref_mut(&mut self) -> &mut T {
+                    &mut self.0
+                }
+            }
+        )*
+    };
+}
+
 #[doc(hidden)]
 #[path = "private.rs"]
 pub mod __private;
 
 pub mod attribute;
+pub mod bits;
+pub mod checksum;
+pub mod collections;
 pub mod endian;
 pub mod error;
 pub mod file_ptr;
+pub mod float;
 pub mod helpers;
+pub mod int;
 pub mod io;
+pub mod named_args;
 #[doc(hidden)]
 pub mod options;
 #[doc(hidden)]
 pub mod pos_value;
 pub mod punctuated;
+#[cfg(feature = "async")]
+pub mod read_async;
+pub mod read_borrowed;
 #[doc(hidden)]
 pub mod strings;
+pub mod varint;
 
 #[cfg(feature = "std")]
 #[cfg(feature = "debug_template")]
 pub mod binary_template;
 
-use core::any::{Any, TypeId};
-
 #[doc(inline)]
 pub use {
+    checksum::Checksummed,
     endian::Endian,
     error::Error,
-    file_ptr::{FilePtr, FilePtr128, FilePtr16, FilePtr32, FilePtr64, FilePtr8},
+    file_ptr::{
+        FilePtr, FilePtr128, FilePtr16, FilePtr32, FilePtr64, FilePtr8, LazyFilePtr,
+        NullableFilePtr,
+    },
     helpers::{count, until, until_eof, until_exclusive},
-    options::ReadOptions,
+    named_args::NamedArgs,
+    options::{Limits, ReadOptions, WriteOptions},
     pos_value::PosValue,
-    strings::{NullString, NullWideString},
+    read_borrowed::BinReadBorrowed,
+    strings::{EncodedNullString, NullString, NullWideString, PrefixedString, PrefixedVec},
 };
 
-use io::{Read, Seek, SeekFrom, StreamPosition};
+use io::{Read, Seek, SeekFrom, StreamPosition, Write};
 
 /// Derive macro for BinRead. [Usage here](BinRead).
 pub use binread_derive::BinRead;
 
+/// Derive macro for BinWrite. [Usage here](BinWrite).
+pub use binread_derive::BinWrite;
+
 /// Equivalent to `derive(BinRead)` but allows for temporary variables.
 pub use binread_derive::derive_binread;
 
+/// Derive macro for BinReadBorrowed. [Usage here](read_borrowed).
+pub use binread_derive::BinReadBorrowed;
+
+/// Derive macro for BinReadAsync. [Usage here](read_async).
+#[cfg(feature = "async")]
+pub use binread_derive::BinReadAsync;
+
+#[cfg(feature = "async")]
+#[doc(inline)]
+pub use read_async::{BinReadAsync, BinReaderAsyncExt};
+
+#[cfg(feature = "const_generics")]
+#[doc(inline)]
+pub use strings::{FixedString, FixedWideString};
+
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use file_ptr::SharedFilePtr;
+
 mod binread_impls;
-pub use binread_impls::*;
+mod binwrite_impls;
 
 /// A Result for any binread function that can return an error
 pub type BinResult<T> = core::result::Result<T, Error>;
 
+/// Arguments which can be created without any input from the caller.
+///
+/// Types whose arguments implement this trait can be read with [`read`](BinRead::read) and
+/// written with [`write`](BinWrite::write). It is implemented for `()` and for named arguments
+/// where every argument has a default value.
+pub trait DefaultArgs {
+    /// Returns the default value of the arguments
+    fn default_args() -> Self;
+}
+
+impl DefaultArgs for () {
+    fn default_args() -> Self {}
+}
+
 /// A `BinRead` trait allows reading a structure from anything that implements [`io::Read`](io::Read) and [`io::Seek`](io::Seek)
 /// BinRead is implemented on the type to be read out of the given reader
 pub trait BinRead: Sized {
     /// The type of arguments needed to be supplied in order to read this type, usually a tuple.
+    /// The lifetime allows arguments to borrow data from the caller, such as a previously read
+    /// field of the parent struct.
     ///
     /// **NOTE:** For types that don't require any arguments, use the unit (`()`) type. This will allow [`read`](BinRead::read) to be used.
-    type Args: Any + Copy;
+    type Args<'a>: Clone;
 
     /// Read the type from the reader while assuming no arguments have been passed
-    ///
-    /// # Panics
-    /// Panics if there is no [`args_default`](BinRead::args_default) implementation
-    fn read<R: Read + Seek>(reader: &mut R) -> BinResult<Self> {
-        let args = match Self::args_default() {
-            Some(args) => args,
-            None => panic!("Must pass args, no args_default implemented"),
-        };
-
-        Self::read_options(reader, &ReadOptions::default(), args)
+    fn read<R: Read + Seek>(reader: &mut R) -> BinResult<Self>
+    where
+        for<'a> Self::Args<'a>: DefaultArgs,
+    {
+        file_ptr::read_scope(|| {
+            Self::read_options(reader, &ReadOptions::default(), DefaultArgs::default_args())
+        })
     }
 
     /// Read the type from the reader using the specified arguments
-    fn read_args<R: Read + Seek>(reader: &mut R, args: Self::Args) -> BinResult<Self> {
-        Self::read_options(reader, &ReadOptions::default(), args)
+    fn read_args<R: Read + Seek>(reader: &mut R, args: Self::Args<'_>) -> BinResult<Self> {
+        file_ptr::read_scope(|| Self::read_options(reader, &ReadOptions::default(), args))
     }
 
     /// Read the type from the reader
     fn read_options<R: Read + Seek>(
         reader: &mut R,
         options: &ReadOptions,
-        args: Self::Args,
+        args: Self::Args<'_>,
     ) -> BinResult<Self>;
 
     fn after_parse<R: Read + Seek>(
         &mut self,
         _: &mut R,
         _: &ReadOptions,
-        _: Self::Args,
+        _: Self::Args<'_>,
     ) -> BinResult<()> {
         Ok(())
     }
-
-    /// The default arguments to be used when using the [`read`](BinRead::read) shortcut method.
-    /// Override this for any type that optionally requries arguments
-    fn args_default() -> Option<Self::Args> {
-        // Trick to effectively get specialization on stable, should constant-folded away
-        // Returns `Some(())` if Self::Args == (), otherwise returns `None`
-        if TypeId::of::<Self::Args>() == TypeId::of::<()>() {
-            Some(unsafe { core::mem::MaybeUninit::uninit().assume_init() })
-        } else {
-            None
-        }
-    }
 }
 
 /// An extension trait for [`io::Read`](io::Read) to provide methods for reading a value directly
@@ -250,76 +379,253 @@ pub trait BinRead: Sized {
 /// ```
 pub trait BinReaderExt: Read + Seek + Sized {
     /// Read the given type from the reader using the given endianness.
-    fn read_type<T: BinRead>(&mut self, endian: Endian) -> BinResult<T> {
-        let args = match T::args_default() {
-            Some(args) => args,
-            None => panic!("Must pass args, no args_default implemented"),
-        };
-
-        let options = ReadOptions {
-            endian,
-            ..Default::default()
-        };
-
-        let mut res = T::read_options(self, &options, args)?;
-        res.after_parse(self, &options, args)?;
-
-        Ok(res)
+    fn read_type<T: BinRead>(&mut self, endian: Endian) -> BinResult<T>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
+        self.read_type_args(endian, DefaultArgs::default_args())
     }
 
     /// Read the given type from the reader with big endian byteorder
-    fn read_be<T: BinRead>(&mut self) -> BinResult<T> {
+    fn read_be<T: BinRead>(&mut self) -> BinResult<T>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
         self.read_type(Endian::Big)
     }
 
     /// Read the given type from the reader with little endian byteorder
-    fn read_le<T: BinRead>(&mut self) -> BinResult<T> {
+    fn read_le<T: BinRead>(&mut self) -> BinResult<T>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
         self.read_type(Endian::Little)
     }
 
     /// Read the given type from the reader with the native byteorder
-    fn read_ne<T: BinRead>(&mut self) -> BinResult<T> {
+    fn read_ne<T: BinRead>(&mut self) -> BinResult<T>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
         self.read_type(Endian::Native)
     }
 
     /// Read `T` from the reader with the given byte order and arguments.
-    fn read_type_args<T: BinRead>(&mut self, endian: Endian, args: T::Args) -> BinResult<T> {
+    fn read_type_args<T: BinRead>(&mut self, endian: Endian, args: T::Args<'_>) -> BinResult<T> {
         let options = ReadOptions {
             endian,
             ..Default::default()
         };
 
-        let mut res = T::read_options(self, &options, args.clone())?;
-        res.after_parse(self, &options, args)?;
+        file_ptr::read_scope(|| {
+            let mut res = T::read_options(self, &options, args.clone())?;
+            res.after_parse(self, &options, args)?;
+
+            Ok(res)
+        })
+    }
+
+    /// Read `T` from the reader with the given byte order and arguments,
+    /// returning a [`LimitExceeded`](Error::LimitExceeded) error if reading
+    /// goes over any of the given [`Limits`].
+    fn read_with_limits<T: BinRead>(
+        &mut self,
+        endian: Endian,
+        limits: Limits,
+        args: T::Args<'_>,
+    ) -> BinResult<T> {
+        let options = ReadOptions {
+            endian,
+            limits,
+            ..Default::default()
+        };
 
-        Ok(res)
+        file_ptr::read_scope(|| {
+            let max_bytes_read = match limits.max_bytes_read {
+                Some(max) => max,
+                None => {
+                    let mut res = T::read_options(self, &options, args.clone())?;
+                    res.after_parse(self, &options, args)?;
+                    return Ok(res);
+                }
+            };
+
+            let mut reader = io::ReadLimit::new(self, max_bytes_read);
+            let result = T::read_options(&mut reader, &options, args.clone())
+                .and_then(|mut res| res.after_parse(&mut reader, &options, args).map(|_| res));
+
+            match (result, reader.exceeded_at()) {
+                (Err(_), Some(pos)) => Err(Error::LimitExceeded {
+                    pos,
+                    limit: "max_bytes_read",
+                    max: max_bytes_read,
+                }),
+                (result, _) => result,
+            }
+        })
     }
 
     /// Read `T` from the reader, assuming big-endian byte order, using the
     /// given arguments.
-    fn read_be_args<T: BinRead>(&mut self, args: T::Args) -> BinResult<T> {
+    fn read_be_args<T: BinRead>(&mut self, args: T::Args<'_>) -> BinResult<T> {
         self.read_type_args(Endian::Big, args)
     }
 
     /// Read `T` from the reader, assuming little-endian byte order, using the
     /// given arguments.
-    fn read_le_args<T: BinRead>(&mut self, args: T::Args) -> BinResult<T> {
+    fn read_le_args<T: BinRead>(&mut self, args: T::Args<'_>) -> BinResult<T> {
         self.read_type_args(Endian::Little, args)
     }
 
     /// Read `T` from the reader, assuming native-endian byte order, using the
     /// given arguments.
-    fn read_ne_args<T: BinRead>(&mut self, args: T::Args) -> BinResult<T> {
+    fn read_ne_args<T: BinRead>(&mut self, args: T::Args<'_>) -> BinResult<T> {
         self.read_type_args(Endian::Native, args)
     }
 }
 
 impl<R: Read + Seek + Sized> BinReaderExt for R {}
 
+/// A `BinWrite` trait allows writing a structure to anything that implements [`io::Write`](io::Write) and [`io::Seek`](io::Seek)
+/// BinWrite is the inverse of [`BinRead`](BinRead) and is implemented on the type to be written
+pub trait BinWrite {
+    /// The type of arguments needed to be supplied in order to write this type, usually a tuple.
+    /// As with [`BinRead::Args`], the lifetime allows arguments to borrow data from the caller.
+    ///
+    /// **NOTE:** For types that don't require any arguments, use the unit (`()`) type. This will allow [`write`](BinWrite::write) to be used.
+    type Args<'a>: Clone;
+
+    /// Write the type to the writer while assuming no arguments have been passed
+    fn write<W: Write + Seek>(&self, writer: &mut W) -> BinResult<()>
+    where
+        for<'a> Self::Args<'a>: DefaultArgs,
+    {
+        self.write_options(
+            writer,
+            &WriteOptions::default(),
+            DefaultArgs::default_args(),
+        )
+    }
+
+    /// Write the type to the writer using the specified arguments
+    fn write_args<W: Write + Seek>(&self, writer: &mut W, args: Self::Args<'_>) -> BinResult<()> {
+        self.write_options(writer, &WriteOptions::default(), args)
+    }
+
+    /// Write the type to the writer
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()>;
+}
+
+/// An extension trait for [`io::Write`](io::Write) to provide methods for writing a value directly
+///
+/// ## Example
+/// ```rust
+/// use binread::prelude::*; // BinWriterExt is in the prelude
+/// use binread::endian::LE;
+/// use binread::io::Cursor;
+///
+/// let mut writer = Cursor::new(Vec::new());
+/// writer.write_le(&7u32).unwrap();
+/// writer.write_type(&0xCCu16, LE).unwrap();
+/// writer.write_be(&5u16).unwrap();
+///
+/// assert_eq!(writer.into_inner(), b"\x07\0\0\0\xCC\0\0\x05");
+/// ```
+pub trait BinWriterExt: Write + Seek + Sized {
+    /// Write the given value to the writer using the given endianness.
+    fn write_type<T: BinWrite + ?Sized>(&mut self, value: &T, endian: Endian) -> BinResult<()>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
+        self.write_type_args(value, endian, DefaultArgs::default_args())
+    }
+
+    /// Write the given value to the writer with big endian byteorder
+    fn write_be<T: BinWrite + ?Sized>(&mut self, value: &T) -> BinResult<()>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
+        self.write_type(value, Endian::Big)
+    }
+
+    /// Write the given value to the writer with little endian byteorder
+    fn write_le<T: BinWrite + ?Sized>(&mut self, value: &T) -> BinResult<()>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
+        self.write_type(value, Endian::Little)
+    }
+
+    /// Write the given value to the writer with the native byteorder
+    fn write_ne<T: BinWrite + ?Sized>(&mut self, value: &T) -> BinResult<()>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
+        self.write_type(value, Endian::Native)
+    }
+
+    /// Write `T` to the writer with the given byte order and arguments.
+    fn write_type_args<T: BinWrite + ?Sized>(
+        &mut self,
+        value: &T,
+        endian: Endian,
+        args: T::Args<'_>,
+    ) -> BinResult<()> {
+        let options = WriteOptions {
+            endian,
+            ..Default::default()
+        };
+
+        value.write_options(self, &options, args)
+    }
+
+    /// Write `T` to the writer, using big-endian byte order and the given
+    /// arguments.
+    fn write_be_args<T: BinWrite + ?Sized>(
+        &mut self,
+        value: &T,
+        args: T::Args<'_>,
+    ) -> BinResult<()> {
+        self.write_type_args(value, Endian::Big, args)
+    }
+
+    /// Write `T` to the writer, using little-endian byte order and the given
+    /// arguments.
+    fn write_le_args<T: BinWrite + ?Sized>(
+        &mut self,
+        value: &T,
+        args: T::Args<'_>,
+    ) -> BinResult<()> {
+        self.write_type_args(value, Endian::Little, args)
+    }
+
+    /// Write `T` to the writer, using native-endian byte order and the given
+    /// arguments.
+    fn write_ne_args<T: BinWrite + ?Sized>(
+        &mut self,
+        value: &T,
+        args: T::Args<'_>,
+    ) -> BinResult<()> {
+        self.write_type_args(value, Endian::Native, args)
+    }
+}
+
+impl<W: Write + Seek + Sized> BinWriterExt for W {}
+
 /// The collection of traits and types you'll likely need when working with binread and are
 /// unlikely to cause name conflicts.
 pub mod prelude {
     pub use crate::BinRead;
+    pub use crate::BinReadBorrowed;
     pub use crate::BinReaderExt;
     pub use crate::BinResult;
+    pub use crate::BinWrite;
+    pub use crate::BinWriterExt;
+    #[cfg(feature = "async")]
+    pub use crate::{BinReadAsync, BinReaderAsyncExt};
 }
@@ -0,0 +1,39 @@
+//! Support types for named arguments.
+//!
+//! Using `#[br(import { ... })]` on a type generates a struct holding each
+//! argument by name, along with a builder which is used by
+//! `#[br(args { ... })]` to construct it. Required arguments are tracked in
+//! the type of the builder, so forgetting one is a compile error instead of a
+//! runtime error.
+//!
+//! For example, `#[br(import { count: usize, version: u8 = 1 })]` on `MyType`
+//! generates `MyTypeBinReadArgs`, which can be constructed with:
+//!
+//! ```text
+//! MyTypeBinReadArgs::builder().count(4).finalize()
+//! ```
+//!
+//! Deriving [`BinWrite`](crate::BinWrite) generates an equivalent
+//! `MyTypeBinWriteArgs` struct.
+
+/// A type of arguments which can be constructed using a builder.
+///
+/// This is implemented automatically for the arguments struct generated by
+/// named imports. It can also be implemented manually to allow
+/// `#[br(args { ... })]` to be used with a hand-written `BinRead`
+/// implementation.
+pub trait NamedArgs {
+    /// The builder, before any arguments have been set.
+    type Builder;
+
+    /// Creates a new builder for these arguments.
+    fn builder() -> Self::Builder;
+}
+
+/// Marks a required argument that has not been set yet.
+#[doc(hidden)]
+pub enum Needed {}
+
+/// Marks a required argument that has been set.
+#[doc(hidden)]
+pub enum Satisfied {}
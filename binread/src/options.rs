@@ -7,9 +7,177 @@ pub struct ReadOptions {
     pub endian: Endian,
     pub count: Option<usize>,
     pub offset: u64,
+    /// Limits for reading untrusted data, which are passed down to every value
+    pub limits: Limits,
+    /// The number of pointers followed to reach the value being read
+    pub pointer_depth: u32,
 
     #[cfg(feature = "debug_template")]
     pub dont_output_to_template: bool,
     #[cfg(feature = "debug_template")]
     pub variable_name: Option<&'static str>,
 }
+
+/// Runtime-configured options for writing the type using [`BinWrite`](BinWrite)
+#[non_exhaustive]
+#[derive(Default, Clone, Copy)]
+pub struct WriteOptions {
+    pub endian: Endian,
+}
+
+/// Limits on the resources used while reading, so that corrupt or malicious
+/// data can not make reading allocate huge amounts of memory or follow
+/// pointers forever.
+///
+/// A value over a limit returns a [`LimitExceeded`](Error::LimitExceeded)
+/// error naming the limit. Only [`max_depth`](Limits::max_depth) is limited by
+/// default, to [`DEFAULT_MAX_DEPTH`](Limits::DEFAULT_MAX_DEPTH), so that
+/// pointers which lead back to themselves return an error instead of
+/// overflowing the stack. Limits are set with
+/// [`BinReaderExt::read_with_limits`], or in the [`ReadOptions`] passed to
+/// [`BinRead::read_options`].
+///
+/// ```rust
+/// use binread::{prelude::*, io::Cursor, Endian, Error, Limits};
+///
+/// #[derive(BinRead, Debug)]
+/// struct Packet {
+///     len: u32,
+///     #[br(count = len)]
+///     data: Vec<u8>,
+/// }
+///
+/// let limits = Limits::new().max_count(1024).max_bytes_read(4096);
+/// let mut reader = Cursor::new(b"\xFF\xFF\xFF\xFF\0\0\0\0");
+/// let error = reader
+///     .read_with_limits::<Packet>(Endian::Big, limits, ())
+///     .unwrap_err();
+/// assert!(matches!(
+///     error.root_cause(),
+///     Error::LimitExceeded { limit: "max_count", max: 1024, .. }
+/// ));
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    /// The most values which can be read into a `Vec`, string or other
+    /// collection, whether they are counted with `count` or read with helpers
+    /// like [`until_eof`](crate::helpers::until_eof)
+    pub max_count: Option<usize>,
+    /// The most bytes which can be allocated for the values of a single
+    /// collection or string
+    pub max_alloc: Option<usize>,
+    /// The most pointers which can be followed to reach a value, counting
+    /// the pointer being read. Setting this to `None` follows pointers until
+    /// the stack overflows.
+    pub max_depth: Option<u32>,
+    /// The most bytes which can be read in total. This is only enforced by
+    /// [`BinReaderExt::read_with_limits`] and, with the `async` feature,
+    /// `BinReaderAsyncExt::read_with_limits_async`, which count the bytes
+    /// read from the reader.
+    pub max_bytes_read: Option<u64>,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_count: None,
+            max_alloc: None,
+            max_depth: Some(Self::DEFAULT_MAX_DEPTH),
+            max_bytes_read: None,
+        }
+    }
+}
+
+impl Limits {
+    /// The most pointers which can be followed by default
+    pub const DEFAULT_MAX_DEPTH: u32 = 128;
+
+    /// Creates the default limits, which only limit
+    /// [`max_depth`](Limits::max_depth)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the most values which can be read into a collection
+    pub fn max_count(mut self, max: usize) -> Self {
+        self.max_count = Some(max);
+        self
+    }
+
+    /// Sets the most bytes which can be allocated for a single collection
+    pub fn max_alloc(mut self, max: usize) -> Self {
+        self.max_alloc = Some(max);
+        self
+    }
+
+    /// Sets the most pointers which can be followed to reach a value
+    pub fn max_depth(mut self, max: u32) -> Self {
+        self.max_depth = Some(max);
+        self
+    }
+
+    /// Sets the most bytes which can be read in total
+    pub fn max_bytes_read(mut self, max: u64) -> Self {
+        self.max_bytes_read = Some(max);
+        self
+    }
+
+    /// Checks that `count` values of type `T` can be read into a collection
+    pub(crate) fn check_count<T>(&self, count: usize) -> Result<(), LimitError> {
+        check(
+            self.max_count.map(|max| max as u64),
+            count as u64,
+            "max_count",
+        )?;
+        self.check_alloc(count.saturating_mul(core::mem::size_of::<T>()))
+    }
+
+    /// Checks that `bytes` bytes can be allocated for a collection
+    pub(crate) fn check_alloc(&self, bytes: usize) -> Result<(), LimitError> {
+        check(
+            self.max_alloc.map(|max| max as u64),
+            bytes as u64,
+            "max_alloc",
+        )
+    }
+}
+
+/// The limit which was exceeded, before the position is known
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct LimitError {
+    limit: &'static str,
+    max: u64,
+}
+
+impl LimitError {
+    pub(crate) fn at(self, pos: u64) -> Error {
+        Error::LimitExceeded {
+            pos,
+            limit: self.limit,
+            max: self.max,
+        }
+    }
+}
+
+fn check(max: Option<u64>, value: u64, limit: &'static str) -> Result<(), LimitError> {
+    match max {
+        Some(max) if value > max => Err(LimitError { limit, max }),
+        _ => Ok(()),
+    }
+}
+
+impl ReadOptions {
+    /// Returns the options for reading the value a pointer points to, or an
+    /// error at `pos` if that would follow too many pointers
+    pub(crate) fn follow_pointer(&self, pos: u64) -> BinResult<ReadOptions> {
+        let depth = u64::from(self.pointer_depth) + 1;
+        check(self.limits.max_depth.map(u64::from), depth, "max_depth")
+            .map_err(|limit| limit.at(pos))?;
+
+        Ok(ReadOptions {
+            pointer_depth: self.pointer_depth + 1,
+            ..*self
+        })
+    }
+}
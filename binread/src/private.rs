@@ -1,6 +1,17 @@
-use crate::{io, BinRead, BinResult, Error, ReadOptions};
+use crate::{
+    error::ContextFrame,
+    io::{self, StreamPosition},
+    BinRead, BinReadBorrowed, BinResult, Endian, Error, NamedArgs, ReadOptions, WriteOptions,
+};
+#[cfg(feature = "async")]
+use crate::{
+    io::{AsyncRead, AsyncSeek},
+    BinReadAsync,
+};
 #[cfg(not(feature = "std"))]
-use alloc::{boxed::Box, string::String};
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+#[cfg(feature = "async")]
+use core::future::Future;
 
 pub enum AssertErrorFn<M, E> {
     Message(M),
@@ -48,16 +59,138 @@ where
     f
 }
 
-pub fn try_after_parse<Reader, ValueType, ArgType>(
+/// Adds the field being read to an error. The position is taken after the
+/// error occurred so that reads which succeed do not pay for it.
+pub fn field_context<R: io::Seek>(
+    reader: &mut R,
+    error: Error,
+    type_name: &'static str,
+    field: &'static str,
+    line: u32,
+) -> Error {
+    let pos = reader.stream_pos().unwrap_or_default();
+    error.with_context(ContextFrame::Field {
+        type_name,
+        field,
+        pos,
+        line,
+    })
+}
+
+// The async implementations generated by `derive(BinReadAsync)` can not call
+// into the reader from inside closures, so these stand in for the methods and
+// closures used by the synchronous implementation.
+#[cfg(feature = "async")]
+pub fn read_options_async<'r, 'o, 'a, T, R>(
+    reader: &'r mut R,
+    options: &'o ReadOptions,
+    args: T::Args<'a>,
+) -> impl Future<Output = BinResult<T>> + use<'r, 'o, 'a, T, R>
+where
+    T: BinReadAsync,
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    crate::read_async::read_options(reader, options, args)
+}
+
+#[cfg(feature = "async")]
+pub fn after_parse_async<'v, 'r, 'o, 'a, T, R>(
+    item: &'v mut T,
+    reader: &'r mut R,
+    ro: &'o ReadOptions,
+    args: T::Args<'a>,
+) -> impl Future<Output = BinResult<()>> + use<'v, 'r, 'o, 'a, T, R>
+where
+    T: BinReadAsync,
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    crate::read_async::after_parse(item, reader, ro, args)
+}
+
+#[cfg(feature = "async")]
+pub async fn try_after_parse_async<T, R>(
+    item: &mut Option<T>,
+    reader: &mut R,
+    ro: &ReadOptions,
+    args: T::Args<'_>,
+) -> BinResult<()>
+where
+    T: BinReadAsync,
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    if let Some(value) = item.as_mut() {
+        after_parse_async(value, reader, ro, args).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "async")]
+pub async fn seek_async<R: AsyncSeek + Unpin>(
+    reader: &mut R,
+    pos: io::SeekFrom,
+) -> io::Result<u64> {
+    futures_util::io::AsyncSeekExt::seek(reader, pos).await
+}
+
+#[cfg(feature = "async")]
+pub async fn stream_pos_async<R: AsyncSeek + Unpin>(reader: &mut R) -> io::Result<u64> {
+    crate::read_async::stream_pos(reader).await
+}
+
+#[cfg(feature = "async")]
+pub async fn magic_async<R, B>(reader: &mut R, expected: B, options: &ReadOptions) -> BinResult<()>
+where
+    B: for<'a> BinReadAsync<Args<'a> = ()> + PartialEq + Sync + Send + 'static,
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let pos = stream_pos_async(reader).await?;
+    let val: B = read_options_async(reader, options, ()).await?;
+    if val == expected {
+        Ok(())
+    } else {
+        Err(Error::BadMagic {
+            pos,
+            expected: Box::new(expected) as _,
+            found: Box::new(val) as _,
+        })
+    }
+}
+
+/// Adds the field being read to the error of a failed read, like
+/// [`field_context`]. The result is taken first so that the reader is no
+/// longer borrowed by the read when it is passed in.
+#[cfg(feature = "async")]
+pub async fn field_context_async<T, R: AsyncSeek + Unpin>(
+    result: BinResult<T>,
+    reader: &mut R,
+    type_name: &'static str,
+    field: &'static str,
+    line: u32,
+) -> BinResult<T> {
+    match result {
+        Ok(value) => Ok(value),
+        Err(error) => {
+            let pos = stream_pos_async(reader).await.unwrap_or_default();
+            Err(error.with_context(ContextFrame::Field {
+                type_name,
+                field,
+                pos,
+                line,
+            }))
+        }
+    }
+}
+
+pub fn try_after_parse<Reader, ValueType>(
     item: &mut Option<ValueType>,
     reader: &mut Reader,
     ro: &ReadOptions,
-    args: ArgType,
+    args: ValueType::Args<'_>,
 ) -> BinResult<()>
 where
     Reader: io::Read + io::Seek,
-    ValueType: BinRead<Args = ArgType>,
-    ArgType: Copy + 'static,
+    ValueType: BinRead,
 {
     if let Some(value) = item.as_mut() {
         value.after_parse(reader, ro, args)?;
@@ -65,3 +198,206 @@ where
 
     Ok(())
 }
+
+pub fn try_after_parse_borrowed<'input, ValueType>(
+    item: &mut Option<ValueType>,
+    reader: &mut io::SliceReader<'input>,
+    ro: &ReadOptions,
+    args: ValueType::Args<'_>,
+) -> BinResult<()>
+where
+    ValueType: BinReadBorrowed<'input>,
+{
+    if let Some(value) = item.as_mut() {
+        value.after_parse_borrowed(reader, ro, args)?;
+    }
+
+    Ok(())
+}
+
+// Fields with the `size` directive are read through a trait object, so
+// that types containing themselves inside a sized field (such as nested
+// chunks) do not instantiate their parser for an endless chain of
+// `TakeSeek<TakeSeek<...>>` reader types.
+pub trait ReadSeek: io::Read + io::Seek {}
+
+impl<T: io::Read + io::Seek> ReadSeek for T {}
+
+/// Checks the number of bytes used by a field with the `size` directive,
+/// which may never be more than its size, and must be all of it if `exact`
+pub fn check_size(pos: u64, end: u64, size: u64, exact: bool) -> BinResult<()> {
+    let used = end.saturating_sub(pos);
+    if used > size || (exact && used < size) {
+        Err(Error::AssertFail {
+            pos,
+            message: format!("field used {} bytes of its size of {} bytes", used, size),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Selects the byte order from a byte order marker read or written at `pos`
+pub fn endian_from<T: PartialEq + core::fmt::Debug>(
+    pos: u64,
+    marker: &T,
+    big: T,
+    little: T,
+) -> BinResult<Endian> {
+    if *marker == big {
+        Ok(Endian::Big)
+    } else if *marker == little {
+        Ok(Endian::Little)
+    } else {
+        Err(Error::AssertFail {
+            pos,
+            message: format!("unknown byte order marker {:?}", marker),
+        })
+    }
+}
+
+pub fn write_zeroes<W: io::Write>(writer: &mut W, count: u64) -> BinResult<()> {
+    const ZEROES: [u8; 64] = [0; 64];
+    let mut remaining = count;
+    while remaining > 0 {
+        let len = core::cmp::min(remaining, ZEROES.len() as u64);
+        writer.write_all(&ZEROES[..len as usize])?;
+        remaining -= len;
+    }
+
+    Ok(())
+}
+
+// Fields are exposed to `BinWrite` directive expressions under their own
+// names, just like they are when reading. Since the value being written is
+// only borrowed, this uses autoref specialization to hand out a copy of any
+// `Copy` field (so expressions like `if(count != 0)` work unchanged) and a
+// reference to everything else.
+pub struct FieldRef<'a, T>(pub &'a T);
+
+pub trait CopyField<T> {
+    fn field_value(&self) -> T;
+}
+
+impl<'a, T: Copy> CopyField<T> for FieldRef<'a, T> {
+    fn field_value(&self) -> T {
+        *self.0
+    }
+}
+
+pub trait BorrowField<'a, T> {
+    fn field_value(&self) -> &'a T;
+}
+
+impl<'a, T> BorrowField<'a, T> for &FieldRef<'a, T> {
+    fn field_value(&self) -> &'a T {
+        self.0
+    }
+}
+
+// Fields read with `count` are checked to still have that many items when
+// written, so that they read back the same. This uses autoref specialization
+// like `FieldRef`, so that types which do not know their length are written
+// without the check.
+pub struct CountRef<'a, T>(pub &'a T);
+
+pub trait ItemCount {
+    fn item_count(&self) -> usize;
+}
+
+impl<T> ItemCount for Vec<T> {
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> ItemCount for crate::alloc::collections::VecDeque<T> {
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> ItemCount for crate::alloc::collections::BTreeSet<T> {
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> ItemCount for crate::alloc::collections::BTreeMap<K, V> {
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> ItemCount for std::collections::HashSet<T, S> {
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> ItemCount for std::collections::HashMap<K, V, S> {
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl ItemCount for String {
+    fn item_count(&self) -> usize {
+        self.len()
+    }
+}
+
+pub trait KnownCount {
+    fn written_count(&self) -> Option<usize>;
+}
+
+impl<'a, T: ItemCount> KnownCount for CountRef<'a, T> {
+    fn written_count(&self) -> Option<usize> {
+        Some(self.0.item_count())
+    }
+}
+
+pub trait UnknownCount {
+    fn written_count(&self) -> Option<usize>;
+}
+
+impl<'a, T> UnknownCount for &CountRef<'a, T> {
+    fn written_count(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Checks that a field read with the `count` directive has `count` items when
+/// it is written at `pos`
+pub fn check_count(pos: u64, written: Option<usize>, count: usize) -> BinResult<()> {
+    match written {
+        Some(written) if written != count => Err(Error::AssertFail {
+            pos,
+            message: format!("field has {} items but its count is {}", written, count),
+        }),
+        _ => Ok(()),
+    }
+}
+
+// These infer the named arguments type of a custom parse or write function
+// from its signature, since it can not be named from inside the derive macro.
+pub fn parse_fn_builder<R, T, Args, F>(_: &F) -> Args::Builder
+where
+    R: io::Read + io::Seek,
+    Args: NamedArgs,
+    F: Fn(&mut R, &ReadOptions, Args) -> BinResult<T>,
+{
+    Args::builder()
+}
+
+pub fn write_fn_builder<W, T, Args, F>(_: &F) -> Args::Builder
+where
+    W: io::Write + io::Seek,
+    T: ?Sized,
+    Args: NamedArgs,
+    F: Fn(&T, &mut W, &WriteOptions, Args) -> BinResult<()>,
+{
+    Args::builder()
+}
@@ -33,7 +33,7 @@ pub struct Punctuated<T: BinRead, P: BinRead> {
     pub seperators: Vec<P>,
 }
 
-impl<C: Copy + 'static, T: BinRead<Args = C>, P: BinRead<Args = ()>> Punctuated<T, P> {
+impl<T: BinRead, P: for<'a> BinRead<Args<'a> = ()>> Punctuated<T, P> {
     /// A parser for values seperated by another value, with no trailing punctuation.
     ///
     /// Requires a specified count.
@@ -59,7 +59,7 @@ impl<C: Copy + 'static, T: BinRead<Args = C>, P: BinRead<Args = ()>> Punctuated<
     pub fn separated<R: Read + Seek>(
         reader: &mut R,
         options: &ReadOptions,
-        args: C,
+        args: T::Args<'_>,
     ) -> BinResult<Self> {
         let count = match options.count {
             Some(x) => x,
@@ -70,7 +70,7 @@ impl<C: Copy + 'static, T: BinRead<Args = C>, P: BinRead<Args = ()>> Punctuated<
         let mut seperators = Vec::with_capacity(count.max(1) - 1);
 
         for i in 0..count {
-            data.push(T::read_options(reader, &options, args)?);
+            data.push(T::read_options(reader, options, args.clone())?);
             if i + 1 != count {
                 seperators.push(P::read_options(reader, options, ())?);
             }
@@ -85,7 +85,7 @@ impl<C: Copy + 'static, T: BinRead<Args = C>, P: BinRead<Args = ()>> Punctuated<
     pub fn separated_trailing<R: Read + Seek>(
         reader: &mut R,
         options: &ReadOptions,
-        args: C,
+        args: T::Args<'_>,
     ) -> BinResult<Self> {
         let count = match options.count {
             Some(x) => x,
@@ -96,7 +96,7 @@ impl<C: Copy + 'static, T: BinRead<Args = C>, P: BinRead<Args = ()>> Punctuated<
         let mut seperators = Vec::with_capacity(count);
 
         for _ in 0..count {
-            data.push(T::read_options(reader, &options, args)?);
+            data.push(T::read_options(reader, options, args.clone())?);
             seperators.push(P::read_options(reader, options, ())?);
         }
 
@@ -0,0 +1,281 @@
+//! Reading from asynchronous sources, such as files or network streams driven by an async
+//! runtime.
+//!
+//! This module is only available with the `async` feature. [`BinReadAsync`] mirrors
+//! [`BinRead`], but reads from anything implementing the [`AsyncRead`](crate::io::AsyncRead)
+//! and [`AsyncSeek`](crate::io::AsyncSeek) traits of the `futures` crate. Readers from `tokio`
+//! can be used through the `compat` adapters of the `tokio-util` crate.
+//!
+//! ```rust
+//! # use binread::{prelude::*, BinReadAsync, BinReaderAsyncExt, FilePtr16};
+//! # use futures_util::io::Cursor;
+//! #[derive(BinRead, BinReadAsync)]
+//! #[br(magic = b"IDX")]
+//! struct Index {
+//!     count: u8,
+//!     #[br(count = count)]
+//!     names: Vec<FilePtr16<u8>>,
+//! }
+//!
+//! # futures_executor::block_on(async {
+//! let mut reader = Cursor::new(b"IDX\x02\x00\x08\x00\x09\x0A\x0B");
+//! let index: Index = reader.read_be_async().await.unwrap();
+//! assert_eq!(*index.names[0], 0x0A);
+//! assert_eq!(*index.names[1], 0x0B);
+//! # });
+//! ```
+//!
+//! Deriving `BinReadAsync` requires deriving [`BinRead`] as well, since both share the same
+//! arguments type. Every directive is supported, but a field using
+//! [`parse_with`](crate::attribute#custom-parsers) also needs a `parse_with_async` function for
+//! the async implementation to call, such as [`until_eof_async`](crate::helpers::until_eof_async).
+//!
+//! The futures returned by these traits are `Send`, so they can be spawned on a multithreaded
+//! runtime, as long as the reader, the type being read and its arguments are `Send` too.
+
+use crate::io::{self, AsyncRead, AsyncSeek, SeekFrom};
+use crate::options::LimitError;
+use crate::{
+    error::ContextFrame, file_ptr, BinRead, BinResult, DefaultArgs, Endian, Error, Limits,
+    ReadOptions,
+};
+use core::future::Future;
+use futures_util::io::AsyncSeekExt;
+
+mod impls;
+
+/// The asynchronous counterpart of [`BinRead`], which reads from an
+/// [`AsyncRead`](crate::io::AsyncRead) + [`AsyncSeek`](crate::io::AsyncSeek) source.
+///
+/// The arguments of a type are the same as its [`BinRead::Args`]. The returned futures are
+/// `Send` when the reader, the type and its arguments are.
+pub trait BinReadAsync: BinRead {
+    /// Read the type from the reader while assuming no arguments have been passed
+    fn read_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+    ) -> impl Future<Output = BinResult<Self>>
+    where
+        for<'a> Self::Args<'a>: DefaultArgs,
+    {
+        file_ptr::read_scope_async(async move {
+            read_options(reader, &ReadOptions::default(), DefaultArgs::default_args()).await
+        })
+    }
+
+    /// Read the type from the reader using the specified arguments
+    fn read_args_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        args: Self::Args<'_>,
+    ) -> impl Future<Output = BinResult<Self>> {
+        file_ptr::read_scope_async(async move {
+            read_options(reader, &ReadOptions::default(), args).await
+        })
+    }
+
+    /// Read the type from the reader
+    fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> impl Future<Output = BinResult<Self>>;
+
+    fn after_parse_async<R: AsyncRead + AsyncSeek + Unpin>(
+        &mut self,
+        _: &mut R,
+        _: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> impl Future<Output = BinResult<()>> {
+        async { Ok(()) }
+    }
+}
+
+/// An extension trait for [`AsyncRead`](crate::io::AsyncRead) to provide methods for reading a
+/// value directly, like [`BinReaderExt`](crate::BinReaderExt) does for synchronous readers.
+///
+/// The returned futures are `Send` when the reader, the type being read and its arguments are.
+pub trait BinReaderAsyncExt: AsyncRead + AsyncSeek + Unpin + Sized {
+    /// Read the given type from the reader using the given endianness.
+    fn read_type_async<T: BinReadAsync>(
+        &mut self,
+        endian: Endian,
+    ) -> impl Future<Output = BinResult<T>>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
+        self.read_type_args_async(endian, DefaultArgs::default_args())
+    }
+
+    /// Read the given type from the reader with big endian byteorder
+    fn read_be_async<T: BinReadAsync>(&mut self) -> impl Future<Output = BinResult<T>>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
+        self.read_type_async(Endian::Big)
+    }
+
+    /// Read the given type from the reader with little endian byteorder
+    fn read_le_async<T: BinReadAsync>(&mut self) -> impl Future<Output = BinResult<T>>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
+        self.read_type_async(Endian::Little)
+    }
+
+    /// Read the given type from the reader with the native byteorder
+    fn read_ne_async<T: BinReadAsync>(&mut self) -> impl Future<Output = BinResult<T>>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
+        self.read_type_async(Endian::Native)
+    }
+
+    /// Read `T` from the reader with the given byte order and arguments.
+    fn read_type_args_async<T: BinReadAsync>(
+        &mut self,
+        endian: Endian,
+        args: T::Args<'_>,
+    ) -> impl Future<Output = BinResult<T>> {
+        file_ptr::read_scope_async(async move {
+            let options = ReadOptions {
+                endian,
+                ..Default::default()
+            };
+
+            let mut res: T = read_options(self, &options, args.clone()).await?;
+            after_parse(&mut res, self, &options, args).await?;
+
+            Ok(res)
+        })
+    }
+
+    /// Read `T` from the reader with the given byte order and arguments,
+    /// returning a [`LimitExceeded`](Error::LimitExceeded) error if reading
+    /// goes over any of the given [`Limits`], like
+    /// [`BinReaderExt::read_with_limits`](crate::BinReaderExt::read_with_limits).
+    fn read_with_limits_async<T: BinReadAsync>(
+        &mut self,
+        endian: Endian,
+        limits: Limits,
+        args: T::Args<'_>,
+    ) -> impl Future<Output = BinResult<T>> {
+        file_ptr::read_scope_async(async move {
+            let options = ReadOptions {
+                endian,
+                limits,
+                ..Default::default()
+            };
+
+            let max_bytes_read = match limits.max_bytes_read {
+                Some(max) => max,
+                None => {
+                    let mut res: T = read_options(self, &options, args.clone()).await?;
+                    after_parse(&mut res, self, &options, args).await?;
+                    return Ok(res);
+                }
+            };
+
+            let mut reader = io::ReadLimit::new(self, max_bytes_read);
+            let result = async {
+                let mut res: T = read_options(&mut reader, &options, args.clone()).await?;
+                after_parse(&mut res, &mut reader, &options, args).await?;
+                Ok(res)
+            }
+            .await;
+
+            match (result, reader.exceeded_at()) {
+                (Err(_), Some(pos)) => Err(Error::LimitExceeded {
+                    pos,
+                    limit: "max_bytes_read",
+                    max: max_bytes_read,
+                }),
+                (result, _) => result,
+            }
+        })
+    }
+
+    /// Read `T` from the reader, assuming big-endian byte order, using the
+    /// given arguments.
+    fn read_be_args_async<T: BinReadAsync>(
+        &mut self,
+        args: T::Args<'_>,
+    ) -> impl Future<Output = BinResult<T>> {
+        self.read_type_args_async(Endian::Big, args)
+    }
+
+    /// Read `T` from the reader, assuming little-endian byte order, using the
+    /// given arguments.
+    fn read_le_args_async<T: BinReadAsync>(
+        &mut self,
+        args: T::Args<'_>,
+    ) -> impl Future<Output = BinResult<T>> {
+        self.read_type_args_async(Endian::Little, args)
+    }
+
+    /// Read `T` from the reader, assuming native-endian byte order, using the
+    /// given arguments.
+    fn read_ne_args_async<T: BinReadAsync>(
+        &mut self,
+        args: T::Args<'_>,
+    ) -> impl Future<Output = BinResult<T>> {
+        self.read_type_args_async(Endian::Native, args)
+    }
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin + Sized> BinReaderAsyncExt for R {}
+
+// Generic code awaits these instead of the trait methods. The compiler can not
+// yet prove that an async block holding the future of a trait method is `Send`
+// when the method takes `T::Args<'_>` (rust-lang/rust#100013), but it can when
+// the future is returned from a plain function like these.
+
+/// Calls [`BinReadAsync::read_options_async`]
+pub(crate) fn read_options<'r, 'o, 'a, T, R>(
+    reader: &'r mut R,
+    options: &'o ReadOptions,
+    args: T::Args<'a>,
+) -> impl Future<Output = BinResult<T>> + use<'r, 'o, 'a, T, R>
+where
+    T: BinReadAsync,
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    T::read_options_async(reader, options, args)
+}
+
+/// Calls [`BinReadAsync::after_parse_async`]
+pub(crate) fn after_parse<'v, 'r, 'o, 'a, T, R>(
+    value: &'v mut T,
+    reader: &'r mut R,
+    options: &'o ReadOptions,
+    args: T::Args<'a>,
+) -> impl Future<Output = BinResult<()>> + use<'v, 'r, 'o, 'a, T, R>
+where
+    T: BinReadAsync,
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    value.after_parse_async(reader, options, args)
+}
+
+/// Returns the current position of an async reader
+pub(crate) async fn stream_pos<R: AsyncSeek + Unpin>(reader: &mut R) -> io::Result<u64> {
+    reader.seek(SeekFrom::Current(0)).await
+}
+
+/// Adds the current position and index of an element to an error from reading
+/// a collection
+pub(crate) async fn index_context<R: AsyncSeek + Unpin>(
+    reader: &mut R,
+    error: Error,
+    index: usize,
+) -> Error {
+    let pos = stream_pos(reader).await.unwrap_or_default();
+    error.with_context(ContextFrame::Index { index, pos })
+}
+
+/// Creates an error at the current position for a value over one of the
+/// [`Limits`](crate::Limits)
+pub(crate) async fn limit_exceeded<R: AsyncSeek + Unpin>(
+    reader: &mut R,
+    limit: LimitError,
+) -> Error {
+    limit.at(stream_pos(reader).await.unwrap_or_default())
+}
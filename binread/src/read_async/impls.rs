@@ -0,0 +1,983 @@
+use super::{after_parse, index_context, limit_exceeded, read_options, stream_pos, BinReadAsync};
+use crate::alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use crate::checksum::{check_checksum, Checksum, Checksummed};
+use crate::collections::{duplicate_key_error, Collection, DuplicateKeys};
+use crate::endian::{Be, Le};
+use crate::file_ptr::{
+    self, shared, FilePtr, IntoSeekFrom, LazyFilePtr, NullableFilePtr, Relative, Sentinel,
+    SharedFilePtr,
+};
+use crate::io::{AsyncRead, AsyncSeek, ChecksumStream, SeekFrom};
+use crate::strings::{decode_error, prefix_error, Encoding, LengthPrefix, Utf8};
+#[cfg(feature = "const_generics")]
+use crate::strings::{decode_padded, FixedString};
+use crate::varint::{Leb128, Step, VarInt, Vlq, ZigZag};
+use crate::{float, int};
+use crate::{
+    BinRead, BinResult, EncodedNullString, Endian, Error, NullString, NullWideString, PosValue,
+    PrefixedString, PrefixedVec, ReadOptions,
+};
+use core::num::{NonZeroU16, NonZeroU8};
+use futures_util::io::{AsyncReadExt, AsyncSeekExt};
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+
+/// Internal macro for quickly implementing async reading for types supporting from_bytes api
+macro_rules! binread_async_impl {
+    ($($type_name:ty),*$(,)?) => {
+        $(
+            impl BinReadAsync for $type_name {
+                async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(reader: &mut R, options: &ReadOptions, _: Self::Args<'_>) -> BinResult<Self> {
+                    let mut val = [0; core::mem::size_of::<$type_name>()];
+                    let pos = stream_pos(reader).await?;
+
+                    if let Err(e) = reader.read_exact(&mut val).await {
+                        reader.seek(SeekFrom::Start(pos)).await?;
+                        return Err(e.into());
+                    }
+
+                    Ok(match options.endian {
+                        Endian::Big => {
+                            <$type_name>::from_be_bytes(val)
+                        }
+                        Endian::Little => {
+                            <$type_name>::from_le_bytes(val)
+                        }
+                        Endian::Native => {
+                            if cfg!(target_endian = "little") {
+                                <$type_name>::from_le_bytes(val)
+                            } else {
+                                <$type_name>::from_be_bytes(val)
+                            }
+                        }
+                    })
+                }
+            }
+        )*
+    }
+}
+
+binread_async_impl!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+macro_rules! binread_async_varint_impl {
+    ($($name:ident),*) => {
+        $(
+            impl<T> BinReadAsync for $name<T>
+            where
+                Self: VarInt,
+            {
+                async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+                    reader: &mut R,
+                    _: &ReadOptions,
+                    _: Self::Args<'_>,
+                ) -> BinResult<Self> {
+                    let pos = stream_pos(reader).await?;
+                    let mut state = <Self as VarInt>::State::default();
+                    let result = loop {
+                        let mut byte = [0];
+                        if let Err(err) = reader.read_exact(&mut byte).await {
+                            break Err(err.into());
+                        }
+
+                        match Self::decode(&mut state, byte[0]) {
+                            Step::More => {}
+                            Step::Done(value) => break Ok(value),
+                            Step::Overflow => {
+                                break Err(Error::Overflow {
+                                    pos,
+                                    type_name: Self::NAME,
+                                })
+                            }
+                        }
+                    };
+
+                    if result.is_err() {
+                        reader.seek(SeekFrom::Start(pos)).await?;
+                    }
+
+                    result
+                }
+            }
+        )*
+    };
+}
+
+binread_async_varint_impl!(Leb128, Vlq, ZigZag);
+
+macro_rules! binread_async_int_impl {
+    ($($type_name:ident),*) => {
+        $(
+            impl BinReadAsync for int::$type_name {
+                async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+                    reader: &mut R,
+                    options: &ReadOptions,
+                    _: Self::Args<'_>,
+                ) -> BinResult<Self> {
+                    let mut bytes = [0; (int::$type_name::BITS / 8) as usize];
+                    let pos = stream_pos(reader).await?;
+
+                    if let Err(e) = reader.read_exact(&mut bytes).await {
+                        reader.seek(SeekFrom::Start(pos)).await?;
+                        return Err(e.into());
+                    }
+
+                    Ok(int::$type_name::from_bytes(bytes, options.endian))
+                }
+            }
+        )*
+    };
+}
+
+binread_async_int_impl!(u24, i24, u40, i40, u48, i48, u56, i56);
+
+impl BinReadAsync for float::f16 {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        <u16>::read_options_async(reader, options, ())
+            .await
+            .map(Self::from_bits)
+    }
+}
+
+impl BinReadAsync for float::bf16 {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        <u16>::read_options_async(reader, options, ())
+            .await
+            .map(Self::from_bits)
+    }
+}
+
+#[cfg(feature = "const_generics")]
+impl<T, const FRAC: u32> BinReadAsync for float::Fixed<T, FRAC>
+where
+    T: for<'a> BinReadAsync<Args<'a> = ()>,
+{
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        read_options::<T, _>(reader, options, ())
+            .await
+            .map(Self::from_bits)
+    }
+}
+
+impl BinReadAsync for char {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(<u8>::read_options_async(reader, options, ()).await? as char)
+    }
+}
+
+impl<B: BinReadAsync> BinReadAsync for Vec<B> {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let mut options = *options;
+        let count = match options.count.take() {
+            Some(x) => x,
+            None => panic!("Missing count for Vec"),
+        };
+        if let Err(limit) = options.limits.check_count::<B>(count) {
+            return Err(limit_exceeded(reader, limit).await);
+        }
+
+        let mut result = Vec::with_capacity(count);
+        for i in 0..count {
+            match read_options::<B, _>(reader, &options, args.clone()).await {
+                Ok(val) => result.push(val),
+                Err(e) => return Err(index_context(reader, e, i).await),
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn after_parse_async<R: AsyncRead + AsyncSeek + Unpin>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        for (i, val) in self.iter_mut().enumerate() {
+            if let Err(e) = after_parse(val, reader, ro, args.clone()).await {
+                return Err(index_context(reader, e, i).await);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<B: BinReadAsync> BinReadAsync for VecDeque<B> {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        read_options::<Vec<B>, _>(reader, options, args)
+            .await
+            .map(VecDeque::from)
+    }
+
+    async fn after_parse_async<R: AsyncRead + AsyncSeek + Unpin>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        for (i, val) in self.iter_mut().enumerate() {
+            if let Err(e) = after_parse(val, reader, ro, args.clone()).await {
+                return Err(index_context(reader, e, i).await);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn read_collection<R, C>(
+    reader: &mut R,
+    options: &ReadOptions,
+    policy: DuplicateKeys,
+    type_name: &str,
+) -> BinResult<C>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+    C: Collection,
+    C::Item: BinReadAsync,
+{
+    let mut options = *options;
+    let count = match options.count.take() {
+        Some(x) => x,
+        None => panic!("Missing count for {}", type_name),
+    };
+    if let Err(limit) = options.limits.check_count::<C::Item>(count) {
+        return Err(limit_exceeded(reader, limit).await);
+    }
+
+    let mut collection = C::default();
+    for i in 0..count {
+        let pos = stream_pos(reader).await?;
+        let item = async {
+            let mut item = read_options::<C::Item, _>(reader, &options, ()).await?;
+            after_parse(&mut item, reader, &options, ()).await?;
+            Ok(item)
+        }
+        .await;
+
+        match item {
+            Ok(item) => {
+                if collection.insert_item(item, policy) && policy == DuplicateKeys::Error {
+                    let error = duplicate_key_error(pos, type_name);
+                    return Err(index_context(reader, error, i).await);
+                }
+            }
+            Err(e) => return Err(index_context(reader, e, i).await),
+        }
+    }
+
+    Ok(collection)
+}
+
+macro_rules! binread_async_collection_impl {
+    ($($collection:ident<$($param:ident),*>
+        where [$($bounds:tt)*]),*$(,)?) => {
+        $(
+            impl<$($param),*> BinReadAsync for $collection<$($param),*>
+            where
+                Self: Collection,
+                <Self as Collection>::Item: BinReadAsync,
+                $($bounds)*
+            {
+                async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+                    reader: &mut R,
+                    options: &ReadOptions,
+                    policy: Self::Args<'_>,
+                ) -> BinResult<Self> {
+                    read_collection(reader, options, policy, stringify!($collection)).await
+                }
+            }
+        )*
+    };
+}
+
+binread_async_collection_impl!(
+    BTreeMap<K, V> where [K: for<'a> BinReadAsync<Args<'a> = ()> + Ord, V: for<'a> BinReadAsync<Args<'a> = ()>],
+    BTreeSet<T> where [T: for<'a> BinReadAsync<Args<'a> = ()> + Ord],
+    HashMap<K, V, S> where [
+        K: for<'a> BinReadAsync<Args<'a> = ()> + Eq + Hash,
+        V: for<'a> BinReadAsync<Args<'a> = ()>,
+        S: BuildHasher + Default,
+    ],
+    HashSet<T, S> where [T: for<'a> BinReadAsync<Args<'a> = ()> + Eq + Hash, S: BuildHasher + Default],
+);
+
+impl BinReadAsync for String {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let count = match options.count {
+            Some(x) => x,
+            None => panic!("Missing count for String"),
+        };
+        if let Err(limit) = options.limits.check_count::<u8>(count) {
+            return Err(limit_exceeded(reader, limit).await);
+        }
+
+        let pos = stream_pos(reader).await?;
+        let mut bytes = vec![0; count];
+        let result = match reader.read_exact(&mut bytes).await {
+            Ok(()) => String::from_utf8(bytes).map_err(|_| decode_error::<Utf8>(pos)),
+            Err(e) => Err(e.into()),
+        };
+
+        if result.is_err() {
+            reader.seek(SeekFrom::Start(pos)).await?;
+        }
+
+        result
+    }
+}
+
+#[cfg(not(feature = "const_generics"))]
+macro_rules! binread_async_array_impl {
+    ($($size:literal),*$(,)?) => {
+        $(
+            impl<B: BinReadAsync + Default> BinReadAsync for [B; $size] {
+                async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(reader: &mut R, options: &ReadOptions, args: Self::Args<'_>) -> BinResult<Self> {
+                    let mut arr: [B; $size] = Default::default();
+                    for (i, elem) in arr.iter_mut().enumerate() {
+                        match read_options::<B, _>(reader, options, args.clone()).await {
+                            Ok(val) => *elem = val,
+                            Err(e) => return Err(index_context(reader, e, i).await),
+                        }
+                    }
+                    Ok(arr)
+                }
+
+                async fn after_parse_async<R: AsyncRead + AsyncSeek + Unpin>(&mut self, reader: &mut R, ro: &ReadOptions, args: B::Args<'_>) -> BinResult<()> {
+                    for (i, val) in self.iter_mut().enumerate() {
+                        if let Err(e) = after_parse(val, reader, ro, args.clone()).await {
+                            return Err(index_context(reader, e, i).await);
+                        }
+                    }
+
+                    Ok(())
+                }
+            }
+        )*
+    }
+}
+
+#[cfg(not(feature = "const_generics"))]
+binread_async_array_impl!(
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+    26, 27, 28, 29, 30, 31, 32
+);
+
+#[cfg(feature = "const_generics")]
+impl<B: BinReadAsync, const N: usize> BinReadAsync for [B; N] {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        // The elements can not be read from inside the closure passed to
+        // `array_init`, since it can not await
+        let mut elems = Vec::with_capacity(N);
+        for i in 0..N {
+            match read_options::<B, _>(reader, options, args.clone()).await {
+                Ok(val) => elems.push(val),
+                Err(e) => return Err(index_context(reader, e, i).await),
+            }
+        }
+
+        let mut elems = elems.into_iter();
+        Ok(array_init::array_init(|_| elems.next().unwrap()))
+    }
+
+    async fn after_parse_async<R: AsyncRead + AsyncSeek + Unpin>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: B::Args<'_>,
+    ) -> BinResult<()> {
+        for (i, val) in self.iter_mut().enumerate() {
+            if let Err(e) = after_parse(val, reader, ro, args.clone()).await {
+                return Err(index_context(reader, e, i).await);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Internal macro to recursively implement BinReadAsync for every size tuple given
+/// in the invocation
+macro_rules! binread_async_tuple_impl {
+    ($type1:ident $(, $types:ident)*) => {
+        #[allow(non_camel_case_types)]
+        impl<$type1: for<'a> BinReadAsync<Args<'a> = ()>, $($types: for<'a> BinReadAsync<Args<'a> = ()>),*> BinReadAsync for ($type1, $($types),*) {
+            async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(reader: &mut R, options: &ReadOptions, _: Self::Args<'_>) -> BinResult<Self> {
+                Ok((
+                    read_options::<$type1, _>(reader, options, ()).await?,
+                    $(
+                        read_options::<$types, _>(reader, options, ()).await?
+                    ),*
+                ))
+            }
+
+            async fn after_parse_async<R: AsyncRead + AsyncSeek + Unpin>(&mut self, reader: &mut R, options: &ReadOptions, _: Self::Args<'_>) -> BinResult<()> {
+                let ($type1, $(
+                    $types
+                ),*) = self;
+
+                after_parse($type1, reader, options, ()).await?;
+                $(
+                    after_parse($types, reader, options, ()).await?;
+                )*
+
+                Ok(())
+            }
+        }
+
+        binread_async_tuple_impl!($($types),*);
+    };
+
+    () => {};
+}
+
+binread_async_tuple_impl!(
+    b1, b2, b3, b4, b5, b6, b7, b8, b9, b10, b11, b12, b13, b14, b15, b16, b17, b18, b19, b20, b21,
+    b22, b23, b24, b25, b26, b27, b28, b29, b30, b31, b32
+);
+
+impl BinReadAsync for () {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        _: &mut R,
+        _: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(())
+    }
+}
+
+impl<T: BinReadAsync> BinReadAsync for Box<T> {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(Box::new(read_options::<T, _>(reader, options, args).await?))
+    }
+}
+
+impl<T: BinReadAsync> BinReadAsync for Option<T> {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(Some(read_options::<T, _>(reader, options, args).await?))
+    }
+
+    async fn after_parse_async<R: AsyncRead + AsyncSeek + Unpin>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        match self {
+            Some(val) => after_parse(val, reader, ro, args).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T> BinReadAsync for core::marker::PhantomData<T> {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        _: &mut R,
+        _: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(core::marker::PhantomData)
+    }
+}
+
+impl BinReadAsync for Vec<NonZeroU8> {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let mut values = vec![];
+        let mut byte = [0];
+
+        // Like the synchronous version, the end of the data ends the string
+        while reader.read(&mut byte).await? != 0 {
+            match NonZeroU8::new(byte[0]) {
+                Some(val) => values.push(val),
+                None => break,
+            }
+
+            if let Err(limit) = options.limits.check_count::<NonZeroU8>(values.len()) {
+                return Err(limit_exceeded(reader, limit).await);
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+impl BinReadAsync for Vec<NonZeroU16> {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let mut values = vec![];
+
+        loop {
+            let val = <u16>::read_options_async(reader, options, ()).await?;
+            match NonZeroU16::new(val) {
+                Some(val) => values.push(val),
+                None => return Ok(values),
+            }
+
+            if let Err(limit) = options.limits.check_count::<NonZeroU16>(values.len()) {
+                return Err(limit_exceeded(reader, limit).await);
+            }
+        }
+    }
+}
+
+impl BinReadAsync for NullString {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        <Vec<NonZeroU8>>::read_options_async(reader, options, args)
+            .await
+            .map(Into::into)
+    }
+}
+
+impl BinReadAsync for NullWideString {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        <Vec<NonZeroU16>>::read_options_async(reader, options, args)
+            .await
+            .map(Into::into)
+    }
+}
+
+/// Reads a length prefix and converts it to a `usize`
+async fn read_prefix<R, L>(reader: &mut R, options: &ReadOptions) -> BinResult<usize>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+    L: LengthPrefix + BinReadAsync,
+{
+    let pos = stream_pos(reader).await?;
+    let prefix = read_options::<L, _>(reader, options, ()).await?;
+    prefix.to_len().ok_or_else(|| prefix_error::<L>(pos))
+}
+
+impl<L, E> BinReadAsync for PrefixedString<L, E>
+where
+    L: LengthPrefix + BinReadAsync,
+    E: Encoding,
+    E::Unit: BinReadAsync,
+{
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = stream_pos(reader).await?;
+        let result = async {
+            let len = read_prefix::<R, L>(reader, options).await?;
+            options
+                .limits
+                .check_count::<E::Unit>(len)
+                .map_err(|limit| limit.at(pos))?;
+            let mut units = Vec::new();
+            for _ in 0..len {
+                units.push(read_options::<E::Unit, _>(reader, options, ()).await?);
+            }
+
+            E::decode(units).ok_or_else(|| decode_error::<E>(pos))
+        }
+        .await;
+
+        if result.is_err() {
+            reader.seek(SeekFrom::Start(pos)).await?;
+        }
+
+        result.map(Self::from)
+    }
+}
+
+impl<E> BinReadAsync for EncodedNullString<E>
+where
+    E: Encoding,
+    E::Unit: BinReadAsync,
+{
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = stream_pos(reader).await?;
+        let result = async {
+            let mut units = Vec::new();
+            loop {
+                let unit = read_options::<E::Unit, _>(reader, options, ()).await?;
+                if unit == E::Unit::default() {
+                    break;
+                }
+                units.push(unit);
+                if let Err(limit) = options.limits.check_count::<E::Unit>(units.len()) {
+                    return Err(limit_exceeded(reader, limit).await);
+                }
+            }
+
+            E::decode(units).ok_or_else(|| decode_error::<E>(pos))
+        }
+        .await;
+
+        if result.is_err() {
+            reader.seek(SeekFrom::Start(pos)).await?;
+        }
+
+        result.map(Self::from)
+    }
+}
+
+impl<L, T> BinReadAsync for PrefixedVec<L, T>
+where
+    L: LengthPrefix + BinReadAsync,
+    T: BinReadAsync,
+{
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = stream_pos(reader).await?;
+        let result = async {
+            let len = read_prefix::<R, L>(reader, options).await?;
+            let options = ReadOptions {
+                count: Some(len),
+                ..*options
+            };
+
+            read_options::<Vec<T>, _>(reader, &options, args).await
+        }
+        .await;
+
+        if result.is_err() {
+            reader.seek(SeekFrom::Start(pos)).await?;
+        }
+
+        result.map(Self::from)
+    }
+
+    async fn after_parse_async<R: AsyncRead + AsyncSeek + Unpin>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        after_parse(&mut self.0, reader, ro, args).await
+    }
+}
+
+impl<T, C> BinReadAsync for Checksummed<T, C>
+where
+    T: BinReadAsync,
+    C: Checksum + Unpin,
+    C::Output: BinReadAsync,
+{
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = stream_pos(reader).await?;
+        let result = async {
+            let mut stream = ChecksumStream::<_, C>::new(&mut *reader);
+            let value = read_options::<T, _>(&mut stream, options, args).await?;
+            let actual = stream.checksum();
+            let checksum_pos = stream_pos(reader).await?;
+            let expected = read_options::<C::Output, _>(reader, options, ()).await?;
+            check_checksum::<C>(checksum_pos, expected, actual).map(|_| value)
+        }
+        .await;
+
+        if result.is_err() {
+            reader.seek(SeekFrom::Start(pos)).await?;
+        }
+
+        result.map(Self::from)
+    }
+
+    async fn after_parse_async<R: AsyncRead + AsyncSeek + Unpin>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        after_parse(&mut self.0, reader, ro, args).await
+    }
+}
+
+#[cfg(feature = "const_generics")]
+impl<const N: usize, E> BinReadAsync for FixedString<N, E>
+where
+    E: Encoding,
+    E::Unit: BinReadAsync,
+{
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        padding: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = stream_pos(reader).await?;
+        let units = read_options::<[E::Unit; N], _>(reader, options, ()).await?;
+        match decode_padded::<E>(&units, padding) {
+            Some(string) => Ok(string.into()),
+            None => {
+                reader.seek(SeekFrom::Start(pos)).await?;
+                Err(decode_error::<E>(pos))
+            }
+        }
+    }
+}
+
+impl<T: BinReadAsync> BinReadAsync for PosValue<T> {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: T::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = stream_pos(reader).await?;
+
+        Ok(PosValue {
+            pos,
+            val: read_options::<T, _>(reader, options, args).await?,
+        })
+    }
+
+    async fn after_parse_async<R: AsyncRead + AsyncSeek + Unpin>(
+        &mut self,
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        after_parse(&mut self.val, reader, options, args).await
+    }
+}
+
+impl<Ptr, BR> BinReadAsync for FilePtr<Ptr, BR>
+where
+    Ptr: for<'a> BinReadAsync<Args<'a> = ()> + IntoSeekFrom,
+    BR: BinReadAsync,
+{
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(FilePtr {
+            ptr: read_options::<Ptr, _>(reader, options, ()).await?,
+            value: None,
+        })
+    }
+
+    async fn after_parse_async<R: AsyncRead + AsyncSeek + Unpin>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: BR::Args<'_>,
+    ) -> BinResult<()> {
+        let relative_to = ro.offset;
+        let before = stream_pos(reader).await?;
+        let ro = &ro.follow_pointer(before)?;
+        reader.seek(SeekFrom::Start(relative_to)).await?;
+        reader.seek(self.ptr.into_seek_from()).await?;
+
+        let mut inner = read_options::<BR, _>(reader, ro, args.clone()).await?;
+
+        after_parse(&mut inner, reader, ro, args).await?;
+
+        self.value = Some(inner);
+
+        reader.seek(SeekFrom::Start(before)).await?;
+        Ok(())
+    }
+}
+
+impl<Ptr, BR> BinReadAsync for SharedFilePtr<Ptr, BR>
+where
+    Ptr: for<'a> BinReadAsync<Args<'a> = ()> + IntoSeekFrom,
+    BR: BinReadAsync + Send + Sync + 'static,
+    for<'a> BR::Args<'a>: PartialEq + Send + Sync + 'static,
+{
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Ok(SharedFilePtr {
+            ptr: read_options::<Ptr, _>(reader, options, ()).await?,
+            value: None,
+        })
+    }
+
+    async fn after_parse_async<R: AsyncRead + AsyncSeek + Unpin>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: BR::Args<'_>,
+    ) -> BinResult<()> {
+        file_ptr::read_scope_async(async move {
+            let before = stream_pos(reader).await?;
+            let ro = &ro.follow_pointer(before)?;
+            reader.seek(SeekFrom::Start(ro.offset)).await?;
+            let pos = reader.seek(self.ptr.into_seek_from()).await?;
+
+            let value = match shared::lookup::<BR, _>(pos, ro, &args) {
+                Ok(Some(value)) => Ok(value),
+                Ok(None) => {
+                    let value = match read_options::<BR, _>(reader, ro, args.clone()).await {
+                        Ok(mut inner) => after_parse(&mut inner, reader, ro, args)
+                            .await
+                            .map(|_| Arc::new(inner)),
+                        Err(error) => Err(error),
+                    };
+                    shared::finish(pos, value.as_ref().ok().cloned());
+                    value
+                }
+                Err(error) => Err(error),
+            };
+
+            reader.seek(SeekFrom::Start(before)).await?;
+            self.value = Some(value?);
+            Ok(())
+        })
+        .await
+    }
+}
+
+impl<Ptr, BR> BinReadAsync for LazyFilePtr<Ptr, BR>
+where
+    Ptr: for<'a> BinReadAsync<Args<'a> = ()> + IntoSeekFrom,
+    BR: BinRead,
+{
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let ptr = read_options::<Ptr, _>(reader, options, ()).await?;
+        Ok(LazyFilePtr::new(ptr, options, args))
+    }
+}
+
+impl<Ptr: for<'a> BinReadAsync<Args<'a> = ()>> BinReadAsync for Relative<Ptr> {
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = stream_pos(reader).await?;
+        Ok(Relative {
+            ptr: read_options::<Ptr, _>(reader, options, ()).await?,
+            pos,
+        })
+    }
+}
+
+impl<Ptr, BR, N> BinReadAsync for NullableFilePtr<Ptr, BR, N>
+where
+    Ptr: for<'a> BinReadAsync<Args<'a> = ()> + IntoSeekFrom,
+    BR: BinReadAsync,
+    N: Sentinel<Ptr>,
+{
+    async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let ptr = read_options::<FilePtr<Ptr, BR>, _>(reader, options, args).await?;
+        Ok(ptr.into())
+    }
+
+    async fn after_parse_async<R: AsyncRead + AsyncSeek + Unpin>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: BR::Args<'_>,
+    ) -> BinResult<()> {
+        if !self.is_null() {
+            let mut ptr = FilePtr::<Ptr, BR> {
+                ptr: self.ptr,
+                value: None,
+            };
+            after_parse(&mut ptr, reader, ro, args).await?;
+            self.value = ptr.value;
+        }
+
+        Ok(())
+    }
+}
+
+macro_rules! binread_async_fixed_endian_impl {
+    ($($name:ident),*) => {
+        $(
+            impl<T: BinReadAsync> BinReadAsync for $name<T> {
+                async fn read_options_async<R: AsyncRead + AsyncSeek + Unpin>(
+                    reader: &mut R,
+                    options: &ReadOptions,
+                    args: Self::Args<'_>,
+                ) -> BinResult<Self> {
+                    let options = Self::fixed_options(options);
+                    read_options::<T, _>(reader, &options, args).await.map($name)
+                }
+
+                async fn after_parse_async<R: AsyncRead + AsyncSeek + Unpin>(
+                    &mut self,
+                    reader: &mut R,
+                    options: &ReadOptions,
+                    args: Self::Args<'_>,
+                ) -> BinResult<()> {
+                    let options = Self::fixed_options(options);
+                    after_parse(&mut self.0, reader, &options, args).await
+                }
+            }
+        )*
+    };
+}
+
+binread_async_fixed_endian_impl!(Be, Le);
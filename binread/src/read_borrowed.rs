@@ -0,0 +1,244 @@
+//! Zero-copy reading from byte slices.
+//!
+//! [`BinReadBorrowed`] reads from a [`SliceReader`], which allows the values read to hold
+//! `&[u8]` and `&str` slices of the input instead of copying them into a `Vec` or `String`.
+//! Every type implementing [`BinRead`] implements `BinReadBorrowed` as well, so a type
+//! deriving it can mix borrowed fields with any other field:
+//!
+//! ```rust
+//! # use binread::{BinReadBorrowed, io::SliceReader};
+//! #[derive(BinReadBorrowed)]
+//! #[br(big, magic = b"MSG")]
+//! struct Message<'a> {
+//!     len: u16,
+//!     #[br(count = len)]
+//!     body: &'a [u8],
+//!     sender: &'a str,
+//!     #[br(count = 2)]
+//!     kind: &'a str,
+//! }
+//!
+//! let data = b"MSG\x00\x03abcbob\0OK";
+//! let message: Message = SliceReader::new(data).read_be().unwrap();
+//! assert_eq!(message.body, b"abc");
+//! assert_eq!(message.sender, "bob");
+//! assert_eq!(message.kind, "OK");
+//! ```
+//!
+//! The derive uses the first lifetime parameter of the type as the lifetime of the input. Types
+//! implementing `BinRead` should not derive `BinReadBorrowed`, since they already implement it.
+
+use crate::io::{self, SliceReader};
+use crate::strings::{decode_error, Utf8};
+use crate::{BinRead, BinResult, DefaultArgs, Endian, ReadOptions};
+
+/// A trait for reading a type which can borrow from the input, such as
+/// `&[u8]`.
+///
+/// Reading `&[u8]` takes as many bytes as the `count` directive gives.
+/// Reading `&str` takes `count` bytes if it is given and otherwise reads up
+/// to a null byte, which is consumed but not included, like
+/// [`NullString`](crate::NullString). Either way, the bytes must be valid
+/// UTF-8, or a [`BadEncoding`](crate::Error::BadEncoding) error is returned.
+pub trait BinReadBorrowed<'input>: Sized {
+    /// The type of arguments needed to be supplied in order to read this
+    /// type, like [`BinRead::Args`].
+    type Args<'a>: Clone;
+
+    /// Read the type from the reader while assuming no arguments have been passed
+    fn read_borrowed(reader: &mut SliceReader<'input>) -> BinResult<Self>
+    where
+        for<'a> Self::Args<'a>: DefaultArgs,
+    {
+        Self::read_options_borrowed(reader, &ReadOptions::default(), DefaultArgs::default_args())
+    }
+
+    /// Read the type from the reader using the specified arguments
+    fn read_args_borrowed(
+        reader: &mut SliceReader<'input>,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        Self::read_options_borrowed(reader, &ReadOptions::default(), args)
+    }
+
+    /// Read the type from the reader
+    fn read_options_borrowed(
+        reader: &mut SliceReader<'input>,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self>;
+
+    fn after_parse_borrowed(
+        &mut self,
+        _: &mut SliceReader<'input>,
+        _: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<()> {
+        Ok(())
+    }
+}
+
+impl<'input, T: BinRead> BinReadBorrowed<'input> for T {
+    type Args<'a> = T::Args<'a>;
+
+    fn read_options_borrowed(
+        reader: &mut SliceReader<'input>,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        T::read_options(reader, options, args)
+    }
+
+    fn after_parse_borrowed(
+        &mut self,
+        reader: &mut SliceReader<'input>,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.after_parse(reader, options, args)
+    }
+}
+
+impl<'input> BinReadBorrowed<'input> for &'input [u8] {
+    type Args<'a> = ();
+
+    fn read_options_borrowed(
+        reader: &mut SliceReader<'input>,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let count = match options.count {
+            Some(x) => x,
+            None => panic!("Missing count for &[u8]"),
+        };
+
+        Ok(reader.read_slice(count)?)
+    }
+}
+
+impl<'input> BinReadBorrowed<'input> for &'input str {
+    type Args<'a> = ();
+
+    fn read_options_borrowed(
+        reader: &mut SliceReader<'input>,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = reader.position();
+        let bytes = match options.count {
+            Some(count) => reader.read_slice(count)?,
+            None => {
+                let len = reader
+                    .remaining()
+                    .iter()
+                    .position(|&byte| byte == 0)
+                    .ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::UnexpectedEof, "missing null terminator")
+                    })?;
+                let bytes = reader.read_slice(len)?;
+                reader.read_slice(1)?;
+                bytes
+            }
+        };
+
+        core::str::from_utf8(bytes).or_else(|_| {
+            io::Seek::seek(reader, io::SeekFrom::Start(pos))?;
+            Err(decode_error::<Utf8>(pos))
+        })
+    }
+}
+
+// `Option<T>` can not be implemented generically without overlapping the
+// impl for `BinRead` types, so the borrowed types get their own impls for use
+// with the `if` directive
+macro_rules! binread_borrowed_option_impl {
+    ($($type_name:ty),*$(,)?) => {
+        $(
+            impl<'input> BinReadBorrowed<'input> for Option<$type_name> {
+                type Args<'a> = ();
+
+                fn read_options_borrowed(
+                    reader: &mut SliceReader<'input>,
+                    options: &ReadOptions,
+                    args: Self::Args<'_>,
+                ) -> BinResult<Self> {
+                    Ok(Some(<$type_name>::read_options_borrowed(reader, options, args)?))
+                }
+            }
+        )*
+    }
+}
+
+binread_borrowed_option_impl!(&'input [u8], &'input str);
+
+/// Methods for reading a value directly, like
+/// [`BinReaderExt`](crate::BinReaderExt) provides for other readers. Any type
+/// implementing [`BinRead`] can be read with these as well.
+impl<'input> SliceReader<'input> {
+    /// Read the given type from the reader using the given endianness.
+    pub fn read_type<T: BinReadBorrowed<'input>>(&mut self, endian: Endian) -> BinResult<T>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
+        self.read_type_args(endian, DefaultArgs::default_args())
+    }
+
+    /// Read the given type from the reader with big endian byteorder
+    pub fn read_be<T: BinReadBorrowed<'input>>(&mut self) -> BinResult<T>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
+        self.read_type(Endian::Big)
+    }
+
+    /// Read the given type from the reader with little endian byteorder
+    pub fn read_le<T: BinReadBorrowed<'input>>(&mut self) -> BinResult<T>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
+        self.read_type(Endian::Little)
+    }
+
+    /// Read the given type from the reader with the native byteorder
+    pub fn read_ne<T: BinReadBorrowed<'input>>(&mut self) -> BinResult<T>
+    where
+        for<'a> T::Args<'a>: DefaultArgs,
+    {
+        self.read_type(Endian::Native)
+    }
+
+    /// Read `T` from the reader with the given byte order and arguments.
+    pub fn read_type_args<T: BinReadBorrowed<'input>>(
+        &mut self,
+        endian: Endian,
+        args: T::Args<'_>,
+    ) -> BinResult<T> {
+        let options = ReadOptions {
+            endian,
+            ..Default::default()
+        };
+
+        let mut res = T::read_options_borrowed(self, &options, args.clone())?;
+        res.after_parse_borrowed(self, &options, args)?;
+
+        Ok(res)
+    }
+
+    /// Read `T` from the reader, assuming big-endian byte order, using the
+    /// given arguments.
+    pub fn read_be_args<T: BinReadBorrowed<'input>>(&mut self, args: T::Args<'_>) -> BinResult<T> {
+        self.read_type_args(Endian::Big, args)
+    }
+
+    /// Read `T` from the reader, assuming little-endian byte order, using the
+    /// given arguments.
+    pub fn read_le_args<T: BinReadBorrowed<'input>>(&mut self, args: T::Args<'_>) -> BinResult<T> {
+        self.read_type_args(Endian::Little, args)
+    }
+
+    /// Read `T` from the reader, assuming native-endian byte order, using the
+    /// given arguments.
+    pub fn read_ne_args<T: BinReadBorrowed<'input>>(&mut self, args: T::Args<'_>) -> BinResult<T> {
+        self.read_type_args(Endian::Native, args)
+    }
+}
@@ -7,24 +7,41 @@ use super::*;
 
 #[cfg(not(feature = "std"))]
 use alloc::{
+    format,
     string::{String, ToString},
     vec,
 };
+use core::convert::TryFrom;
+use core::marker::PhantomData;
 use core::num::{NonZeroU16, NonZeroU8};
 
 impl BinRead for Vec<NonZeroU8> {
-    type Args = ();
+    type Args<'a> = ();
 
     fn read_options<R: Read + Seek>(
         reader: &mut R,
-        _: &ReadOptions,
-        _: Self::Args,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
     ) -> BinResult<Self> {
-        reader
-            .bytes()
-            .take_while(|x| !matches!(x, Ok(0)))
-            .map(|x| Ok(x.map(|byte| unsafe { NonZeroU8::new_unchecked(byte) })?))
-            .collect()
+        let mut values = vec![];
+
+        #[allow(clippy::unbuffered_bytes)]
+        let exceeded = loop {
+            match reader.by_ref().bytes().next() {
+                None | Some(Ok(0)) => break None,
+                Some(Ok(byte)) => values.push(unsafe { NonZeroU8::new_unchecked(byte) }),
+                Some(Err(err)) => return Err(err.into()),
+            }
+
+            if let Err(limit) = options.limits.check_count::<NonZeroU8>(values.len()) {
+                break Some(limit);
+            }
+        };
+
+        match exceeded {
+            Some(limit) => Err(error::limit_exceeded(reader, limit)),
+            None => Ok(values),
+        }
     }
 }
 
@@ -125,12 +142,12 @@ impl From<NullString> for Vec<u8> {
 }
 
 impl BinRead for Vec<NonZeroU16> {
-    type Args = ();
+    type Args<'a> = ();
 
     fn read_options<R: Read + Seek>(
         reader: &mut R,
         options: &ReadOptions,
-        _: Self::Args,
+        _: Self::Args<'_>,
     ) -> BinResult<Self> {
         let mut values = vec![];
 
@@ -140,20 +157,24 @@ impl BinRead for Vec<NonZeroU16> {
                 return Ok(values);
             }
             values.push(unsafe { NonZeroU16::new_unchecked(val) });
+            options
+                .limits
+                .check_count::<NonZeroU16>(values.len())
+                .map_err(|limit| error::limit_exceeded(reader, limit))?;
         }
     }
 }
 
 impl BinRead for NullWideString {
-    type Args = ();
+    type Args<'a> = ();
 
     fn read_options<R: Read + Seek>(
         reader: &mut R,
         options: &ReadOptions,
-        args: Self::Args,
+        args: Self::Args<'_>,
     ) -> BinResult<Self> {
         #[cfg(feature = "debug_template")]
-        let options = {
+        let options = &{
             let mut options = *options;
             let pos = reader.stream_pos().unwrap();
 
@@ -174,17 +195,17 @@ impl BinRead for NullWideString {
 
         // https://github.com/rust-lang/rust-clippy/issues/6447
         #[allow(clippy::unit_arg)]
-        <Vec<NonZeroU16>>::read_options(reader, &options, args).map(|chars| chars.into())
+        <Vec<NonZeroU16>>::read_options(reader, options, args).map(|chars| chars.into())
     }
 }
 
 impl BinRead for NullString {
-    type Args = ();
+    type Args<'a> = ();
 
     fn read_options<R: Read + Seek>(
         reader: &mut R,
         options: &ReadOptions,
-        args: Self::Args,
+        args: Self::Args<'_>,
     ) -> BinResult<Self> {
         #[cfg(feature = "debug_template")]
         {
@@ -209,6 +230,825 @@ impl BinRead for NullString {
     }
 }
 
+impl BinWrite for NullString {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.0.write_options(writer, options, args)?;
+        0u8.write_options(writer, options, args)
+    }
+}
+
+impl BinWrite for NullWideString {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.0.write_options(writer, options, args)?;
+        0u16.write_options(writer, options, args)
+    }
+}
+
+/// A null terminated string in the [`Encoding`] `E`, for null-terminated data
+/// which is not UTF-8 or which should be checked while reading.
+///
+/// **Note:** Does not include the null, but the null is consumed from the Reader.
+///
+/// Unlike [`NullString`] and [`NullWideString`], the string is decoded while
+/// reading, and invalid data is returned as a
+/// [`BadEncoding`](crate::Error::BadEncoding) error. Strings which the
+/// encoding can not represent return the same error when written.
+///
+/// ```rust
+/// use binread::{io::Cursor, BinReaderExt, EncodedNullString, strings::{Latin1, Utf16Be}};
+///
+/// // UTF-16BE data is read as big endian even from a little endian reader
+/// let mut reader = Cursor::new(b"\0h\0i\0\0caf\xE9\0");
+/// let hi: EncodedNullString<Utf16Be> = reader.read_le().unwrap();
+/// assert_eq!(*hi, "hi");
+/// let cafe: EncodedNullString<Latin1> = reader.read_le().unwrap();
+/// assert_eq!(*cafe, "caf\u{E9}");
+/// ```
+pub struct EncodedNullString<E = Utf8>(pub String, PhantomData<E>);
+
+impl<E> EncodedNullString<E> {
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl<E> From<String> for EncodedNullString<E> {
+    fn from(s: String) -> Self {
+        EncodedNullString(s, PhantomData)
+    }
+}
+
+impl<E> From<&str> for EncodedNullString<E> {
+    fn from(s: &str) -> Self {
+        s.to_string().into()
+    }
+}
+
+impl<E> From<EncodedNullString<E>> for String {
+    fn from(s: EncodedNullString<E>) -> Self {
+        s.0
+    }
+}
+
+impl<E: Encoding> BinRead for EncodedNullString<E> {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = reader.stream_pos()?;
+
+        #[cfg(feature = "debug_template")]
+        let options = &{
+            let mut options = *options;
+            if !options.dont_output_to_template {
+                binary_template::write_named(
+                    options.endian,
+                    pos,
+                    if core::mem::size_of::<E::Unit>() == 1 {
+                        "string"
+                    } else {
+                        "wstring"
+                    },
+                    &options
+                        .variable_name
+                        .map(ToString::to_string)
+                        .unwrap_or_else(binary_template::get_next_var_name),
+                );
+            }
+            options.dont_output_to_template = true;
+            options
+        };
+
+        let mut units = Vec::new();
+        loop {
+            let result = match E::Unit::read_options(reader, options, ()) {
+                Ok(unit) if unit == E::Unit::default() => break,
+                Ok(unit) => {
+                    units.push(unit);
+                    options
+                        .limits
+                        .check_count::<E::Unit>(units.len())
+                        .map_err(|limit| error::limit_exceeded(reader, limit))
+                }
+                Err(err) => Err(err),
+            };
+
+            if let Err(err) = result {
+                reader.seek(SeekFrom::Start(pos))?;
+                return Err(err);
+            }
+        }
+
+        check_decoded::<R, E>(reader, pos, E::decode(units)).map(Self::from)
+    }
+}
+
+impl<E: Encoding> BinWrite for EncodedNullString<E> {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        encode::<W, E>(writer, &self.0)?.write_options(writer, options, args)?;
+        E::Unit::default().write_options(writer, options, args)
+    }
+}
+
+/// A text encoding used by string types such as [`PrefixedString`] and
+/// [`EncodedNullString`].
+///
+/// Invalid data is returned as a [`BadEncoding`](crate::Error::BadEncoding)
+/// error. Encodings can be wrapped in [`Lossy`] to replace invalid data with
+/// [`U+FFFD REPLACEMENT CHARACTER`](core::char::REPLACEMENT_CHARACTER)
+/// instead.
+///
+/// Besides the Unicode encodings, [`Latin1`] is always available, and the
+/// `legacy_encodings` feature adds `ShiftJis` and `Windows1252`.
+pub trait Encoding {
+    /// The code unit of the encoding. Units are read and written using the
+    /// endianness of the reader or writer, unless the encoding fixes its own
+    /// byte order. The default value of the unit is the null terminator.
+    type Unit: for<'a> BinRead<Args<'a> = ()>
+        + for<'a> BinWrite<Args<'a> = ()>
+        + Copy
+        + Default
+        + PartialEq
+        + 'static;
+
+    /// The name of the encoding, which is given in errors.
+    const NAME: &'static str;
+
+    /// The name of a code unit in binary templates.
+    const TEMPLATE_TYPE: &'static str;
+
+    /// Decodes a string, returning `None` for invalid data.
+    fn decode(units: Vec<Self::Unit>) -> Option<String>;
+
+    /// Decodes a string, replacing invalid data.
+    fn decode_lossy(units: Vec<Self::Unit>) -> String;
+
+    /// Encodes a string, returning `None` if it contains characters which
+    /// the encoding can not represent.
+    fn encode(string: &str) -> Option<Vec<Self::Unit>>;
+}
+
+/// The UTF-8 [`Encoding`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Utf8;
+
+/// The UTF-16 [`Encoding`], in the endianness of the reader or writer
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Utf16;
+
+/// The UTF-16 [`Encoding`] in big endian, regardless of the endianness of
+/// the reader or writer
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Utf16Be;
+
+/// The UTF-16 [`Encoding`] in little endian, regardless of the endianness of
+/// the reader or writer
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Utf16Le;
+
+/// The ISO-8859-1 (Latin-1) [`Encoding`], which maps each byte to the
+/// character with the same code point. Decoding can not fail, but encoding
+/// fails for characters above `U+00FF`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Latin1;
+
+/// An [`Encoding`] which replaces invalid data when decoding instead of
+/// returning an error.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Lossy<E>(PhantomData<E>);
+
+impl Encoding for Utf8 {
+    type Unit = u8;
+
+    const NAME: &'static str = "UTF-8";
+    const TEMPLATE_TYPE: &'static str = "char";
+
+    fn decode(units: Vec<u8>) -> Option<String> {
+        String::from_utf8(units).ok()
+    }
+
+    fn decode_lossy(units: Vec<u8>) -> String {
+        String::from_utf8_lossy(&units).into()
+    }
+
+    fn encode(string: &str) -> Option<Vec<u8>> {
+        Some(string.as_bytes().to_vec())
+    }
+}
+
+impl Encoding for Utf16 {
+    type Unit = u16;
+
+    const NAME: &'static str = "UTF-16";
+    const TEMPLATE_TYPE: &'static str = "wchar_t";
+
+    fn decode(units: Vec<u16>) -> Option<String> {
+        String::from_utf16(&units).ok()
+    }
+
+    fn decode_lossy(units: Vec<u16>) -> String {
+        String::from_utf16_lossy(&units)
+    }
+
+    fn encode(string: &str) -> Option<Vec<u16>> {
+        Some(string.encode_utf16().collect())
+    }
+}
+
+macro_rules! fixed_utf16_impl {
+    ($($type_name:ty => $name:literal, $from_bytes:ident, $to_bytes:ident);*$(;)?) => {
+        $(
+            impl Encoding for $type_name {
+                type Unit = [u8; 2];
+
+                const NAME: &'static str = $name;
+                const TEMPLATE_TYPE: &'static str = "wchar_t";
+
+                fn decode(units: Vec<[u8; 2]>) -> Option<String> {
+                    Utf16::decode(units.into_iter().map(u16::$from_bytes).collect())
+                }
+
+                fn decode_lossy(units: Vec<[u8; 2]>) -> String {
+                    Utf16::decode_lossy(units.into_iter().map(u16::$from_bytes).collect())
+                }
+
+                fn encode(string: &str) -> Option<Vec<[u8; 2]>> {
+                    Some(string.encode_utf16().map(u16::$to_bytes).collect())
+                }
+            }
+        )*
+    }
+}
+
+fixed_utf16_impl!(
+    Utf16Be => "UTF-16BE", from_be_bytes, to_be_bytes;
+    Utf16Le => "UTF-16LE", from_le_bytes, to_le_bytes;
+);
+
+impl Encoding for Latin1 {
+    type Unit = u8;
+
+    const NAME: &'static str = "ISO-8859-1";
+    const TEMPLATE_TYPE: &'static str = "char";
+
+    fn decode(units: Vec<u8>) -> Option<String> {
+        Some(Self::decode_lossy(units))
+    }
+
+    fn decode_lossy(units: Vec<u8>) -> String {
+        units.into_iter().map(char::from).collect()
+    }
+
+    fn encode(string: &str) -> Option<Vec<u8>> {
+        string.chars().map(|c| u8::try_from(c).ok()).collect()
+    }
+}
+
+impl<E: Encoding> Encoding for Lossy<E> {
+    type Unit = E::Unit;
+
+    const NAME: &'static str = E::NAME;
+    const TEMPLATE_TYPE: &'static str = E::TEMPLATE_TYPE;
+
+    fn decode(units: Vec<Self::Unit>) -> Option<String> {
+        Some(E::decode_lossy(units))
+    }
+
+    fn decode_lossy(units: Vec<Self::Unit>) -> String {
+        E::decode_lossy(units)
+    }
+
+    fn encode(string: &str) -> Option<Vec<Self::Unit>> {
+        E::encode(string)
+    }
+}
+
+/// The Shift-JIS [`Encoding`], as used by many Japanese games and legacy
+/// formats.
+///
+/// This type is only available with the `legacy_encodings` feature.
+#[cfg(feature = "legacy_encodings")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShiftJis;
+
+/// The Windows-1252 [`Encoding`], the Western European code page of Windows.
+///
+/// This type is only available with the `legacy_encodings` feature.
+#[cfg(feature = "legacy_encodings")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Windows1252;
+
+#[cfg(feature = "legacy_encodings")]
+macro_rules! legacy_encoding_impl {
+    ($($type_name:ty => $name:literal, $encoding:ident);*$(;)?) => {
+        $(
+            impl Encoding for $type_name {
+                type Unit = u8;
+
+                const NAME: &'static str = $name;
+                const TEMPLATE_TYPE: &'static str = "char";
+
+                fn decode(units: Vec<u8>) -> Option<String> {
+                    encoding_rs::$encoding
+                        .decode_without_bom_handling_and_without_replacement(&units)
+                        .map(Into::into)
+                }
+
+                fn decode_lossy(units: Vec<u8>) -> String {
+                    encoding_rs::$encoding.decode_without_bom_handling(&units).0.into()
+                }
+
+                fn encode(string: &str) -> Option<Vec<u8>> {
+                    match encoding_rs::$encoding.encode(string) {
+                        (_, _, true) => None,
+                        (bytes, _, false) => Some(bytes.into()),
+                    }
+                }
+            }
+        )*
+    }
+}
+
+#[cfg(feature = "legacy_encodings")]
+legacy_encoding_impl!(
+    ShiftJis => "Shift_JIS", SHIFT_JIS;
+    Windows1252 => "windows-1252", WINDOWS_1252;
+);
+
+/// An integer type which can be used as the length prefix of a
+/// [`PrefixedString`] or [`PrefixedVec`].
+pub trait LengthPrefix:
+    for<'a> BinRead<Args<'a> = ()> + for<'a> BinWrite<Args<'a> = ()> + Copy + 'static
+{
+    /// Converts the prefix to a length, or returns `None` if it does not fit
+    /// in a `usize`.
+    fn to_len(self) -> Option<usize>;
+
+    /// Converts a length to a prefix, or returns `None` if it does not fit.
+    fn from_len(len: usize) -> Option<Self>;
+}
+
+macro_rules! length_prefix_impl {
+    ($($type_name:ty),*$(,)?) => {
+        $(
+            impl LengthPrefix for $type_name {
+                fn to_len(self) -> Option<usize> {
+                    usize::try_from(self).ok()
+                }
+
+                fn from_len(len: usize) -> Option<Self> {
+                    <$type_name>::try_from(len).ok()
+                }
+            }
+        )*
+    }
+}
+
+length_prefix_impl!(u8, u16, u32, u64);
+
+/// A string stored as its length, of type `L`, followed by its data in the
+/// [`Encoding`] `E`. The length counts code units, so it is the number of
+/// bytes for [`Utf8`] and the number of `u16`s for [`Utf16`].
+///
+/// Both the length and the data use the endianness of the reader. Invalid
+/// data is returned as a [`BadEncoding`](crate::Error::BadEncoding) error,
+/// unless the encoding is [`Lossy`].
+///
+/// ```rust
+/// use binread::{io::Cursor, BinReaderExt, PrefixedString, strings::{Lossy, Utf16}};
+///
+/// let mut reader = Cursor::new(b"\x00\x05hello\x02h\0i\0");
+/// let hello: PrefixedString<u16> = reader.read_be().unwrap();
+/// assert_eq!(hello.into_string(), "hello");
+/// let hi: PrefixedString<u8, Lossy<Utf16>> = reader.read_le().unwrap();
+/// assert_eq!(hi.into_string(), "hi");
+/// ```
+pub struct PrefixedString<L, E = Utf8>(pub String, PhantomData<(L, E)>);
+
+/// A list of values stored as its length, of type `L`, followed by the
+/// values. The length uses the endianness of the reader, and arguments are
+/// passed to every value.
+///
+/// ```rust
+/// use binread::{io::Cursor, BinReaderExt, PrefixedVec};
+///
+/// let mut reader = Cursor::new(b"\x02\x00\x00\x00\x01\x00\x02\x00");
+/// let values: PrefixedVec<u32, u16> = reader.read_le().unwrap();
+/// assert_eq!(*values, [1, 2]);
+/// ```
+pub struct PrefixedVec<L, T>(pub Vec<T>, PhantomData<L>);
+
+impl<L, E> PrefixedString<L, E> {
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl<L, T> PrefixedVec<L, T> {
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<L, E> From<String> for PrefixedString<L, E> {
+    fn from(s: String) -> Self {
+        PrefixedString(s, PhantomData)
+    }
+}
+
+impl<L, E> From<&str> for PrefixedString<L, E> {
+    fn from(s: &str) -> Self {
+        s.to_string().into()
+    }
+}
+
+impl<L, E> From<PrefixedString<L, E>> for String {
+    fn from(s: PrefixedString<L, E>) -> Self {
+        s.0
+    }
+}
+
+impl<L, T> From<Vec<T>> for PrefixedVec<L, T> {
+    fn from(v: Vec<T>) -> Self {
+        PrefixedVec(v, PhantomData)
+    }
+}
+
+impl<L, T> From<PrefixedVec<L, T>> for Vec<T> {
+    fn from(v: PrefixedVec<L, T>) -> Self {
+        v.0
+    }
+}
+
+/// Reads a length prefix and converts it to a `usize`
+pub(crate) fn read_prefix<R, L>(reader: &mut R, options: &ReadOptions) -> BinResult<usize>
+where
+    R: Read + Seek,
+    L: LengthPrefix,
+{
+    let pos = reader.stream_pos()?;
+
+    #[cfg(feature = "debug_template")]
+    let options = &{
+        let mut options = *options;
+        if !options.dont_output_to_template {
+            binary_template::write_named(
+                options.endian,
+                pos,
+                core::any::type_name::<L>(),
+                &format!(
+                    "{}_len",
+                    options
+                        .variable_name
+                        .map(ToString::to_string)
+                        .unwrap_or_else(binary_template::get_next_var_name)
+                ),
+            );
+        }
+        options.dont_output_to_template = true;
+        options
+    };
+
+    let prefix = L::read_options(reader, options, ())?;
+    prefix.to_len().ok_or_else(|| prefix_error::<L>(pos))
+}
+
+pub(crate) fn prefix_error<L>(pos: u64) -> Error {
+    Error::AssertFail {
+        pos,
+        message: format!(
+            "length prefix of type {} does not fit in a usize",
+            core::any::type_name::<L>()
+        ),
+    }
+}
+
+/// Writes the length prefix of `len` values
+fn write_prefix<W, L>(writer: &mut W, options: &WriteOptions, len: usize) -> BinResult<()>
+where
+    W: Write + Seek,
+    L: LengthPrefix,
+{
+    match L::from_len(len) {
+        Some(prefix) => prefix.write_options(writer, options, ()),
+        None => Err(Error::AssertFail {
+            pos: writer.stream_pos()?,
+            message: format!(
+                "length {} does not fit in a length prefix of type {}",
+                len,
+                core::any::type_name::<L>()
+            ),
+        }),
+    }
+}
+
+/// The error for a string at `pos` which is not valid in the encoding `E`
+pub(crate) fn decode_error<E: Encoding>(pos: u64) -> Error {
+    Error::BadEncoding {
+        pos,
+        encoding: E::NAME,
+    }
+}
+
+/// Converts the result of decoding a string, seeking back to `pos` if the
+/// data is invalid
+fn check_decoded<R: Seek, E: Encoding>(
+    reader: &mut R,
+    pos: u64,
+    result: Option<String>,
+) -> BinResult<String> {
+    match result {
+        Some(string) => Ok(string),
+        None => {
+            reader.seek(SeekFrom::Start(pos))?;
+            Err(decode_error::<E>(pos))
+        }
+    }
+}
+
+/// Encodes a string to be written, returning an error if the encoding can
+/// not represent it
+fn encode<W: Seek, E: Encoding>(writer: &mut W, string: &str) -> BinResult<Vec<E::Unit>> {
+    match E::encode(string) {
+        Some(units) => Ok(units),
+        None => Err(decode_error::<E>(writer.stream_pos()?)),
+    }
+}
+
+impl<L: LengthPrefix, E: Encoding> BinRead for PrefixedString<L, E> {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = reader.stream_pos()?;
+        let len = read_prefix::<R, L>(reader, options)?;
+
+        #[cfg(feature = "debug_template")]
+        let options = &{
+            let mut options = *options;
+            if !options.dont_output_to_template {
+                binary_template::write_vec_named(
+                    options.endian,
+                    pos,
+                    E::TEMPLATE_TYPE,
+                    len,
+                    &options
+                        .variable_name
+                        .map(ToString::to_string)
+                        .unwrap_or_else(binary_template::get_next_var_name),
+                );
+            }
+            options.dont_output_to_template = true;
+            options
+        };
+
+        let units = options
+            .limits
+            .check_count::<E::Unit>(len)
+            .map_err(|limit| limit.at(pos))
+            .and_then(|_| {
+                (0..len)
+                    .map(|_| E::Unit::read_options(reader, options, ()))
+                    .collect::<BinResult<Vec<_>>>()
+            })
+            .or_else(|err| {
+                reader.seek(SeekFrom::Start(pos))?;
+                Err(err)
+            })?;
+
+        check_decoded::<R, E>(reader, pos, E::decode(units)).map(Self::from)
+    }
+}
+
+impl<L: LengthPrefix, T: BinRead> BinRead for PrefixedVec<L, T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = reader.stream_pos()?;
+        let len = read_prefix::<R, L>(reader, options)?;
+        let options = ReadOptions {
+            count: Some(len),
+            ..*options
+        };
+
+        <Vec<T>>::read_options(reader, &options, args)
+            .map(Self::from)
+            .or_else(|err| {
+                reader.seek(SeekFrom::Start(pos))?;
+                Err(err)
+            })
+    }
+
+    fn after_parse<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+        ro: &ReadOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.0.after_parse(reader, ro, args)
+    }
+}
+
+impl<L: LengthPrefix, E: Encoding> BinWrite for PrefixedString<L, E> {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let units = encode::<W, E>(writer, &self.0)?;
+        write_prefix::<W, L>(writer, options, units.len())?;
+        units.write_options(writer, options, args)
+    }
+}
+
+impl<L: LengthPrefix, T: BinWrite> BinWrite for PrefixedVec<L, T> {
+    type Args<'a> = T::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        write_prefix::<W, L>(writer, options, self.0.len())?;
+        self.0.write_options(writer, options, args)
+    }
+}
+
+/// A string stored in a field of exactly `N` code units of the [`Encoding`]
+/// `E`, with any unused space at the end filled with padding. The
+/// [`Padding`] is given as the arguments, and defaults to
+/// [`Padding::Null`].
+///
+/// This type is only available with the `const_generics` feature.
+///
+/// ```rust
+/// use binread::{io::Cursor, BinReaderExt, FixedString, strings::Padding};
+///
+/// let mut reader = Cursor::new(b"abc\0garbagename    ");
+/// let abc: FixedString<11> = reader.read_le().unwrap();
+/// assert_eq!(*abc, "abc");
+/// let name: FixedString<8> = reader.read_le_args(Padding::Trailing(' ')).unwrap();
+/// assert_eq!(*name, "name");
+/// ```
+#[cfg(feature = "const_generics")]
+pub struct FixedString<const N: usize, E = Utf8>(pub String, PhantomData<E>);
+
+/// A [`FixedString`] of `N` UTF-16 code units.
+#[cfg(feature = "const_generics")]
+pub type FixedWideString<const N: usize, E = Utf16> = FixedString<N, E>;
+
+/// The padding filling the unused end of a [`FixedString`].
+#[cfg(feature = "const_generics")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Padding {
+    /// The string ends at the first null, and anything after it is ignored.
+    /// Strings are padded with nulls when written.
+    Null,
+    /// Any copies of the character at the end of the data are removed, such
+    /// as trailing spaces.
+    Trailing(char),
+    /// The string fills the whole field.
+    Unpadded,
+}
+
+#[cfg(feature = "const_generics")]
+impl DefaultArgs for Padding {
+    fn default_args() -> Self {
+        Padding::Null
+    }
+}
+
+#[cfg(feature = "const_generics")]
+impl<const N: usize, E> FixedString<N, E> {
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+#[cfg(feature = "const_generics")]
+impl<const N: usize, E> From<String> for FixedString<N, E> {
+    fn from(s: String) -> Self {
+        FixedString(s, PhantomData)
+    }
+}
+
+#[cfg(feature = "const_generics")]
+impl<const N: usize, E> From<&str> for FixedString<N, E> {
+    fn from(s: &str) -> Self {
+        s.to_string().into()
+    }
+}
+
+#[cfg(feature = "const_generics")]
+impl<const N: usize, E> From<FixedString<N, E>> for String {
+    fn from(s: FixedString<N, E>) -> Self {
+        s.0
+    }
+}
+
+/// Decodes the code units of a fixed size string, removing the padding
+#[cfg(feature = "const_generics")]
+pub(crate) fn decode_padded<E: Encoding>(units: &[E::Unit], padding: Padding) -> Option<String> {
+    let units = match padding {
+        Padding::Null => units.split(|unit| *unit == E::Unit::default()).next(),
+        Padding::Trailing(_) | Padding::Unpadded => None,
+    }
+    .unwrap_or(units);
+
+    let mut string = E::decode(units.to_vec())?;
+    if let Padding::Trailing(c) = padding {
+        string.truncate(string.trim_end_matches(c).len());
+    }
+
+    Some(string)
+}
+
+#[cfg(feature = "const_generics")]
+impl<const N: usize, E: Encoding> BinRead for FixedString<N, E> {
+    type Args<'a> = Padding;
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        padding: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = reader.stream_pos()?;
+        let units = <[E::Unit; N]>::read_options(reader, options, ())?;
+        check_decoded::<R, E>(reader, pos, decode_padded::<E>(&units, padding)).map(Self::from)
+    }
+}
+
+#[cfg(feature = "const_generics")]
+impl<const N: usize, E: Encoding> BinWrite for FixedString<N, E> {
+    type Args<'a> = Padding;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        options: &WriteOptions,
+        padding: Self::Args<'_>,
+    ) -> BinResult<()> {
+        let mut units = encode::<W, E>(writer, &self.0)?;
+        let len = units.len();
+        let fill = match padding {
+            Padding::Null => vec![E::Unit::default()],
+            Padding::Trailing(c) => encode::<W, E>(writer, c.encode_utf8(&mut [0; 4]))?,
+            Padding::Unpadded => Vec::new(),
+        };
+
+        while !fill.is_empty() && units.len() + fill.len() <= N {
+            units.extend_from_slice(&fill);
+        }
+
+        if units.len() != N {
+            return Err(Error::AssertFail {
+                pos: writer.stream_pos()?,
+                message: format!(
+                    "string of {} code units with {:?} does not fill a fixed string of {}",
+                    len, padding, N
+                ),
+            });
+        }
+
+        units.write_options(writer, options, ())
+    }
+}
+
 use core::fmt;
 
 impl fmt::Debug for NullString {
@@ -223,6 +1063,108 @@ impl fmt::Debug for NullWideString {
     }
 }
 
+impl<E> fmt::Debug for EncodedNullString<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EncodedNullString({:?})", self.0)
+    }
+}
+
+impl<L, E> fmt::Debug for PrefixedString<L, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PrefixedString({:?})", self.0)
+    }
+}
+
+#[cfg(feature = "const_generics")]
+impl<const N: usize, E> fmt::Debug for FixedString<N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FixedString({:?})", self.0)
+    }
+}
+
+impl<L, T: fmt::Debug> fmt::Debug for PrefixedVec<L, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PrefixedVec({:?})", self.0)
+    }
+}
+
+// These are implemented by hand so they do not require the prefix and
+// encoding types to implement them as well
+impl<E> Clone for EncodedNullString<E> {
+    fn clone(&self) -> Self {
+        self.0.clone().into()
+    }
+}
+
+impl<L, E> Clone for PrefixedString<L, E> {
+    fn clone(&self) -> Self {
+        self.0.clone().into()
+    }
+}
+
+#[cfg(feature = "const_generics")]
+impl<const N: usize, E> Clone for FixedString<N, E> {
+    fn clone(&self) -> Self {
+        self.0.clone().into()
+    }
+}
+
+impl<L, T: Clone> Clone for PrefixedVec<L, T> {
+    fn clone(&self) -> Self {
+        self.0.clone().into()
+    }
+}
+
+impl<E> PartialEq for EncodedNullString<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<L, E> PartialEq for PrefixedString<L, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+#[cfg(feature = "const_generics")]
+impl<const N: usize, E> PartialEq for FixedString<N, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<L, T: PartialEq> PartialEq for PrefixedVec<L, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<E> Default for EncodedNullString<E> {
+    fn default() -> Self {
+        String::new().into()
+    }
+}
+
+impl<L, E> Default for PrefixedString<L, E> {
+    fn default() -> Self {
+        String::new().into()
+    }
+}
+
+#[cfg(feature = "const_generics")]
+impl<const N: usize, E> Default for FixedString<N, E> {
+    fn default() -> Self {
+        String::new().into()
+    }
+}
+
+impl<L, T> Default for PrefixedVec<L, T> {
+    fn default() -> Self {
+        Vec::new().into()
+    }
+}
+
 impl core::ops::Deref for NullString {
     type Target = Vec<u8>;
 
@@ -239,12 +1181,72 @@ impl core::ops::Deref for NullWideString {
     }
 }
 
+#[cfg(feature = "const_generics")]
+impl<const N: usize, E> core::ops::Deref for FixedString<N, E> {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "const_generics")]
+impl<const N: usize, E> core::ops::DerefMut for FixedString<N, E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<E> core::ops::Deref for EncodedNullString<E> {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> core::ops::DerefMut for EncodedNullString<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<L, E> core::ops::Deref for PrefixedString<L, E> {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<L, E> core::ops::DerefMut for PrefixedString<L, E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<L, T> core::ops::Deref for PrefixedVec<L, T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<L, T> core::ops::DerefMut for PrefixedVec<L, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for NullString {
     fn to_string(&self) -> String {
-        core::str::from_utf8(&self).unwrap().to_string()
+        core::str::from_utf8(self).unwrap().to_string()
     }
 }
 
+#[allow(clippy::to_string_trait_impl)]
 impl ToString for NullWideString {
     fn to_string(&self) -> String {
         String::from_utf16_lossy(self)
@@ -303,4 +1305,154 @@ mod tests {
             "no thanks"
         );
     }
+
+    #[test]
+    fn prefixed_strings() {
+        use crate::{
+            io::Cursor,
+            strings::{Lossy, Utf16, Utf8},
+            BinReaderExt, BinWriterExt, Error, PrefixedString,
+        };
+
+        let mut reader = Cursor::new(b"\x00\x03abc\x00\x02\x00h\x00i");
+        let abc: PrefixedString<u16> = reader.read_be().unwrap();
+        let hi: PrefixedString<u16, Utf16> = reader.read_be().unwrap();
+        assert_eq!(*abc, "abc");
+        assert_eq!(*hi, "hi");
+
+        let mut writer = Cursor::new(Vec::new());
+        writer.write_be(&abc).unwrap();
+        writer.write_be(&hi).unwrap();
+        assert_eq!(writer.into_inner(), reader.into_inner());
+
+        let mut reader = Cursor::new(b"\x02\xFFa");
+        let error = reader.read_le::<PrefixedString<u8>>().unwrap_err();
+        assert!(matches!(
+            error,
+            Error::BadEncoding {
+                pos: 0,
+                encoding: "UTF-8"
+            }
+        ));
+        assert_eq!(reader.position(), 0);
+
+        let lossy: PrefixedString<u8, Lossy<Utf8>> = reader.read_le().unwrap();
+        assert_eq!(*lossy, "\u{FFFD}a");
+
+        let mut reader = Cursor::new(b"\x05abc");
+        assert!(matches!(
+            reader.read_le::<PrefixedString<u8>>().unwrap_err(),
+            Error::Io(_)
+        ));
+        assert_eq!(reader.position(), 0);
+
+        let long = PrefixedString::<u8>::from("a".repeat(256));
+        let error = Cursor::new(Vec::new()).write_le(&long).unwrap_err();
+        assert!(matches!(error, Error::AssertFail { .. }));
+    }
+
+    #[test]
+    fn encoded_null_strings() {
+        use crate::{
+            io::Cursor,
+            strings::{Latin1, Lossy, Utf16Be, Utf16Le},
+            BinReaderExt, BinWriterExt, EncodedNullString, Error,
+        };
+
+        let data = b"\0a\0b\0\0c\0d\0\0\0\xFF\0";
+        let mut reader = Cursor::new(data);
+        let ab: EncodedNullString<Utf16Be> = reader.read_le().unwrap();
+        let cd: EncodedNullString<Utf16Le> = reader.read_be().unwrap();
+        let latin: EncodedNullString<Latin1> = reader.read_be().unwrap();
+        assert_eq!(*ab, "ab");
+        assert_eq!(*cd, "cd");
+        assert_eq!(*latin, "\u{FF}");
+
+        let mut writer = Cursor::new(Vec::new());
+        writer.write_be(&ab).unwrap();
+        writer.write_le(&cd).unwrap();
+        writer.write_le(&latin).unwrap();
+        assert_eq!(writer.into_inner(), data);
+
+        let mut reader = Cursor::new(b"\xD8\x00\0\0");
+        let error = reader.read_le::<EncodedNullString<Utf16Be>>().unwrap_err();
+        assert!(matches!(
+            error,
+            Error::BadEncoding {
+                pos: 0,
+                encoding: "UTF-16BE"
+            }
+        ));
+        assert_eq!(reader.position(), 0);
+        let lossy: EncodedNullString<Lossy<Utf16Be>> = reader.read_le().unwrap();
+        assert_eq!(*lossy, "\u{FFFD}");
+
+        let mut reader = Cursor::new(b"abc");
+        let error = reader.read_le::<EncodedNullString>().unwrap_err();
+        assert!(matches!(error, Error::Io(_)));
+        assert_eq!(reader.position(), 0);
+
+        let mut writer = Cursor::new(Vec::new());
+        let error = writer
+            .write_le(&EncodedNullString::<Latin1>::from("\u{100}"))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::BadEncoding {
+                pos: 0,
+                encoding: "ISO-8859-1"
+            }
+        ));
+    }
+
+    #[cfg(feature = "legacy_encodings")]
+    #[test]
+    fn legacy_encodings() {
+        use crate::{
+            io::Cursor,
+            strings::{ShiftJis, Windows1252},
+            BinReaderExt, BinWriterExt, EncodedNullString, Error,
+        };
+
+        let data = b"\x83\x65\x83\x58\x83\x67\0\x80 \x93quoted\x94\0";
+        let mut reader = Cursor::new(data);
+        let test: EncodedNullString<ShiftJis> = reader.read_le().unwrap();
+        let euro: EncodedNullString<Windows1252> = reader.read_le().unwrap();
+        assert_eq!(*test, "\u{30C6}\u{30B9}\u{30C8}");
+        assert_eq!(*euro, "\u{20AC} \u{201C}quoted\u{201D}");
+
+        let mut writer = Cursor::new(Vec::new());
+        writer.write_le(&test).unwrap();
+        writer.write_le(&euro).unwrap();
+        assert_eq!(writer.into_inner(), data);
+
+        let error = Cursor::new(b"\x83\0")
+            .read_le::<EncodedNullString<ShiftJis>>()
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::BadEncoding {
+                pos: 0,
+                encoding: "Shift_JIS"
+            }
+        ));
+
+        let error = Cursor::new(Vec::new())
+            .write_le(&EncodedNullString::<Windows1252>::from("\u{30C6}"))
+            .unwrap_err();
+        assert!(matches!(error, Error::BadEncoding { .. }));
+    }
+
+    #[test]
+    fn prefixed_vecs() {
+        use crate::{io::Cursor, BinReaderExt, BinWriterExt, PrefixedVec};
+
+        let data = b"\x00\x00\x00\x02\x00\x01\x00\x02";
+        let values: PrefixedVec<u32, u16> = Cursor::new(data).read_be().unwrap();
+        assert_eq!(*values, [1, 2]);
+
+        let mut writer = Cursor::new(Vec::new());
+        writer.write_be(&values).unwrap();
+        assert_eq!(writer.into_inner(), data);
+    }
 }
@@ -0,0 +1,332 @@
+//! Variable-length integers, which use fewer bytes for smaller values.
+//!
+//! Each byte stores seven bits of the value, and its top bit is set when
+//! another byte follows:
+//!
+//! * [`Leb128`] stores the lowest seven bits first, as in WebAssembly, DWARF
+//!   and protobuf. `Leb128<u64>` is a protobuf varint, and signed types such
+//!   as `Leb128<i32>` read signed LEB128, where the last byte is sign extended.
+//! * [`Vlq`] stores the highest seven bits first, as in MIDI files.
+//! * [`ZigZag`] maps signed values to unsigned ones so that small negative
+//!   values are short too, and stores them as unsigned LEB128, as in the
+//!   `sint32` and `sint64` types of protobuf.
+//!
+//! The byte order of the reader does not change them. A value which does not
+//! fit in its type, including one with more bytes than the type needs,
+//! returns an [`Overflow`](crate::Error::Overflow) error instead of wrapping.
+//!
+//! ```rust
+//! use binread::{prelude::*, io::Cursor, varint::{Leb128, Vlq, ZigZag}};
+//!
+//! #[derive(BinRead, BinWrite, Debug)]
+//! struct Record {
+//!     len: Leb128<u32>,
+//!     delta: Leb128<i32>,
+//!     time: Vlq<u32>,
+//!     offset: ZigZag<i64>,
+//! }
+//!
+//! let data = b"\xE5\x8E\x26\x7F\x81\x80\x00\x03";
+//! let record: Record = Cursor::new(data).read_le().unwrap();
+//! assert_eq!(*record.len, 624_485);
+//! assert_eq!(*record.delta, -1);
+//! assert_eq!(*record.time, 0x4000);
+//! assert_eq!(*record.offset, -2);
+//!
+//! let mut writer = Cursor::new(Vec::new());
+//! writer.write_le(&record).unwrap();
+//! assert_eq!(writer.into_inner(), data);
+//!
+//! let error = Cursor::new(b"\xFF\xFF\xFF\xFF\x7F").read_le::<Leb128<u32>>().unwrap_err();
+//! assert!(matches!(error, binread::Error::Overflow { pos: 0, .. }));
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// An integer stored as LEB128, with the lowest seven bits first. Signed
+/// types are read as signed LEB128.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Leb128<T>(pub T);
+
+/// An unsigned integer stored as a variable-length quantity, with the highest
+/// seven bits first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vlq<T>(pub T);
+
+/// A signed integer stored with zigzag encoding as unsigned LEB128, so that
+/// `0, -1, 1, -2` are stored as `0, 1, 2, 3`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZigZag<T>(pub T);
+
+/// The result of adding a byte to a variable-length integer
+pub(crate) enum Step<T> {
+    /// Another byte follows
+    More,
+    /// The byte was the last one
+    Done(T),
+    /// The value does not fit in its type
+    Overflow,
+}
+
+/// A variable-length integer, which is decoded one byte at a time so the
+/// sync and async readers can share the decoding
+pub(crate) trait VarInt: Sized {
+    /// The part of the value decoded so far
+    type State: Default;
+
+    /// The name of the type, for errors
+    const NAME: &'static str;
+
+    /// Adds the next byte to the value
+    fn decode(state: &mut Self::State, byte: u8) -> Step<Self>;
+
+    /// Returns the bytes of the value
+    fn encode(&self) -> Vec<u8>;
+}
+
+wrapper_impl!(Leb128, Vlq, ZigZag);
+
+macro_rules! unsigned_varint_impl {
+    ($($type_name:ty),*) => {
+        $(
+            impl VarInt for Leb128<$type_name> {
+                type State = ($type_name, u32);
+
+                const NAME: &'static str = concat!("Leb128<", stringify!($type_name), ">");
+
+                fn decode((value, shift): &mut Self::State, byte: u8) -> Step<Self> {
+                    let low = <$type_name>::from(byte & 0x7F);
+                    if *shift >= <$type_name>::BITS || (low << *shift) >> *shift != low {
+                        return Step::Overflow;
+                    }
+
+                    *value |= low << *shift;
+                    *shift += 7;
+                    if byte & 0x80 == 0 {
+                        Step::Done(Leb128(*value))
+                    } else {
+                        Step::More
+                    }
+                }
+
+                fn encode(&self) -> Vec<u8> {
+                    let mut value = self.0;
+                    let mut bytes = Vec::new();
+                    loop {
+                        let byte = (value & 0x7F) as u8;
+                        value >>= 7;
+                        if value == 0 {
+                            bytes.push(byte);
+                            return bytes;
+                        }
+                        bytes.push(byte | 0x80);
+                    }
+                }
+            }
+
+            impl VarInt for Vlq<$type_name> {
+                type State = ($type_name, u32);
+
+                const NAME: &'static str = concat!("Vlq<", stringify!($type_name), ">");
+
+                fn decode((value, len): &mut Self::State, byte: u8) -> Step<Self> {
+                    // Leading zero bytes do not change the value, so the
+                    // length is limited as well
+                    *len += 1;
+                    if *len > <$type_name>::BITS.div_ceil(7)
+                        || *value >> (<$type_name>::BITS - 7) != 0
+                    {
+                        return Step::Overflow;
+                    }
+
+                    *value = (*value << 7) | <$type_name>::from(byte & 0x7F);
+                    if byte & 0x80 == 0 {
+                        Step::Done(Vlq(*value))
+                    } else {
+                        Step::More
+                    }
+                }
+
+                fn encode(&self) -> Vec<u8> {
+                    let mut value = self.0;
+                    let mut bytes = vec![(value & 0x7F) as u8];
+                    value >>= 7;
+                    while value != 0 {
+                        bytes.push((value & 0x7F) as u8 | 0x80);
+                        value >>= 7;
+                    }
+
+                    bytes.reverse();
+                    bytes
+                }
+            }
+        )*
+    };
+}
+
+unsigned_varint_impl!(u8, u16, u32, u64, u128);
+
+macro_rules! signed_varint_impl {
+    ($($type_name:ty => $unsigned:ty),*) => {
+        $(
+            impl VarInt for Leb128<$type_name> {
+                type State = ($type_name, u32);
+
+                const NAME: &'static str = concat!("Leb128<", stringify!($type_name), ">");
+
+                fn decode((value, shift): &mut Self::State, byte: u8) -> Step<Self> {
+                    if *shift >= <$type_name>::BITS {
+                        return Step::Overflow;
+                    }
+
+                    let low = byte & 0x7F;
+                    let used = <$type_name>::BITS - *shift;
+                    if used < 7 {
+                        // The bits which do not fit must all repeat the sign bit
+                        let rest = (((low << 1) as i8) >> 1) >> (used - 1);
+                        if rest != 0 && rest != -1 {
+                            return Step::Overflow;
+                        }
+                    }
+
+                    *value |= (low as $type_name) << *shift;
+                    *shift += 7;
+                    if byte & 0x80 != 0 {
+                        return Step::More;
+                    }
+
+                    if *shift < <$type_name>::BITS && byte & 0x40 != 0 {
+                        *value |= -1 << *shift;
+                    }
+
+                    Step::Done(Leb128(*value))
+                }
+
+                fn encode(&self) -> Vec<u8> {
+                    let mut value = self.0;
+                    let mut bytes = Vec::new();
+                    loop {
+                        let byte = (value & 0x7F) as u8;
+                        value >>= 7;
+                        let sign = byte & 0x40 != 0;
+                        if (value == 0 && !sign) || (value == -1 && sign) {
+                            bytes.push(byte);
+                            return bytes;
+                        }
+                        bytes.push(byte | 0x80);
+                    }
+                }
+            }
+
+            impl VarInt for ZigZag<$type_name> {
+                type State = <Leb128<$unsigned> as VarInt>::State;
+
+                const NAME: &'static str = concat!("ZigZag<", stringify!($type_name), ">");
+
+                fn decode(state: &mut Self::State, byte: u8) -> Step<Self> {
+                    match Leb128::<$unsigned>::decode(state, byte) {
+                        Step::More => Step::More,
+                        Step::Done(Leb128(value)) => {
+                            Step::Done(ZigZag((value >> 1) as $type_name ^ -((value & 1) as $type_name)))
+                        }
+                        Step::Overflow => Step::Overflow,
+                    }
+                }
+
+                fn encode(&self) -> Vec<u8> {
+                    let value = self.0;
+                    Leb128(((value << 1) ^ (value >> (<$type_name>::BITS - 1))) as $unsigned).encode()
+                }
+            }
+        )*
+    };
+}
+
+signed_varint_impl!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Cursor;
+    use crate::{BinReaderExt, BinWriterExt, Error};
+
+    fn round_trip<T>(value: T, data: &[u8])
+    where
+        T: for<'a> crate::BinRead<Args<'a> = ()>
+            + for<'a> crate::BinWrite<Args<'a> = ()>
+            + PartialEq
+            + core::fmt::Debug,
+    {
+        let mut reader = Cursor::new(data);
+        assert_eq!(reader.read_le::<T>().unwrap(), value);
+        assert_eq!(reader.position(), data.len() as u64);
+
+        let mut writer = Cursor::new(Vec::new());
+        writer.write_le(&value).unwrap();
+        assert_eq!(writer.into_inner(), data);
+    }
+
+    fn overflow<T: for<'a> crate::BinRead<Args<'a> = ()> + core::fmt::Debug>(data: &[u8]) {
+        let mut reader = Cursor::new(data);
+        let error = reader.read_le::<T>().unwrap_err();
+        assert!(
+            matches!(error, Error::Overflow { pos: 0, .. }),
+            "{:?}",
+            error
+        );
+        assert_eq!(reader.position(), 0);
+    }
+
+    #[test]
+    fn leb128() {
+        round_trip(Leb128(0u32), b"\x00");
+        round_trip(Leb128(127u8), b"\x7F");
+        round_trip(Leb128(255u8), b"\xFF\x01");
+        round_trip(Leb128(624_485u32), b"\xE5\x8E\x26");
+        round_trip(
+            Leb128(u64::MAX),
+            b"\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\x01",
+        );
+        round_trip(Leb128(-1i32), b"\x7F");
+        round_trip(Leb128(63i8), b"\x3F");
+        round_trip(Leb128(64i8), b"\xC0\x00");
+        round_trip(Leb128(-128i8), b"\x80\x7F");
+        round_trip(Leb128(-123_456i32), b"\xC0\xBB\x78");
+        round_trip(
+            Leb128(i64::MIN),
+            b"\x80\x80\x80\x80\x80\x80\x80\x80\x80\x7F",
+        );
+
+        overflow::<Leb128<u8>>(b"\x80\x02");
+        overflow::<Leb128<u32>>(b"\xFF\xFF\xFF\xFF\x1F");
+        overflow::<Leb128<u32>>(b"\x80\x80\x80\x80\x80\x00");
+        overflow::<Leb128<i8>>(b"\x80\x01");
+        overflow::<Leb128<i8>>(b"\xFF\x7E");
+        overflow::<Leb128<i32>>(b"\x80\x80\x80\x80\x40");
+    }
+
+    #[test]
+    fn vlq() {
+        round_trip(Vlq(0u32), b"\x00");
+        round_trip(Vlq(0x40u32), b"\x40");
+        round_trip(Vlq(0x2000u32), b"\xC0\x00");
+        round_trip(Vlq(0x0FFF_FFFFu32), b"\xFF\xFF\xFF\x7F");
+        round_trip(Vlq(255u8), b"\x81\x7F");
+
+        overflow::<Vlq<u8>>(b"\x82\x00");
+        overflow::<Vlq<u32>>(b"\x80\x80\x80\x80\x80\x00");
+    }
+
+    #[test]
+    fn zigzag() {
+        round_trip(ZigZag(0i32), b"\x00");
+        round_trip(ZigZag(-1i32), b"\x01");
+        round_trip(ZigZag(1i32), b"\x02");
+        round_trip(ZigZag(-64i64), b"\x7F");
+        round_trip(ZigZag(i32::MIN), b"\xFF\xFF\xFF\xFF\x0F");
+        round_trip(ZigZag(i32::MAX), b"\xFE\xFF\xFF\xFF\x0F");
+
+        overflow::<ZigZag<i32>>(b"\xFF\xFF\xFF\xFF\x1F");
+    }
+}
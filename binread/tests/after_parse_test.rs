@@ -9,7 +9,7 @@ fn BinReaderExt_calls_after_parse() {
 }
 
 #[derive(BinRead)]
-struct Try<BR: BinRead<Args = ()>>(#[br(try)] Option<BR>);
+struct Try<BR: for<'a> BinRead<Args<'a> = ()>>(#[br(try)] Option<BR>);
 
 #[test]
 fn try_calls_after_parse() {
@@ -0,0 +1,640 @@
+use binread::{
+    checksum::{Checksummed, Crc32, Sum8},
+    collections::DuplicateKeys,
+    endian::{Be, Le},
+    file_ptr::RelativeFilePtr8,
+    float::{bf16, f16},
+    helpers::{until_eof, until_eof_async},
+    int::{i24, u48},
+    prelude::*,
+    strings::Utf16Le,
+    varint::{Leb128, Vlq, ZigZag},
+    BinReadAsync, BinReaderAsyncExt, EncodedNullString, FilePtr, FilePtr8, LazyFilePtr, Limits,
+    NullString, NullableFilePtr, PosValue, PrefixedString, PrefixedVec, ReadOptions, SharedFilePtr,
+};
+use futures_executor::block_on;
+use futures_util::io::Cursor;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+
+#[test]
+fn read_struct() {
+    #[derive(BinRead, BinReadAsync, Debug)]
+    #[br(big, magic = b"TST")]
+    struct Test {
+        count: u8,
+        #[br(little, count = count)]
+        values: Vec<u16>,
+        #[br(align_before = 8)]
+        name: NullString,
+        #[br(restore_position)]
+        peek: u8,
+        position: PosValue<u8>,
+        #[br(pad_before = 1, map = |x: u8| x * 2)]
+        doubled: u8,
+        #[br(if(count > 5))]
+        missing: Option<u8>,
+        #[br(try)]
+        eof: Option<u32>,
+    }
+
+    let data = b"TST\x02\x01\x00\x02\x00hi\0\x07\x00\x05";
+    let test: Test = block_on(Cursor::new(data).read_be_async()).unwrap();
+    assert_eq!(test.count, 2);
+    assert_eq!(test.values, [1, 2]);
+    assert_eq!(test.name.into_string(), "hi");
+    assert_eq!(test.peek, 7);
+    assert_eq!(test.position.pos, 0xB);
+    assert_eq!(*test.position, 7);
+    assert_eq!(test.doubled, 10);
+    assert_eq!(test.missing, None);
+    assert_eq!(test.eof, None);
+
+    // The synchronous implementation reads the same value
+    let sync: Test = binread::io::Cursor::new(data).read_be().unwrap();
+    assert_eq!(sync.doubled, test.doubled);
+}
+
+#[test]
+fn read_file_ptr() {
+    #[derive(BinRead, BinReadAsync, Debug)]
+    struct Inner {
+        a: u8,
+        b: FilePtr8<u8>,
+    }
+
+    #[derive(BinRead, BinReadAsync, Debug)]
+    struct Test {
+        first: FilePtr8<Inner>,
+        #[br(deref_now)]
+        second: FilePtr8<u16>,
+        #[br(parse_with = FilePtr8::parse, parse_with_async = FilePtr::<u8, u8>::parse_async)]
+        third: u8,
+    }
+
+    let mut reader = Cursor::new(b"\x03\x05\x04\x0A\x07\x00\x0C\x0B");
+    let test: Test = block_on(reader.read_be_async()).unwrap();
+    assert_eq!(test.first.a, 0x0A);
+    assert_eq!(*test.first.b, 0x0B);
+    assert_eq!(*test.second, 0x0C);
+    assert_eq!(test.third, 0x07);
+    assert_eq!(reader.position(), 3);
+}
+
+#[test]
+fn read_lazy_file_ptr() {
+    #[derive(BinRead, BinReadAsync)]
+    struct Test {
+        value: LazyFilePtr<u8, u16>,
+        #[br(count = 2)]
+        values: LazyFilePtr<u8, Vec<u8>>,
+        relative: RelativeFilePtr8<u8>,
+    }
+
+    let mut reader = Cursor::new(b"\x03\x05\xFE\x00\x07\x08\x09");
+    let mut test: Test = block_on(reader.read_be_async()).unwrap();
+    assert_eq!(test.value.value(), None);
+    assert_eq!(*block_on(test.value.get_async(&mut reader)).unwrap(), 7);
+    assert_eq!(test.value.value(), Some(&7));
+    assert_eq!(
+        block_on(test.values.read_value_async(&mut reader)).unwrap(),
+        [8, 9]
+    );
+    assert_eq!(*test.relative, 3);
+    assert_eq!(reader.position(), 3);
+}
+
+#[test]
+fn read_nullable_file_ptr() {
+    #[derive(BinRead, BinReadAsync)]
+    struct Test {
+        present: NullableFilePtr<u8, u8>,
+        absent: NullableFilePtr<u8, u8>,
+    }
+
+    let test: Test = block_on(Cursor::new(b"\x02\x00\x07").read_be_async()).unwrap();
+    assert_eq!(*test.present, Some(7));
+    assert!(test.absent.is_null());
+}
+
+#[test]
+fn read_shared_file_ptr() {
+    #[derive(BinRead, BinReadAsync)]
+    struct Test {
+        #[br(count = 3)]
+        names: Vec<SharedFilePtr<u8, NullString>>,
+    }
+
+    #[derive(BinRead, BinReadAsync, Debug)]
+    struct Node {
+        value: u8,
+        next: NullableFilePtr<u8, SharedFilePtr<u8, Node>>,
+    }
+
+    let test: Test = block_on(Cursor::new(b"\x03\x03\x03abc\0").read_be_async()).unwrap();
+    assert_eq!(test.names[0].to_string(), "abc");
+    assert!(Arc::ptr_eq(
+        test.names[0].value.as_ref().unwrap(),
+        test.names[2].value.as_ref().unwrap()
+    ));
+
+    let node = block_on(Cursor::new(b"\x01\x0A\x00").read_be_async::<SharedFilePtr<u8, Node>>())
+        .unwrap()
+        .into_inner();
+    assert_eq!(node.value, 0x0A);
+    assert!(node.next.is_null());
+
+    let error =
+        block_on(Cursor::new(b"\x02\x00\x0A\x04\x02").read_be_async::<SharedFilePtr<u8, Node>>())
+            .unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binread::Error::PointerCycle { pos: 2 }
+    ));
+}
+
+#[test]
+fn read_shared_file_ptr_futures_are_send() {
+    fn assert_send<T: Send>(value: T) -> T {
+        value
+    }
+
+    #[derive(BinRead, BinReadAsync)]
+    struct Names {
+        #[br(count = 2)]
+        names: Vec<SharedFilePtr<u8, NullString>>,
+    }
+
+    #[derive(BinRead, BinReadAsync)]
+    #[br(import(scale: u8))]
+    struct Scaled {
+        #[br(map = |x: u8| x * scale)]
+        value: u8,
+    }
+
+    let data = b"\x02\x02ab\0";
+    let names = block_on(assert_send(Cursor::new(data).read_be_async::<Names>())).unwrap();
+    assert_eq!(names.names[1].to_string(), "ab");
+    assert!(Arc::ptr_eq(
+        names.names[0].value.as_ref().unwrap(),
+        names.names[1].value.as_ref().unwrap()
+    ));
+    let names = block_on(assert_send(Cursor::new(data).read_le_async::<Names>())).unwrap();
+    assert_eq!(names.names[0].to_string(), "ab");
+    let names = block_on(assert_send(Names::read_async(&mut Cursor::new(data)))).unwrap();
+    assert_eq!(names.names[0].to_string(), "ab");
+
+    let names = block_on(assert_send(Names::read_args_async(
+        &mut Cursor::new(data),
+        (),
+    )))
+    .unwrap();
+    assert_eq!(names.names[0].to_string(), "ab");
+
+    let scaled = block_on(assert_send(
+        Cursor::new(b"\x01\x05")
+            .read_type_args_async::<SharedFilePtr<u8, Scaled>>(binread::Endian::Big, (2,)),
+    ))
+    .unwrap();
+    assert_eq!(scaled.into_inner().value, 10);
+}
+
+#[test]
+fn read_futures_are_send() {
+    fn assert_send<T: Send>(value: T) -> T {
+        value
+    }
+
+    #[derive(BinRead, BinReadAsync)]
+    #[br(import(len: u8))]
+    struct Inner {
+        #[br(count = len)]
+        values: Vec<u16>,
+    }
+
+    #[derive(BinRead, BinReadAsync)]
+    #[br(magic = b"TST")]
+    struct Test {
+        len: u8,
+        #[br(args(len))]
+        inner: FilePtr8<Inner>,
+        pair: (u8, Option<Le<u16>>),
+        #[br(parse_with = until_eof, parse_with_async = until_eof_async)]
+        rest: Vec<u8>,
+    }
+
+    let data = b"TST\x01\x08\x07\x34\x12\xAB\xCD";
+    let value = block_on(assert_send(Cursor::new(data).read_be_async::<u32>())).unwrap();
+    assert_eq!(value, 0x5453_5401);
+    let inner = block_on(assert_send(Inner::read_args_async(
+        &mut Cursor::new(b"\x01\x01"),
+        (1,),
+    )))
+    .unwrap();
+    assert_eq!(inner.values, [0x0101]);
+    let test = block_on(assert_send(Test::read_async(&mut Cursor::new(data)))).unwrap();
+    assert_eq!(test.rest, [0xAB, 0xCD]);
+
+    let test = block_on(assert_send(Cursor::new(data).read_be_async::<Test>())).unwrap();
+    assert_eq!(test.len, 1);
+    assert_eq!(test.inner.values, [0xABCD]);
+    assert_eq!(test.pair.0, 7);
+    assert_eq!(test.pair.1.map(|value| value.0), Some(0x1234));
+    assert_eq!(test.rest, [0xAB, 0xCD]);
+}
+
+#[test]
+fn read_until_eof() {
+    #[derive(BinRead, BinReadAsync)]
+    struct Test {
+        len: u8,
+        #[br(parse_with = until_eof, parse_with_async = until_eof_async)]
+        rest: Vec<u16>,
+    }
+
+    let test: Test = block_on(Cursor::new(b"\x02\x00\x01\x00\x02").read_be_async()).unwrap();
+    assert_eq!(test.len, 2);
+    assert_eq!(test.rest, [1, 2]);
+}
+
+#[test]
+fn read_enums() {
+    #[derive(BinRead, BinReadAsync, Debug, PartialEq)]
+    #[br(repr = u8)]
+    enum Kind {
+        A = 1,
+        B = 2,
+    }
+
+    #[derive(BinRead, BinReadAsync, Debug, PartialEq)]
+    #[br(return_all_errors)]
+    enum Command {
+        #[br(magic = 0u8)]
+        Stop,
+        #[br(magic = 1u8)]
+        Move { x: i8, kind: Kind },
+        #[br(magic = 1u8)]
+        Short(u8),
+    }
+
+    let read = |data: &'static [u8]| block_on(Cursor::new(data).read_le_async::<Command>());
+    assert_eq!(read(b"\0").unwrap(), Command::Stop);
+    assert_eq!(
+        read(b"\x01\xFF\x02").unwrap(),
+        Command::Move {
+            x: -1,
+            kind: Kind::B
+        }
+    );
+    assert_eq!(read(b"\x01\xFF\x03").unwrap(), Command::Short(0xFF));
+
+    let mut reader = Cursor::new(b"\x02");
+    let error = block_on(reader.read_le_async::<Command>()).unwrap_err();
+    match error {
+        binread::Error::EnumErrors { variant_errors, .. } => assert_eq!(variant_errors.len(), 3),
+        error => panic!("unexpected error: {:?}", error),
+    }
+    assert_eq!(reader.position(), 0);
+}
+
+#[test]
+fn read_prefixed() {
+    #[derive(BinRead, BinReadAsync)]
+    struct Test {
+        name: PrefixedString<u8>,
+        values: PrefixedVec<u16, u8>,
+        label: EncodedNullString<Utf16Le>,
+    }
+
+    let data = b"\x02hi\x00\x02\x01\x02o\0k\0\0\0";
+    let test: Test = block_on(Cursor::new(data).read_be_async()).unwrap();
+    assert_eq!(*test.name, "hi");
+    assert_eq!(*test.values, [1, 2]);
+    assert_eq!(*test.label, "ok");
+
+    let mut reader = Cursor::new(b"\x01\xFF");
+    let error = block_on(reader.read_be_async::<PrefixedString<u8>>()).unwrap_err();
+    assert!(matches!(error, binread::Error::BadEncoding { pos: 0, .. }));
+    assert_eq!(reader.position(), 0);
+}
+
+#[test]
+fn read_bits() {
+    #[derive(BinRead, BinReadAsync)]
+    #[br(lsb_first)]
+    struct Test {
+        #[br(bits = 3)]
+        low: u8,
+        #[br(bits = 5)]
+        high: u8,
+        #[br(bits = 4)]
+        next: u8,
+        last: u8,
+    }
+
+    let test: Test = block_on(Cursor::new(b"\xF9\x0A\x03").read_le_async()).unwrap();
+    assert_eq!(test.low, 1);
+    assert_eq!(test.high, 0x1F);
+    assert_eq!(test.next, 0xA);
+    assert_eq!(test.last, 3);
+}
+
+#[test]
+fn read_args_and_errors() {
+    #[derive(BinRead, BinReadAsync, Debug)]
+    #[br(import(expected: u8))]
+    struct Inner {
+        #[br(assert(value == expected))]
+        value: u8,
+    }
+
+    #[derive(BinRead, BinReadAsync, Debug)]
+    struct Test {
+        #[br(try_map = |x: u8| if x < 10 { Ok(x) } else { Err("too big") })]
+        small: u8,
+        #[br(args(small))]
+        inner: Inner,
+    }
+
+    let test: Test = block_on(Cursor::new(b"\x03\x03").read_le_async()).unwrap();
+    assert_eq!(test.small, 3);
+    assert_eq!(test.inner.value, 3);
+
+    let error = block_on(Cursor::new(b"\x0A\x0A").read_le_async::<Test>()).unwrap_err();
+    assert_eq!(error.custom_err::<&str>(), Some(&"too big"));
+
+    // Errors have the same context as when reading synchronously
+    let data = b"\x03\x04";
+    let error = block_on(Cursor::new(data).read_le_async::<Test>()).unwrap_err();
+    let sync_error = binread::io::Cursor::new(data)
+        .read_le::<Test>()
+        .unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binread::Error::AssertFail { .. }
+    ));
+    assert_eq!(error.to_string(), sync_error.to_string());
+
+    let error = block_on(Cursor::new(b"\x03").read_le_async::<Test>()).unwrap_err();
+    assert!(matches!(error.root_cause(), binread::Error::Io(_)));
+}
+
+#[test]
+fn read_size() {
+    #[derive(BinRead, BinReadAsync, Debug)]
+    struct Chunk {
+        len: u8,
+        #[br(size = len, parse_with = until_eof, parse_with_async = until_eof_async)]
+        data: Vec<u16>,
+        #[br(size_exact = 2)]
+        name: NullString,
+        value: u8,
+    }
+
+    let mut reader = Cursor::new(b"\x05\x01\x00\x02\x00\xFFa\0\x07");
+    let chunk: Chunk = block_on(reader.read_le_async()).unwrap();
+    assert_eq!(chunk.len, 5);
+    assert_eq!(chunk.data, [1, 2]);
+    assert_eq!(chunk.name.to_string(), "a");
+    assert_eq!(chunk.value, 7);
+
+    let error = block_on(Cursor::new(b"\x00\0\0\x07").read_le_async::<Chunk>()).unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binread::Error::AssertFail { pos: 1, .. }
+    ));
+}
+
+#[test]
+fn read_checksummed() {
+    #[derive(BinRead, BinReadAsync)]
+    struct Test {
+        #[br(count = 2)]
+        values: Checksummed<Vec<u8>, Sum8>,
+        name: Checksummed<NullString, Crc32>,
+    }
+
+    let data = b"\x01\x02\x03hi\0\x0D\xBA\x93\x64";
+    let test: Test = block_on(Cursor::new(data).read_be_async()).unwrap();
+    assert_eq!(*test.values, [1, 2]);
+    assert_eq!(test.name.to_string(), "hi");
+
+    let mut reader = Cursor::new(b"\x01\x02\x04");
+    let error = block_on(reader.read_be_async::<Checksummed<[u8; 2], Sum8>>()).unwrap_err();
+    assert!(matches!(
+        error,
+        binread::Error::BadChecksum {
+            pos: 2,
+            expected: 4,
+            actual: 3
+        }
+    ));
+    assert_eq!(reader.position(), 0);
+}
+
+#[test]
+fn read_collections() {
+    #[derive(BinRead, BinReadAsync)]
+    struct Test {
+        #[br(count = 2)]
+        name: String,
+        #[br(count = 2)]
+        values: VecDeque<u8>,
+        #[br(count = 2, args_tuple = DuplicateKeys::FirstWins)]
+        map: HashMap<u8, u8>,
+    }
+
+    let test: Test = block_on(Cursor::new(b"hi\x01\x02\x05\x06\x05\x07").read_le_async()).unwrap();
+    assert_eq!(test.name, "hi");
+    assert_eq!(test.values, [1, 2]);
+    assert_eq!(test.map[&5], 6);
+
+    let mut reader = Cursor::new(b"\x05\x06\x05\x07");
+    let mut options = ReadOptions::default();
+    options.count = Some(2);
+    let error = block_on(BTreeMap::<u8, u8>::read_options_async(
+        &mut reader,
+        &options,
+        DuplicateKeys::Error,
+    ))
+    .unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binread::Error::AssertFail { pos: 2, .. }
+    ));
+}
+
+#[test]
+fn read_limits() {
+    #[derive(BinRead, BinReadAsync, Debug)]
+    #[br(big)]
+    struct Test {
+        len: u8,
+        #[br(count = len)]
+        values: Vec<u16>,
+        #[br(parse_with = until_eof, parse_with_async = until_eof_async)]
+        rest: Vec<u8>,
+    }
+
+    let mut options = ReadOptions::default();
+    options.limits = Limits::new().max_count(2);
+    let test = block_on(Test::read_options_async(
+        &mut Cursor::new(b"\x02\0\x01\0\x02ab"),
+        &options,
+        (),
+    ))
+    .unwrap();
+    assert_eq!(test.len, 2);
+    assert_eq!(test.values, [1, 2]);
+    assert_eq!(test.rest, b"ab");
+
+    for (data, pos) in [(&b"\x03"[..], 1), (b"\x00abc", 4)] {
+        let error = block_on(Test::read_options_async(
+            &mut Cursor::new(data),
+            &options,
+            (),
+        ))
+        .unwrap_err();
+        assert!(matches!(
+            *error.root_cause(),
+            binread::Error::LimitExceeded { pos: p, limit: "max_count", max: 2 } if p == pos
+        ));
+    }
+
+    options.limits = Limits::new().max_depth(1);
+    let error = block_on(FilePtr8::<FilePtr8<u8>>::parse_async(
+        &mut Cursor::new(b"\x01\x02\x03"),
+        &options,
+        (),
+    ))
+    .unwrap_err();
+    assert!(matches!(
+        error,
+        binread::Error::LimitExceeded {
+            pos: 2,
+            limit: "max_depth",
+            ..
+        }
+    ));
+
+    let limits = Limits::new().max_bytes_read(5);
+    let test: Test = block_on(Cursor::new(b"\x01\0\x01a").read_with_limits_async(
+        binread::Endian::Big,
+        limits,
+        (),
+    ))
+    .unwrap();
+    assert_eq!(test.rest, b"a");
+
+    let error = block_on(
+        Cursor::new(b"\x01\0\x01abc").read_with_limits_async::<Test>(
+            binread::Endian::Big,
+            limits,
+            (),
+        ),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        error,
+        binread::Error::LimitExceeded {
+            pos: 5,
+            limit: "max_bytes_read",
+            max: 5,
+        }
+    ));
+}
+
+#[test]
+fn read_varints() {
+    #[derive(BinRead, BinReadAsync)]
+    struct Test {
+        len: Leb128<u32>,
+        delta: Leb128<i16>,
+        time: Vlq<u32>,
+        offset: ZigZag<i64>,
+    }
+
+    let test: Test =
+        block_on(Cursor::new(b"\xE5\x8E\x26\x7F\x81\x80\x00\x03").read_le_async()).unwrap();
+    assert_eq!(*test.len, 624_485);
+    assert_eq!(*test.delta, -1);
+    assert_eq!(*test.time, 0x4000);
+    assert_eq!(*test.offset, -2);
+
+    let mut reader = Cursor::new(b"\x80\x80\x04");
+    let error = block_on(reader.read_le_async::<Leb128<u16>>()).unwrap_err();
+    assert!(matches!(
+        error,
+        binread::Error::Overflow {
+            pos: 0,
+            type_name: "Leb128<u16>"
+        }
+    ));
+    assert_eq!(reader.position(), 0);
+}
+
+#[test]
+fn read_odd_sized_ints() {
+    #[derive(BinRead, BinReadAsync)]
+    struct Test {
+        sample: i24,
+        #[br(little)]
+        time: u48,
+    }
+
+    let test: Test =
+        block_on(Cursor::new(b"\xFF\xFF\xFE\x01\x02\x03\x04\x05\x06").read_be_async()).unwrap();
+    assert_eq!(test.sample.get(), -2);
+    assert_eq!(test.time.get(), 0x0605_0403_0201);
+}
+
+#[test]
+fn read_half_floats() {
+    #[derive(BinRead, BinReadAsync)]
+    struct Test {
+        x: f16,
+        #[br(little)]
+        y: bf16,
+    }
+
+    let test: Test = block_on(Cursor::new(b"\xC1\x00\x40\x3F").read_be_async()).unwrap();
+    assert_eq!(test.x.to_f32(), -2.5);
+    assert_eq!(test.y.to_f64(), 0.75);
+}
+
+#[test]
+fn read_fixed_endian() {
+    #[derive(BinRead, BinReadAsync)]
+    struct Test {
+        length: u16,
+        #[br(count = length)]
+        values: Vec<Be<u16>>,
+        tail: Le<u16>,
+    }
+
+    let test: Test =
+        block_on(Cursor::new(b"\x00\x02\x00\x01\x00\x02\x03\x00").read_be_async()).unwrap();
+    assert_eq!(test.length, 2);
+    assert_eq!(test.values, [Be(1), Be(2)]);
+    assert_eq!(*test.tail, 3);
+}
+
+#[test]
+fn read_endian_from() {
+    #[derive(BinRead, BinReadAsync, Debug)]
+    #[br(endian_from = "order", big = b"MM", little = b"II")]
+    struct Test {
+        order: [u8; 2],
+        values: [u16; 2],
+    }
+
+    let test: Test = block_on(Cursor::new(b"II\x01\0\x02\0").read_be_async()).unwrap();
+    assert_eq!(test.order, *b"II");
+    assert_eq!(test.values, [1, 2]);
+
+    let error = block_on(Cursor::new(b"XX\x01\0\x02\0").read_be_async::<Test>()).unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binread::Error::AssertFail { pos: 0, .. }
+    ));
+}
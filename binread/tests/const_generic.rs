@@ -1,4 +1,10 @@
-use binread::{io::Cursor, BinRead, BinReaderExt};
+use binread::{
+    float::{Fixed, I8F8, U16F16},
+    io::Cursor,
+    prelude::*,
+    strings::{Lossy, Padding, Utf8},
+    Error, FixedString, FixedWideString,
+};
 
 #[test]
 fn const_generic_test() {
@@ -9,3 +15,71 @@ fn const_generic_test() {
 
     assert_eq!(IN, out);
 }
+
+#[test]
+fn fixed_strings() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    struct Entry {
+        name: FixedString<8>,
+        #[br(args_tuple = Padding::Trailing(' '))]
+        label: FixedString<6>,
+        #[br(args_tuple = Padding::Unpadded)]
+        code: FixedString<2>,
+        wide: FixedWideString<4>,
+    }
+
+    let data = b"abc\0\0\0\0\0hi    OKh\0i\0\0\0\0\0";
+    let entry: Entry = Cursor::new(data).read_le().unwrap();
+    assert_eq!(*entry.name, "abc");
+    assert_eq!(*entry.label, "hi");
+    assert_eq!(*entry.code, "OK");
+    assert_eq!(*entry.wide, "hi");
+
+    let mut writer = Cursor::new(Vec::new());
+    writer.write_le(&entry).unwrap();
+    assert_eq!(writer.into_inner(), data);
+}
+
+#[test]
+fn fixed_string_errors() {
+    // Data after the null is ignored, even if it is not valid
+    let mut reader = Cursor::new(b"ab\0\xFF\xFF\xFF");
+    let name: FixedString<6> = reader.read_be().unwrap();
+    assert_eq!(*name, "ab");
+
+    let mut reader = Cursor::new(b"a\xFFcd");
+    let error = reader.read_be::<FixedString<4>>().unwrap_err();
+    assert!(matches!(error, Error::BadEncoding { pos: 0, .. }));
+    assert_eq!(reader.position(), 0);
+    let lossy: FixedString<4, Lossy<Utf8>> = reader.read_be().unwrap();
+    assert_eq!(*lossy, "a\u{FFFD}cd");
+
+    let error = Cursor::new(Vec::new())
+        .write_be(&FixedString::<2>::from("abc"))
+        .unwrap_err();
+    assert!(matches!(error, Error::AssertFail { .. }));
+    let error = Cursor::new(Vec::new())
+        .write_be_args(&FixedString::<4>::from("abc"), Padding::Unpadded)
+        .unwrap_err();
+    assert!(matches!(error, Error::AssertFail { .. }));
+}
+
+#[test]
+fn fixed_point() {
+    #[derive(BinRead, BinWrite)]
+    struct Transform {
+        scale: U16F16,
+        angle: I8F8,
+        offset: Fixed<i16, 4>,
+    }
+
+    let data = b"\x00\x40\x02\x00\x80\xFE\xF8\xFF";
+    let transform: Transform = Cursor::new(data).read_le().unwrap();
+    assert_eq!(transform.scale.to_f64(), 2.25);
+    assert_eq!(transform.angle.to_f32(), -1.5);
+    assert_eq!(transform.offset.to_f32(), -0.5);
+
+    let mut writer = Cursor::new(Vec::new());
+    writer.write_le(&transform).unwrap();
+    assert_eq!(writer.into_inner(), data);
+}
@@ -0,0 +1,83 @@
+use binread::{error::ContextFrame, io::Cursor, BinRead, Error};
+
+#[test]
+fn backtrace_nested_path() {
+    let name_line = line!() + 5;
+    #[derive(BinRead, Debug)]
+    #[br(big)]
+    struct Entry {
+        id: u8,
+        #[br(assert(name != 0))]
+        name: u32,
+    }
+
+    #[derive(BinRead, Debug)]
+    struct Header {
+        count: u8,
+        #[br(count = count)]
+        entries: Vec<Entry>,
+    }
+
+    let error = Header::read(&mut Cursor::new(b"\x02\x01\0\0\0\x01\x02\0\0")).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Header.entries[1].name @ 0x7: UnexpectedEof"
+    );
+    assert!(matches!(error.root_cause(), Error::Io(..)));
+
+    let backtrace = match error {
+        Error::Backtrace(backtrace) => backtrace,
+        _ => panic!("missing backtrace"),
+    };
+    assert_eq!(
+        backtrace.frames,
+        [
+            ContextFrame::Field {
+                type_name: "Entry",
+                field: "name",
+                pos: 7,
+                line: name_line,
+            },
+            ContextFrame::Index { index: 1, pos: 6 },
+            ContextFrame::Field {
+                type_name: "Header",
+                field: "entries",
+                pos: 6,
+                line: name_line + 7,
+            },
+        ]
+    );
+}
+
+#[test]
+fn backtrace_enum_and_tuple() {
+    #[derive(BinRead, Debug)]
+    struct Point(u8, u16);
+
+    #[derive(BinRead, Debug)]
+    enum Command {
+        #[br(magic = 1u8)]
+        Move { to: Point },
+    }
+
+    let error = Command::read(&mut Cursor::new(b"\x01\x02\x03")).unwrap_err();
+    match error {
+        Error::EnumErrors { variant_errors, .. } => assert_eq!(
+            variant_errors[0].1.to_string(),
+            "Command::Move.to.1 @ 0x2: UnexpectedEof"
+        ),
+        _ => panic!("wrong error type"),
+    }
+}
+
+#[test]
+fn backtrace_custom_err() {
+    #[derive(BinRead, Debug)]
+    struct Test {
+        #[br(try_map = |x: u8| if x == 0 { Err("zero") } else { Ok(x) })]
+        value: u8,
+    }
+
+    let error = Test::read(&mut Cursor::new(b"\0")).unwrap_err();
+    assert_eq!(error.custom_err::<&str>(), Some(&"zero"));
+}
@@ -0,0 +1,116 @@
+use binread::{io::Cursor, BinRead, BinReaderExt, BinWrite};
+
+fn to_bytes<T: for<'a> BinWrite<Args<'a> = ()>>(value: &T) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new());
+    value.write(&mut writer).unwrap();
+    writer.into_inner()
+}
+
+#[test]
+fn bits_msb_first() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        #[br(bits = 3)]
+        version: u8,
+        #[br(bits = 5)]
+        kind: u8,
+        #[br(bits = 4)]
+        flags: u8,
+        len: u16,
+        #[br(calc = len * 2)]
+        double_len: u16,
+        #[br(bits = 1)]
+        enabled: bool,
+        #[br(bits = 2)]
+        delta: i8,
+    }
+
+    let value = Test {
+        version: 5,
+        kind: 0b1_0011,
+        flags: 0xA,
+        len: 0x102,
+        double_len: 0x204,
+        enabled: true,
+        delta: -1,
+    };
+
+    // The unused low bits of the third byte are skipped when reading
+    let result: Test = Cursor::new(b"\xB3\xAF\x01\x02\xE0").read_ne().unwrap();
+    assert_eq!(result, value);
+    assert_eq!(to_bytes(&value), b"\xB3\xA0\x01\x02\xE0");
+}
+
+#[test]
+fn bits_lsb_first() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[br(lsb_first)]
+    struct Test {
+        #[br(bits = 3)]
+        a: u8,
+        #[br(bits = 9)]
+        b: u16,
+    }
+
+    let value = Test {
+        a: 5,
+        b: 0b1_0001_0011,
+    };
+
+    let result = Test::read(&mut Cursor::new(b"\x9D\x08")).unwrap();
+    assert_eq!(result, value);
+    assert_eq!(to_bytes(&value), b"\x9D\x08");
+}
+
+#[test]
+fn bits_enum_variant_order() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(lsb_first)]
+    enum Test {
+        #[br(magic = 0u8)]
+        Lsb(#[br(bits = 4)] u8, #[br(bits = 4)] u8),
+        #[br(magic = 1u8, msb_first)]
+        Msb(
+            #[br(bits = 4)] u8,
+            #[br(bits = 4, map = |x: u8| u32::from(x) * 2)] u32,
+        ),
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x00\x21")).unwrap(),
+        Test::Lsb(1, 2)
+    );
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x01\x21")).unwrap(),
+        Test::Msb(2, 2)
+    );
+}
+
+#[test]
+fn bits_errors() {
+    #[derive(BinRead, Debug)]
+    struct Test {
+        #[br(bits = 4)]
+        a: u8,
+        #[br(bits = 12)]
+        b: u8,
+    }
+
+    let error = Test::read(&mut Cursor::new(b"\xFF\xFF")).expect_err("accepted bad bit count");
+    assert!(matches!(
+        error.root_cause(),
+        binread::Error::AssertFail { pos: 0x1, .. }
+    ));
+
+    #[derive(BinRead, Debug)]
+    struct Eof {
+        #[br(bits = 4)]
+        a: u8,
+        #[br(bits = 8)]
+        b: u8,
+    }
+
+    let error = Eof::read(&mut Cursor::new(b"\xFF")).expect_err("accepted short data");
+    assert_eq!(error.to_string(), "Eof.b @ 0x1: UnexpectedEof");
+}
@@ -0,0 +1,93 @@
+use binread::{io::SliceReader, BinRead, BinReadBorrowed, Error, FilePtr8, NullString};
+
+#[test]
+fn borrowed_struct() {
+    #[derive(BinRead, Debug)]
+    struct Header {
+        len: u8,
+        kind: u8,
+    }
+
+    #[derive(BinReadBorrowed, Debug)]
+    #[br(magic = b"BLB")]
+    struct Blob<'a> {
+        header: Header,
+        #[br(count = header.len)]
+        data: &'a [u8],
+        name: &'a str,
+        #[br(if(header.kind == 1), count = 2)]
+        suffix: Option<&'a str>,
+        owned: NullString,
+        ptr: FilePtr8<u8>,
+    }
+
+    let data = b"BLB\x03\x01abcfoo\0hiowned\0\x03";
+    let blob: Blob = SliceReader::new(data).read_le().unwrap();
+    assert_eq!(blob.data, b"abc");
+    assert!(core::ptr::eq(blob.data, &data[5..8]));
+    assert_eq!(blob.name, "foo");
+    assert_eq!(blob.suffix, Some("hi"));
+    assert_eq!(blob.owned.into_string(), "owned");
+    assert_eq!(*blob.ptr, 0x03);
+}
+
+#[test]
+fn borrowed_args_and_enums() {
+    #[derive(BinReadBorrowed, Debug, PartialEq)]
+    #[br(import { len: usize })]
+    struct Chunk<'a> {
+        #[br(count = len)]
+        data: &'a [u8],
+    }
+
+    #[derive(BinReadBorrowed, Debug, PartialEq)]
+    enum Record<'a> {
+        #[br(magic = 0u8)]
+        Text(&'a str),
+        #[br(magic = 1u8)]
+        Chunk {
+            len: u8,
+            #[br(args { len: len as usize })]
+            chunk: Chunk<'a>,
+        },
+    }
+
+    let mut reader = SliceReader::new(b"\x01\x02ab\0text\0");
+    assert_eq!(
+        reader.read_be::<Record<'_>>().unwrap(),
+        Record::Chunk {
+            len: 2,
+            chunk: Chunk { data: b"ab" }
+        }
+    );
+    assert_eq!(
+        reader.read_be::<Record<'_>>().unwrap(),
+        Record::Text("text")
+    );
+}
+
+#[test]
+fn borrowed_errors() {
+    #[derive(BinReadBorrowed, Debug)]
+    struct Test<'a> {
+        #[br(count = 2)]
+        text: &'a str,
+    }
+
+    let mut reader = SliceReader::new(b"\xFF\xFF");
+    let error = reader.read_be::<Test<'_>>().unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        Error::BadEncoding {
+            pos: 0,
+            encoding: "UTF-8"
+        }
+    ));
+    assert_eq!(reader.position(), 0);
+
+    let error = SliceReader::new(b"\0").read_be::<Test<'_>>().unwrap_err();
+    assert!(matches!(error.root_cause(), Error::Io(_)));
+
+    let error = SliceReader::new(b"no null").read_be::<&str>().unwrap_err();
+    assert!(matches!(error, Error::Io(_)));
+}
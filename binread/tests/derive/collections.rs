@@ -0,0 +1,61 @@
+use binread::{
+    collections::DuplicateKeys, io::Cursor, BinRead, BinReaderExt, BinWrite, BinWriterExt, Error,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+#[test]
+fn collections_round_trip() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        len: u8,
+        #[br(count = len)]
+        name: String,
+        #[br(count = 2)]
+        values: VecDeque<u16>,
+        #[br(count = 2)]
+        offsets: BTreeMap<u8, u16>,
+        #[br(count = 3)]
+        tags: BTreeSet<u8>,
+    }
+
+    let data = b"\x02hi\x00\x01\x00\x02\x05\x00\x50\x03\x00\x30\x09\x07\x08";
+    let test: Test = Cursor::new(data).read_be().unwrap();
+    assert_eq!(test.name, "hi");
+    assert_eq!(test.values, [1, 2]);
+    assert_eq!(test.offsets[&3], 0x30);
+    assert_eq!(test.tags.iter().copied().collect::<Vec<_>>(), [7, 8, 9]);
+
+    // Ordered collections are written sorted
+    let mut writer = Cursor::new(Vec::new());
+    writer.write_be(&test).unwrap();
+    assert_eq!(
+        writer.into_inner(),
+        b"\x02hi\x00\x01\x00\x02\x03\x00\x30\x05\x00\x50\x07\x08\x09"
+    );
+}
+
+#[test]
+fn collections_duplicate_keys() {
+    #[derive(BinRead, Debug)]
+    struct Strict {
+        #[br(count = 2)]
+        map: HashMap<u8, u8>,
+    }
+
+    #[derive(BinRead, Debug)]
+    struct LastWins {
+        #[br(count = 2, args_tuple = DuplicateKeys::LastWins)]
+        map: HashMap<u8, u8>,
+    }
+
+    let data = b"\x01\x02\x01\x03";
+    let last: LastWins = Cursor::new(data).read_le().unwrap();
+    assert_eq!(last.map[&1], 3);
+
+    let error = Cursor::new(data).read_le::<Strict>().unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        Error::AssertFail { pos: 2, .. }
+    ));
+}
@@ -88,7 +88,10 @@ fn enum_return_all_errors() {
                 binread::Error::BadMagic { .. }
             ));
             assert_eq!(variant_errors[1].0, "Two");
-            assert!(matches!(variant_errors[1].1, binread::Error::Io(..)));
+            assert!(matches!(
+                variant_errors[1].1.root_cause(),
+                binread::Error::Io(..)
+            ));
         }
         _ => panic!("wrong error type"),
     }
@@ -0,0 +1,251 @@
+use binread::{
+    file_ptr::{
+        with_shared_cache, Max, NullableFilePtr32, Relative, RelativeFilePtr, RelativeFilePtr16,
+    },
+    io::Cursor,
+    BinRead, BinReaderExt, BinResult, Error, LazyFilePtr, NullString, NullableFilePtr,
+    SharedFilePtr,
+};
+use std::sync::Arc;
+
+#[test]
+fn lazy_file_ptr() {
+    #[derive(BinRead, Debug)]
+    #[br(import(len: u8))]
+    struct Payload {
+        #[br(count = len)]
+        data: Vec<u8>,
+    }
+
+    #[derive(BinRead, Debug)]
+    #[br(big)]
+    struct Entry {
+        len: u8,
+        #[br(args(len), offset = 2)]
+        payload: LazyFilePtr<u16, Payload>,
+    }
+
+    #[derive(BinRead, Debug)]
+    struct Directory {
+        count: u8,
+        #[br(count = count)]
+        entries: Vec<Entry>,
+        #[br(count = 2)]
+        extra: LazyFilePtr<u8, Vec<u16>>,
+    }
+
+    let data = b"\x02\x02\x00\x08\x03\x00\x0A\x40xxab\xFF\x01\x02\x03";
+    let mut reader = Cursor::new(data);
+    let mut directory: Directory = reader.read_be().unwrap();
+    assert_eq!(reader.position(), 8);
+    assert!(directory.entries[0].payload.value().is_none());
+
+    let payload = directory.entries[1].payload.get(&mut reader).unwrap();
+    assert_eq!(payload.data, [0xFF, 1, 2]);
+    payload.data.clear();
+    assert!(directory.entries[1]
+        .payload
+        .get(&mut reader)
+        .unwrap()
+        .data
+        .is_empty());
+    assert_eq!(
+        directory.entries[1]
+            .payload
+            .read_value(&mut reader)
+            .unwrap()
+            .data,
+        [0xFF, 1, 2]
+    );
+    assert_eq!(
+        directory.entries[0]
+            .payload
+            .read_value(&mut reader)
+            .unwrap()
+            .data,
+        b"ab"
+    );
+    assert_eq!(reader.position(), 8);
+
+    // Errors do not move the reader either
+    let error = directory.extra.read_value(&mut reader).unwrap_err();
+    assert!(matches!(error.root_cause(), Error::Io(_)));
+    assert_eq!(reader.position(), 8);
+}
+
+#[test]
+fn relative_file_ptr() {
+    #[derive(BinRead, Debug)]
+    #[br(big)]
+    struct Test {
+        #[br(count = 2)]
+        values: Vec<RelativeFilePtr16<u8>>,
+        #[br(offset = 1)]
+        unsigned: RelativeFilePtr<u8, u8>,
+        lazy: LazyFilePtr<Relative<i8>, u8>,
+    }
+
+    let data = b"\x00\x07\xFF\xFE\x02\xFB\xAA\xBB\xCC";
+    let mut reader = Cursor::new(data);
+    let mut test: Test = reader.read_be().unwrap();
+    assert_eq!(*test.values[0], 0xBB);
+    assert_eq!(test.values[1].ptr, Relative { ptr: -2, pos: 2 });
+    assert_eq!(*test.values[1], 0x00);
+    assert_eq!(*test.unsigned, 0xBB);
+    assert_eq!(*test.lazy.get(&mut reader).unwrap(), 0x00);
+
+    // Pointing before the start of the reader is an error
+    let error = Cursor::new(b"\x00\x07\xFF\xF0\x02\xFB\xAA\xBB")
+        .read_be::<Test>()
+        .unwrap_err();
+    assert!(matches!(error.root_cause(), Error::Io(_)));
+}
+
+#[test]
+fn nullable_file_ptr() {
+    #[derive(BinRead, Debug)]
+    #[br(little)]
+    struct Test {
+        present: NullableFilePtr32<u8>,
+        absent: NullableFilePtr32<u8>,
+        #[br(count = 2)]
+        all_ones: NullableFilePtr<i16, Vec<u8>, Max>,
+        zero: NullableFilePtr<u8, u8, Max>,
+        relative: NullableFilePtr<Relative<i8>, u8>,
+        relative_null: NullableFilePtr<Relative<i8>, u8>,
+    }
+
+    let data = b"\x0D\0\0\0\0\0\0\0\xFF\xFF\0\xFE\0\x05";
+    let test: Test = Cursor::new(data).read_le().unwrap();
+    assert_eq!(*test.present, Some(5));
+    assert!(test.absent.is_null());
+    assert_eq!(test.absent.value, None);
+    assert_eq!(test.all_ones.into_inner(), None);
+    assert_eq!(*test.zero, Some(13));
+    assert_eq!(*test.relative, Some(0xFF));
+    assert!(test.relative_null.is_none());
+}
+
+#[test]
+fn shared_file_ptr() {
+    #[derive(BinRead, Debug)]
+    struct Pair {
+        first: SharedFilePtr<u8, u8>,
+        second: SharedFilePtr<u8, u8>,
+    }
+
+    #[derive(BinRead, Debug)]
+    struct Test {
+        a: SharedFilePtr<u8, Pair>,
+        b: SharedFilePtr<u8, Pair>,
+        // The same position as a different type is not shared
+        c: SharedFilePtr<u8, u16>,
+    }
+
+    let data = b"\x03\x03\x03\x05\x05\x07";
+    let test: Test = Cursor::new(data).read_be().unwrap();
+    assert!(Arc::ptr_eq(
+        test.a.first.value.as_ref().unwrap(),
+        test.a.second.value.as_ref().unwrap()
+    ));
+    assert!(Arc::ptr_eq(
+        test.a.value.as_ref().unwrap(),
+        test.b.value.as_ref().unwrap()
+    ));
+    assert_eq!(*test.a.first, 7);
+    assert_eq!(*test.c, 0x0505);
+
+    // Separate reads only share values inside `with_shared_cache`
+    let mut reader = Cursor::new(data);
+    let a: SharedFilePtr<u8, Pair> = reader.read_be().unwrap();
+    let b: SharedFilePtr<u8, Pair> = reader.read_be().unwrap();
+    assert!(!Arc::ptr_eq(
+        a.value.as_ref().unwrap(),
+        b.value.as_ref().unwrap()
+    ));
+
+    let mut reader = Cursor::new(data);
+    let (a, b) = with_shared_cache(|| -> BinResult<_> {
+        let a: SharedFilePtr<u8, Pair> = reader.read_be()?;
+        let b: SharedFilePtr<u8, Pair> = reader.read_be()?;
+        Ok((a, b))
+    })
+    .unwrap();
+    assert!(Arc::ptr_eq(
+        a.value.as_ref().unwrap(),
+        b.value.as_ref().unwrap()
+    ));
+}
+
+#[test]
+fn shared_file_ptr_vec() {
+    #[derive(BinRead, Debug)]
+    struct Test {
+        #[br(count = 3)]
+        names: Vec<SharedFilePtr<u8, NullString>>,
+    }
+
+    let test: Test = Cursor::new(b"\x03\x03\x03abc\0").read_be().unwrap();
+    assert_eq!(test.names[2].to_string(), "abc");
+    assert!(Arc::ptr_eq(
+        test.names[0].value.as_ref().unwrap(),
+        test.names[2].value.as_ref().unwrap()
+    ));
+}
+
+#[test]
+fn shared_file_ptr_conflict() {
+    #[derive(BinRead, Debug)]
+    struct Test {
+        #[br(count = 2)]
+        a: SharedFilePtr<u8, Vec<u8>>,
+        #[br(count = 1)]
+        b: SharedFilePtr<u8, Vec<u8>>,
+    }
+
+    let error = Cursor::new(b"\x02\x02\xAA\xBB")
+        .read_be::<Test>()
+        .unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        Error::PointerConflict { pos: 2 }
+    ));
+}
+
+#[test]
+fn shared_file_ptr_args_conflict() {
+    #[derive(BinRead, Debug)]
+    #[br(import(scale: u8))]
+    struct Scaled {
+        #[br(map = |x: u8| x * scale)]
+        value: u8,
+    }
+
+    #[derive(BinRead, Debug)]
+    struct Test {
+        #[br(args(1))]
+        a: SharedFilePtr<u8, Scaled>,
+        #[br(args(2))]
+        b: SharedFilePtr<u8, Scaled>,
+    }
+
+    let error = Cursor::new(b"\x02\x02\x05").read_be::<Test>().unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        Error::PointerConflict { pos: 2 }
+    ));
+}
+
+#[test]
+fn shared_file_ptr_cycle() {
+    #[derive(BinRead, Debug)]
+    struct Node {
+        value: u8,
+        next: NullableFilePtr<u8, SharedFilePtr<u8, Node>>,
+    }
+
+    let error = Cursor::new(b"\x02\x00\x0A\x04\x02")
+        .read_be::<SharedFilePtr<u8, Node>>()
+        .unwrap_err();
+    assert!(matches!(error.root_cause(), Error::PointerCycle { pos: 2 }));
+}
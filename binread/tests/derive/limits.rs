@@ -0,0 +1,169 @@
+use binread::{
+    file_ptr::{FilePtr8, NullableFilePtr8},
+    helpers::until_eof,
+    io::Cursor,
+    BinRead, BinReaderExt, Endian, Error, Limits, NullString, PrefixedString, ReadOptions,
+};
+use std::fmt::Debug;
+
+/// Reads `T` and returns the limit, maximum and position of the limit error
+fn limit_error<T>(data: &[u8], limits: Limits) -> (&'static str, u64, u64)
+where
+    T: for<'a> BinRead<Args<'a> = ()> + Debug,
+{
+    let error = Cursor::new(data)
+        .read_with_limits::<T>(Endian::Big, limits, ())
+        .unwrap_err();
+    match *error.root_cause() {
+        Error::LimitExceeded { pos, limit, max } => (limit, max, pos),
+        ref error => panic!("unexpected error {:?}", error),
+    }
+}
+
+#[test]
+fn limit_count() {
+    #[derive(BinRead, Debug)]
+    struct Packet {
+        len: u32,
+        #[br(count = len)]
+        data: Vec<u16>,
+    }
+
+    let limits = Limits::new().max_count(4);
+    let packet: Packet = Cursor::new(b"\0\0\0\x04\0\x01\0\x02\0\x03\0\x04")
+        .read_with_limits(Endian::Big, limits, ())
+        .unwrap();
+    assert_eq!(packet.data, [1, 2, 3, 4]);
+
+    let data = b"\xFF\xFF\xFF\xFF\0\x01";
+    assert_eq!(limit_error::<Packet>(data, limits), ("max_count", 4, 4));
+
+    let error = Cursor::new(data)
+        .read_with_limits::<Packet>(Endian::Big, limits, ())
+        .unwrap_err();
+    let report = error.report(data).to_string();
+    assert!(report.starts_with("Packet.data @ 0x4: max_count exceeded at 0x4"));
+    assert!(report.contains("max_count: 4"));
+
+    // Each element of a `Vec<u16>` takes two bytes
+    assert_eq!(
+        limit_error::<Packet>(b"\0\0\0\x04", Limits::new().max_alloc(6)),
+        ("max_alloc", 6, 4)
+    );
+}
+
+#[test]
+fn limit_until_eof() {
+    #[derive(BinRead, Debug)]
+    struct Rest {
+        #[br(parse_with = until_eof)]
+        data: Vec<u8>,
+    }
+
+    let limits = Limits::new().max_count(3);
+    let rest: Rest = Cursor::new(b"abc")
+        .read_with_limits(Endian::Big, limits, ())
+        .unwrap();
+    assert_eq!(rest.data, b"abc");
+    assert_eq!(limit_error::<Rest>(b"abcde", limits), ("max_count", 3, 4));
+}
+
+#[test]
+fn limit_strings() {
+    let limits = Limits::new().max_count(3);
+    let string: NullString = Cursor::new(b"abc\0")
+        .read_with_limits(Endian::Big, limits, ())
+        .unwrap();
+    assert_eq!(string.to_string(), "abc");
+    assert_eq!(
+        limit_error::<NullString>(b"abcd\0", limits),
+        ("max_count", 3, 4)
+    );
+
+    let mut reader = Cursor::new(b"\x04abcd");
+    let error = reader
+        .read_with_limits::<PrefixedString<u8>>(Endian::Big, limits, ())
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::LimitExceeded {
+            pos: 0,
+            limit: "max_count",
+            max: 3
+        }
+    ));
+    assert_eq!(reader.position(), 0);
+}
+
+#[test]
+fn limit_pointer_depth() {
+    #[derive(BinRead, Debug)]
+    struct Node {
+        next: NullableFilePtr8<Box<Node>>,
+    }
+
+    let data = b"\x01\x02\x00";
+    let node: Node = Cursor::new(data)
+        .read_with_limits(Endian::Big, Limits::new().max_depth(2), ())
+        .unwrap();
+    assert!(node
+        .next
+        .as_ref()
+        .unwrap()
+        .next
+        .as_ref()
+        .unwrap()
+        .next
+        .is_null());
+    assert_eq!(
+        limit_error::<Node>(data, Limits::new().max_depth(1)),
+        ("max_depth", 1, 2)
+    );
+
+    // A pointer to itself would otherwise be followed until the stack overflows
+    #[derive(BinRead, Debug)]
+    struct Cycle {
+        next: FilePtr8<Box<Cycle>>,
+    }
+
+    assert_eq!(
+        limit_error::<Cycle>(b"\x00", Limits::new().max_depth(8)),
+        ("max_depth", 8, 1)
+    );
+
+    // Which is also caught by the default limits
+    assert_eq!(
+        limit_error::<Cycle>(b"\x00", Limits::default()),
+        ("max_depth", u64::from(Limits::DEFAULT_MAX_DEPTH), 1)
+    );
+
+    // The depth is carried in the options for manual implementations
+    let mut options = ReadOptions::default();
+    options.limits = Limits::new().max_depth(1);
+    options.pointer_depth = 1;
+    let mut ptr = FilePtr8::<u8>::read_options(&mut Cursor::new(b"\x00"), &options, ()).unwrap();
+    let error = ptr
+        .after_parse(&mut Cursor::new(b"\x00"), &options, ())
+        .unwrap_err();
+    assert!(matches!(error, Error::LimitExceeded { pos: 0, .. }));
+}
+
+#[test]
+fn limit_bytes_read() {
+    #[derive(BinRead, Debug)]
+    struct Packet {
+        len: u32,
+        #[br(count = len)]
+        data: Vec<u8>,
+    }
+
+    let limits = Limits::new().max_bytes_read(8);
+    let packet: Packet = Cursor::new(b"\0\0\0\x04abcd")
+        .read_with_limits(Endian::Big, limits, ())
+        .unwrap();
+    assert_eq!(packet.data, b"abcd");
+    assert_eq!(
+        limit_error::<Packet>(b"\0\0\0\x10abcdefghijklmnop", limits),
+        ("max_bytes_read", 8, 8)
+    );
+}
@@ -0,0 +1,164 @@
+use binread::{
+    io::{Cursor, Read, Seek, Write},
+    BinRead, BinResult, BinWrite, NamedArgs, NullString, ReadOptions, WriteOptions,
+};
+
+#[derive(BinRead, BinWrite, Debug, PartialEq)]
+#[br(import { count: usize, add: u8 = 0 })]
+struct Inner {
+    #[br(count = count, map = |data: Vec<u8>| data.iter().map(|x| x + add).collect())]
+    #[br(write_map = |data: &Vec<u8>| data.iter().map(|x| x - add).collect::<Vec<_>>())]
+    data: Vec<u8>,
+}
+
+#[test]
+fn named_args() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    struct Test {
+        count: u8,
+        #[br(args { count: count as usize })]
+        default: Inner,
+        #[br(args { add: 1, count: count.into() })]
+        added: Inner,
+    }
+
+    let bytes = b"\x02\x01\x02\x01\x02";
+    let value = Test::read(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(
+        value,
+        Test {
+            count: 2,
+            default: Inner { data: vec![1, 2] },
+            added: Inner { data: vec![2, 3] },
+        }
+    );
+
+    let mut writer = Cursor::new(Vec::new());
+    value.write(&mut writer).unwrap();
+    assert_eq!(writer.into_inner(), bytes);
+}
+
+#[test]
+fn named_args_shorthand() {
+    #[derive(BinRead, Debug, PartialEq)]
+    struct Test {
+        #[br(map = |x: u8| x as usize)]
+        count: usize,
+        #[br(args { count })]
+        inner: Inner,
+    }
+
+    let value = Test::read(&mut Cursor::new(b"\x01\x05")).unwrap();
+    assert_eq!(value.inner, Inner { data: vec![5] });
+}
+
+#[test]
+fn named_args_borrowed() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[br(import { names: &[NullString], scale: u8 = 1 })]
+    struct Child {
+        #[br(count = names.len(), map = |data: Vec<u8>| data.iter().map(|x| x * scale).collect())]
+        #[br(write_map = |data: &Vec<u8>| data.iter().map(|x| x / scale).collect::<Vec<_>>())]
+        data: Vec<u8>,
+    }
+
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    struct Test {
+        #[br(count = 2)]
+        names: Vec<NullString>,
+        #[br(args { names: &names, scale: 2 })]
+        child: Child,
+    }
+
+    let bytes = b"a\0b\0\x01\x02";
+    let value = Test::read(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(value.child, Child { data: vec![2, 4] });
+
+    let mut writer = Cursor::new(Vec::new());
+    value.write(&mut writer).unwrap();
+    assert_eq!(writer.into_inner(), bytes);
+}
+
+#[test]
+fn named_args_builder() {
+    let args = InnerBinReadArgs::builder().count(1).finalize();
+    assert_eq!((args.count, args.add), (1, 0));
+
+    let args = <InnerBinReadArgs as NamedArgs>::builder()
+        .add(2)
+        .count(3)
+        .finalize();
+    assert_eq!((args.count, args.add), (3, 2));
+
+    let value = Inner::read_args(&mut Cursor::new(b"\x01\x02"), args).unwrap_err();
+    assert!(matches!(value.root_cause(), binread::Error::Io(..)));
+}
+
+#[test]
+fn named_args_all_defaults() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import { a: u8 = 1, b: u8 = 2 })]
+    struct Test {
+        #[br(calc = a + b)]
+        sum: u8,
+    }
+
+    assert_eq!(TestBinReadArgs::default().a, 1);
+    assert_eq!(Test::read(&mut Cursor::new(b"")).unwrap(), Test { sum: 3 });
+}
+
+#[test]
+fn named_args_custom_fns() {
+    #[derive(Clone, Copy)]
+    struct Args {
+        scale: u8,
+    }
+
+    impl NamedArgs for Args {
+        type Builder = ArgsBuilder;
+
+        fn builder() -> Self::Builder {
+            ArgsBuilder { scale: 1 }
+        }
+    }
+
+    struct ArgsBuilder {
+        scale: u8,
+    }
+
+    impl ArgsBuilder {
+        fn scale(self, scale: u8) -> Self {
+            Self { scale }
+        }
+
+        fn finalize(self) -> Args {
+            Args { scale: self.scale }
+        }
+    }
+
+    fn parse<R: Read + Seek>(reader: &mut R, options: &ReadOptions, args: Args) -> BinResult<u8> {
+        Ok(u8::read_options(reader, options, ())? * args.scale)
+    }
+
+    fn write<W: Write + Seek>(
+        value: &u8,
+        writer: &mut W,
+        options: &WriteOptions,
+        args: Args,
+    ) -> BinResult<()> {
+        (value / args.scale).write_options(writer, options, ())
+    }
+
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    struct Test {
+        #[br(parse_with = parse, write_with = write, args { scale: 3 })]
+        value: u8,
+    }
+
+    let value = Test::read(&mut Cursor::new(b"\x02")).unwrap();
+    assert_eq!(value, Test { value: 6 });
+
+    let mut writer = Cursor::new(Vec::new());
+    value.write(&mut writer).unwrap();
+    assert_eq!(writer.into_inner(), b"\x02");
+}
@@ -0,0 +1,117 @@
+use binread::{
+    io::{Cursor, Seek, SeekFrom},
+    BinRead, Error,
+};
+
+#[derive(BinRead, Debug)]
+#[br(big)]
+struct Inner {
+    #[br(magic = 0x1234u16)]
+    value: u8,
+}
+
+#[derive(BinRead, Debug)]
+#[br(big)]
+struct Header {
+    padding: [u8; 18],
+    #[br(magic = b"TEST")]
+    inner: Inner,
+}
+
+fn header_data() -> Vec<u8> {
+    let mut data = vec![0; 18];
+    data.extend_from_slice(b"TEST\x12\x35\x05");
+    data
+}
+
+#[test]
+fn report_bad_magic_bytes() {
+    let mut data = header_data();
+    data[20] = b'X';
+    let error = Header::read(&mut Cursor::new(&data)).unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        Error::BadMagic { expected, found, .. }
+            if expected.downcast_ref::<[u8; 4]>() == Some(b"TEST")
+                && found.downcast_ref::<[u8; 4]>() == Some(b"TEXT")
+    ));
+    assert_eq!(
+        error.report(&data).to_string(),
+        "bad magic at 0x12\n  \
+         expected: b\"TEST\"\n  \
+         found:    b\"TEXT\"\n\
+         \n  \
+         00000000  00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00  |................|\n\
+         > 00000010  00 00 54 45 58 54 12 35 05                       |..TEXT.5.|\n            \
+         \x20     ^^ ^^ ^^ ^^"
+    );
+}
+
+#[test]
+fn report_nested_magic_from_reader() {
+    let data = header_data();
+    let mut reader = Cursor::new(&data);
+    let error = Header::read(&mut reader).unwrap_err();
+    reader.seek(SeekFrom::Start(3)).unwrap();
+    let report = error.report_from(&mut reader).unwrap().to_string();
+    assert_eq!(reader.position(), 3);
+    assert!(report.starts_with("Header.inner @ 0x16: bad magic at 0x16\n"));
+    assert!(report.contains("\n  expected: 0x1234 (u16)\n  found:    0x1235 (u16)\n"));
+    assert!(report.contains("\n> 00000010  00 00 54 45 53 54 12 35 05"));
+    assert!(report.ends_with("^^ ^^"));
+}
+
+#[test]
+fn report_enum_errors() {
+    #[derive(BinRead, Debug)]
+    #[br(big)]
+    enum Command {
+        #[br(magic = 1u8)]
+        Set(#[br(assert(self_0 == 2, "bad value {}", self_0))] u8),
+        #[br(magic = 2u8)]
+        Clear,
+        Other(u32),
+    }
+
+    let data = b"\x01\x03";
+    let error = Command::read(&mut Cursor::new(data)).unwrap_err();
+    let report = error.report(data).to_string();
+    assert!(report.starts_with(
+        "no variant matched at 0x0\n  \
+         Set: assertion failed at 0x0\n    \
+         message: bad value 3\n  \
+         Clear: bad magic at 0x0\n    \
+         expected: 0x02 (u8)\n    \
+         found:    0x01 (u8)\n  \
+         Other: Command::Other.0 @ 0x0: io error: UnexpectedEof\n"
+    ));
+    assert!(report.ends_with(
+        "> 00000000  01 03                                            |..|\n            ^^"
+    ));
+}
+
+#[test]
+fn report_without_position() {
+    let error = Error::Io(binread::io::ErrorKind::UnexpectedEof.into());
+    assert_eq!(
+        error.report(b"").to_string(),
+        "io error: UnexpectedEof\n  unexpected end of file"
+    );
+}
+
+#[test]
+fn report_at_end_of_positions() {
+    let error = Error::AssertFail {
+        pos: u64::MAX - 1,
+        message: "too far".into(),
+    };
+    assert!(error
+        .report(b"abc")
+        .to_string()
+        .ends_with("no data at 0xFFFFFFFFFFFFFFFE"));
+    assert!(error
+        .report_from(&mut Cursor::new(b"abc"))
+        .unwrap()
+        .to_string()
+        .ends_with("no data at 0xFFFFFFFFFFFFFFFE"));
+}
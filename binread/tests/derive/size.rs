@@ -0,0 +1,114 @@
+use binread::{
+    helpers::until_eof, io::Cursor, io::SliceReader, BinRead, BinReadBorrowed, BinReaderExt,
+    BinWrite, BinWriterExt, Error, NullString,
+};
+
+#[test]
+fn size_skips_remaining_bytes() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[br(big)]
+    struct Chunk {
+        len: u8,
+        #[br(size = len)]
+        name: NullString,
+        value: u8,
+    }
+
+    let data = b"\x05ab\0xx\x07";
+    let chunk: Chunk = Cursor::new(data).read_be().unwrap();
+    assert_eq!(chunk.name.to_string(), "ab");
+    assert_eq!(chunk.value, 7);
+
+    let mut writer = Cursor::new(Vec::new());
+    writer.write_be(&chunk).unwrap();
+    assert_eq!(writer.into_inner(), b"\x05ab\0\0\0\x07");
+
+    // The field can not read past its size
+    #[derive(BinRead, Debug)]
+    struct Short {
+        #[br(size = 2)]
+        value: u32,
+    }
+
+    let error = Cursor::new(b"\0\0\0\x01").read_be::<Short>().unwrap_err();
+    assert!(matches!(error.root_cause(), Error::Io(_)));
+}
+
+#[test]
+fn size_nested_chunks() {
+    #[derive(BinRead, Debug, PartialEq)]
+    struct Node {
+        len: u8,
+        #[br(size = len, parse_with = until_eof)]
+        children: Vec<Node>,
+    }
+
+    let leaf = || Node {
+        len: 0,
+        children: Vec::new(),
+    };
+
+    let mut reader = Cursor::new(b"\x04\0\x02\0\0\x09");
+    let node: Node = reader.read_be().unwrap();
+    assert_eq!(reader.position(), 5);
+    assert_eq!(
+        node,
+        Node {
+            len: 4,
+            children: vec![
+                leaf(),
+                Node {
+                    len: 2,
+                    children: vec![leaf(), leaf()],
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn size_exact() {
+    #[derive(BinRead, BinWrite, Debug)]
+    struct Chunk {
+        len: u8,
+        #[br(size_exact = len)]
+        name: NullString,
+    }
+
+    let chunk: Chunk = Cursor::new(b"\x03ab\0").read_le().unwrap();
+    assert_eq!(chunk.name.to_string(), "ab");
+
+    let error = Cursor::new(b"\x04ab\0\0").read_le::<Chunk>().unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        Error::AssertFail { pos: 1, .. }
+    ));
+
+    let chunk = Chunk {
+        len: 2,
+        name: NullString(b"abc".to_vec()),
+    };
+    let error = Cursor::new(Vec::new()).write_le(&chunk).unwrap_err();
+    assert!(matches!(error, Error::AssertFail { pos: 1, .. }));
+}
+
+#[test]
+fn size_borrowed() {
+    #[derive(BinReadBorrowed, Debug)]
+    struct Chunk<'a> {
+        len: u8,
+        #[br(size = len)]
+        name: &'a str,
+        value: u8,
+    }
+
+    let chunk: Chunk = SliceReader::new(b"\x04ab\0x\x07").read_le().unwrap();
+    assert_eq!(chunk.name, "ab");
+    assert_eq!(chunk.value, 7);
+
+    // Without a null in its size the string is missing its terminator
+    let error = SliceReader::new(b"\x02abc\0\x07")
+        .read_le::<Chunk<'_>>()
+        .unwrap_err();
+    assert!(matches!(error.root_cause(), Error::Io(_)));
+}
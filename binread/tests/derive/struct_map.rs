@@ -67,7 +67,7 @@ fn try_map_field() {
     assert_eq!(result.a, -1);
     let error = Test::read(&mut Cursor::new(b"\x7f\0\0\0")).expect_err("accepted bad data");
     assert!(matches!(
-        error,
+        error.root_cause(),
         binread::error::Error::Custom { pos: 0, .. }
     ));
     error
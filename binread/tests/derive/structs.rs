@@ -1,7 +1,7 @@
 use binread::{
     derive_binread,
     io::{Cursor, Read, Seek, SeekFrom},
-    BinRead, BinResult, FilePtr, NullString, ReadOptions,
+    BinRead, BinReaderExt, BinResult, Error, FilePtr, NullString, ReadOptions,
 };
 
 #[test]
@@ -114,6 +114,39 @@ fn assert_formatted() {
     }
 }
 
+#[test]
+fn borrowed_args() {
+    #[derive(BinRead, Debug)]
+    #[br(import(offsets: &[u8], header: &'a Header))]
+    struct Child {
+        #[br(calc = offsets.iter().sum::<u8>() + header.base)]
+        sum: u8,
+        #[br(count = offsets.len())]
+        data: Vec<u8>,
+    }
+
+    #[derive(BinRead, Debug)]
+    struct Header {
+        base: u8,
+    }
+
+    #[derive(BinRead, Debug)]
+    struct Test {
+        header: Header,
+        offsets: [u8; 2],
+        #[br(args(&offsets, &header))]
+        child: Child,
+        #[br(offset = header.base.into(), args(&offsets[..1], &header))]
+        ptr: FilePtr<u8, Child>,
+    }
+
+    let result = Test::read(&mut Cursor::new(b"\x01\x02\x03\xAA\xBB\x05\xCC")).unwrap();
+    assert_eq!(result.child.sum, 6);
+    assert_eq!(result.child.data, [0xAA, 0xBB]);
+    assert_eq!(result.ptr.sum, 3);
+    assert_eq!(result.ptr.data, [0xCC]);
+}
+
 #[test]
 fn calc_temp_field() {
     #[derive_binread]
@@ -400,3 +433,62 @@ fn tuple_calc_temp_field() {
     // compilation would fail if it weren’t due to missing a second item
     assert_eq!(result, Test(5u32));
 }
+
+#[test]
+fn endian_from() {
+    #[derive(BinRead, Debug, PartialEq)]
+    struct Entry(u16);
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(endian_from = "bom", big = 0xFEFF, little = 0xFFFE)]
+    struct Header {
+        version: u8,
+        #[br(big)]
+        bom: u16,
+        count: u16,
+        #[br(count = count)]
+        entries: Vec<Entry>,
+        #[br(big)]
+        tail: u16,
+    }
+
+    let header: Header = Cursor::new(b"\x01\xFE\xFF\0\x01\0\x02\0\x03")
+        .read_le()
+        .unwrap();
+    assert_eq!(header.count, 1);
+    assert_eq!(header.entries, [Entry(2)]);
+    assert_eq!(header.tail, 3);
+
+    let header: Header = Cursor::new(b"\x01\xFF\xFE\x01\0\x02\0\0\x03")
+        .read_be()
+        .unwrap();
+    assert_eq!(header.count, 1);
+    assert_eq!(header.entries, [Entry(2)]);
+    assert_eq!(header.tail, 3);
+
+    let error = Cursor::new(b"\x01\x12\x34\0\x01\0\x02\0\x03")
+        .read_le::<Header>()
+        .unwrap_err();
+    match error.root_cause() {
+        Error::AssertFail { pos, message } => {
+            assert_eq!(*pos, 1);
+            assert_eq!(message, "unknown byte order marker 4660");
+        }
+        error => panic!("unexpected error {:?}", error),
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(endian_from(order, little = b"II", big = b"MM"))]
+    struct Tiff {
+        order: [u8; 2],
+        version: u16,
+        offset: u32,
+    }
+
+    let tiff: Tiff = Cursor::new(b"MM\0\x2A\0\0\0\x08").read_le().unwrap();
+    assert_eq!(tiff.version, 42);
+    assert_eq!(tiff.offset, 8);
+    let tiff: Tiff = Cursor::new(b"II\x2A\0\x08\0\0\0").read_be().unwrap();
+    assert_eq!(tiff.version, 42);
+    assert_eq!(tiff.offset, 8);
+}
@@ -0,0 +1,314 @@
+use binread::{
+    io::{Cursor, Read, Seek, SeekFrom, Write},
+    BinRead, BinReaderExt, BinResult, BinWrite, BinWriterExt, NullString, ReadOptions,
+    WriteOptions,
+};
+
+fn to_bytes<T: for<'a> BinWrite<Args<'a> = ()>>(value: &T) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new());
+    value.write(&mut writer).unwrap();
+    writer.into_inner()
+}
+
+#[test]
+fn write_struct() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[br(big, magic = b"TEST")]
+    struct Test {
+        len: u16,
+        #[br(little)]
+        value: u32,
+        #[br(count = len)]
+        data: Vec<u8>,
+        name: NullString,
+    }
+
+    let bytes = b"TEST\0\x02\x01\0\0\0\xAA\xBBhi\0";
+    let value = Test {
+        len: 2,
+        value: 1,
+        data: vec![0xAA, 0xBB],
+        name: NullString(b"hi".to_vec()),
+    };
+
+    assert_eq!(to_bytes(&value), bytes);
+    assert_eq!(Cursor::new(bytes).read_ne::<Test>().unwrap(), value);
+}
+
+#[test]
+fn write_count_mismatch() {
+    #[derive(BinRead, BinWrite, Debug)]
+    struct Test {
+        len: u8,
+        #[br(count = len)]
+        data: Vec<u8>,
+    }
+
+    let mut writer = Cursor::new(Vec::new());
+    let error = writer
+        .write_be(&Test {
+            len: 2,
+            data: vec![1, 2, 3],
+        })
+        .unwrap_err();
+    assert!(matches!(error, binread::Error::AssertFail { pos: 1, .. }));
+}
+
+#[test]
+fn write_char() {
+    assert_eq!(to_bytes(&'\u{E9}'), b"\xE9");
+
+    let mut writer = Cursor::new(Vec::new());
+    writer.write_all(b"ab").unwrap();
+    let error = writer.write_be(&'\u{20AC}').unwrap_err();
+    assert!(matches!(error, binread::Error::AssertFail { pos: 2, .. }));
+}
+
+#[test]
+fn write_tuple_struct_endian() {
+    #[derive(BinWrite)]
+    struct Test(u16, #[br(big)] u16);
+
+    let mut writer = Cursor::new(Vec::new());
+    writer.write_le(&Test(1, 2)).unwrap();
+    assert_eq!(writer.into_inner(), b"\x01\0\0\x02");
+}
+
+#[test]
+fn write_skips_generated_values() {
+    #[derive(BinWrite)]
+    struct Test {
+        a: u8,
+        #[br(calc = a + 1)]
+        b: u8,
+        #[br(default)]
+        c: u8,
+        #[br(ignore)]
+        d: Option<u8>,
+    }
+
+    let value = Test {
+        a: 1,
+        b: 2,
+        c: 3,
+        d: Some(4),
+    };
+    assert_eq!(to_bytes(&value), b"\x01");
+}
+
+#[test]
+fn write_condition_and_args() {
+    #[derive(BinWrite)]
+    #[br(import(add: u8))]
+    struct Inner {
+        #[br(write_map = |x: &u8| x + add)]
+        value: u8,
+    }
+
+    #[derive(BinWrite)]
+    struct Test {
+        flag: u8,
+        #[br(if(flag != 0), args(flag))]
+        inner: Option<Inner>,
+        #[br(is_big = flag == 0)]
+        end: u16,
+    }
+
+    let value = Test {
+        flag: 1,
+        inner: Some(Inner { value: 2 }),
+        end: 1,
+    };
+    assert_eq!(to_bytes(&value), b"\x01\x03\x01\0");
+
+    let value = Test {
+        flag: 0,
+        inner: Some(Inner { value: 2 }),
+        end: 1,
+    };
+    assert_eq!(to_bytes(&value), b"\0\0\x01");
+}
+
+#[test]
+fn write_padding() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    struct Test {
+        #[br(pad_before = 1, pad_after = 2)]
+        a: u8,
+        #[br(align_before = 8)]
+        b: u8,
+        #[br(pad_size_to = 4)]
+        c: u8,
+        #[br(align_after = 4, magic = b'M')]
+        d: u8,
+        #[br(restore_position)]
+        e: u8,
+        f: u8,
+    }
+
+    let value = Test {
+        a: 1,
+        b: 2,
+        c: 3,
+        d: 4,
+        e: 5,
+        f: 5,
+    };
+    let bytes = b"\0\x01\0\0\0\0\0\0\x02\x03\0\0\0M\x04\0\x05";
+    assert_eq!(to_bytes(&value), bytes);
+    assert_eq!(Test::read(&mut Cursor::new(bytes)).unwrap(), value);
+}
+
+#[test]
+fn write_seek_before() {
+    #[derive(BinWrite)]
+    struct Test {
+        #[br(seek_before = SeekFrom::Start(2))]
+        a: u8,
+    }
+
+    let mut writer = Cursor::new(vec![0xFF; 4]);
+    writer.write_ne(&Test { a: 1 }).unwrap();
+    assert_eq!(writer.into_inner(), b"\xFF\xFF\x01\xFF");
+}
+
+#[test]
+fn write_map_and_write_with() {
+    fn read_offsets<R: Read + Seek>(
+        reader: &mut R,
+        options: &ReadOptions,
+        _: (),
+    ) -> BinResult<(u16, u16)> {
+        Ok((
+            u16::read_options(reader, options, ())?,
+            u16::read_options(reader, options, ())?,
+        ))
+    }
+
+    fn write_offsets<W: Write + Seek>(
+        value: &(u16, u16),
+        writer: &mut W,
+        options: &WriteOptions,
+        _: (),
+    ) -> BinResult<()> {
+        value.0.write_options(writer, options, ())?;
+        value.1.write_options(writer, options, ())
+    }
+
+    #[derive(BinRead, BinWrite)]
+    #[br(little)]
+    struct Test {
+        #[br(map = |x: u8| x.to_string(), write_map = |x: &String| x.parse::<u8>().unwrap())]
+        a: String,
+        #[br(parse_with = read_offsets, write_with = write_offsets)]
+        b: (u16, u16),
+    }
+
+    let value = Test {
+        a: "7".into(),
+        b: (1, 2),
+    };
+    assert_eq!(to_bytes(&value), b"\x07\x01\0\x02\0");
+}
+
+#[test]
+fn write_top_level_map() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[br(map = |x: u8| Self(x.into()), write_map = |x: &Self| x.0 as u8)]
+    struct Test(u32);
+
+    assert_eq!(to_bytes(&Test(3)), b"\x03");
+    assert_eq!(Test::read(&mut Cursor::new(b"\x03")).unwrap(), Test(3));
+}
+
+#[test]
+fn write_enum() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[br(big)]
+    enum Test {
+        #[br(magic = 0u8)]
+        A { a: u16 },
+        #[br(magic = 1u8, little)]
+        B(u16, u8),
+        #[br(magic = 2u8)]
+        C,
+    }
+
+    for (value, bytes) in [
+        (Test::A { a: 1 }, &b"\0\0\x01"[..]),
+        (Test::B(1, 2), b"\x01\x01\0\x02"),
+        (Test::C, b"\x02"),
+    ] {
+        assert_eq!(to_bytes(&value), bytes);
+        assert_eq!(Test::read(&mut Cursor::new(bytes)).unwrap(), value);
+    }
+}
+
+#[test]
+fn write_unit_enum() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[br(big, repr = u16)]
+    enum Repr {
+        A = 1,
+        B = 0x100,
+    }
+
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    enum Magic {
+        #[br(magic = b"AA")]
+        A,
+        #[br(magic = b"BB")]
+        B,
+    }
+
+    assert_eq!(to_bytes(&Repr::A), b"\0\x01");
+    assert_eq!(to_bytes(&Repr::B), b"\x01\0");
+    assert_eq!(to_bytes(&Magic::A), b"AA");
+    assert_eq!(to_bytes(&Magic::B), b"BB");
+}
+
+#[test]
+fn write_generic() {
+    #[derive(BinWrite)]
+    struct Test<T: for<'a> BinWrite<Args<'a> = ()>> {
+        count: u8,
+        data: Vec<T>,
+    }
+
+    let value = Test {
+        count: 2,
+        data: vec![1u16, 2],
+    };
+    let mut writer = Cursor::new(Vec::new());
+    writer.write_be(&value).unwrap();
+    assert_eq!(writer.into_inner(), b"\x02\0\x01\0\x02");
+}
+
+#[test]
+fn write_endian_from() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[br(endian_from = "order", big = b"MM", little = b"II")]
+    struct Tiff {
+        order: [u8; 2],
+        version: u16,
+    }
+
+    let data = b"MM\0\x2AII\x2A\0";
+    let mut reader = Cursor::new(data);
+    let big: Tiff = reader.read_le().unwrap();
+    let little: Tiff = reader.read_be().unwrap();
+
+    let mut writer = Cursor::new(Vec::new());
+    writer.write_le(&big).unwrap();
+    writer.write_be(&little).unwrap();
+    assert_eq!(writer.into_inner(), data);
+
+    let mut writer = Cursor::new(Vec::new());
+    let error = writer
+        .write_le(&Tiff {
+            order: *b"XX",
+            version: 42,
+        })
+        .unwrap_err();
+    assert!(matches!(error, binread::Error::AssertFail { pos: 0, .. }));
+}
@@ -1,8 +1,21 @@
+// Many test types only exist to be parsed, so not every field is read back
+#![allow(dead_code, clippy::seek_from_current)]
+
 mod derive {
+    mod backtrace;
+    mod bits;
+    mod borrowed;
+    mod collections;
     mod enums;
+    mod file_ptr;
+    mod limits;
+    mod named_args;
+    mod report;
+    mod size;
     mod struct_generic;
     mod struct_map;
     mod structs;
     mod unit_enum;
     mod unit_struct;
+    mod write;
 }
@@ -1,12 +1,21 @@
 #[macro_use]
 pub(crate) mod sanitization;
+mod named_args;
 mod read_options;
+mod write_options;
 
-use crate::parser::{Input, ParseResult};
-use proc_macro2::TokenStream;
-use quote::quote;
+use read_options::Mode;
+
+use crate::parser::{BitOrder, CondEndian, Endian, Imports, Input, ParseResult, PassedArgs};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
 #[allow(clippy::wildcard_imports)]
 use sanitization::*;
+use syn::{GenericParam, Lifetime, LifetimeDef};
+
+/// The lifetime of the input of a `BinReadBorrowed` impl for a type without
+/// any lifetime parameters
+const INPUT_LIFETIME: &str = "'__binread_input";
 
 pub(crate) fn generate_impl(
     derive_input: &syn::DeriveInput,
@@ -15,26 +24,108 @@ pub(crate) fn generate_impl(
     // If there is a parsing error, a BinRead impl still needs to be
     // generated to avoid misleading errors at all call sites that use the
     // BinRead trait
-    let (arg_type, read_opt_impl) = match binread_input {
-        ParseResult::Ok(binread_input) => (
-            binread_input.imports().types(),
-            read_options::generate(&derive_input.ident, &binread_input),
-        ),
-        ParseResult::Partial(binread_input, error) => {
-            (binread_input.imports().types(), error.to_compile_error())
-        }
-        ParseResult::Err(error) => (quote! { () }, error.to_compile_error()),
+    let read_opt_impl = match binread_input {
+        ParseResult::Ok(binread_input) => {
+            read_options::generate(&derive_input.ident, binread_input, Mode::Sync)
+        }
+        ParseResult::Partial(_, error) | ParseResult::Err(error) => error.to_compile_error(),
     };
 
+    let ArgsType {
+        lifetime,
+        arg_type,
+        named_args,
+    } = get_args_type(derive_input, binread_input, "BinReadArgs");
+
     let name = &derive_input.ident;
     let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
     quote! {
+        #named_args
+
         #[allow(non_snake_case)]
         impl #impl_generics #TRAIT_NAME for #name #ty_generics #where_clause {
-            type Args = #arg_type;
+            type Args<#lifetime> = #arg_type;
 
             fn read_options<R: #READ_TRAIT + #SEEK_TRAIT>
-                (#READER: &mut R, #OPT: &#OPTIONS, #ARGS: Self::Args)
+                (#READER: &mut R, #OPT: &#OPTIONS, #ARGS: Self::Args<'_>)
+                -> #BIN_RESULT<Self>
+            {
+                #read_opt_impl
+            }
+        }
+    }
+}
+
+pub(crate) fn generate_async_impl(
+    derive_input: &syn::DeriveInput,
+    binread_input: &ParseResult<Input>,
+) -> TokenStream {
+    // The arguments type, including any named arguments struct, comes from
+    // the `BinRead` impl, so only the read function is generated here
+    let read_opt_impl = match binread_input {
+        ParseResult::Ok(binread_input) => {
+            read_options::generate(&derive_input.ident, binread_input, Mode::Async)
+        }
+        ParseResult::Partial(_, error) | ParseResult::Err(error) => error.to_compile_error(),
+    };
+
+    let name = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+    quote! {
+        #[allow(non_snake_case)]
+        impl #impl_generics #ASYNC_TRAIT_NAME for #name #ty_generics #where_clause {
+            async fn read_options_async<R: #ASYNC_READ_TRAIT + #ASYNC_SEEK_TRAIT + ::core::marker::Unpin>
+                (#READER: &mut R, #OPT: &#OPTIONS, #ARGS: Self::Args<'_>)
+                -> #BIN_RESULT<Self>
+            {
+                #read_opt_impl
+            }
+        }
+    }
+}
+
+pub(crate) fn generate_borrowed_impl(
+    derive_input: &syn::DeriveInput,
+    binread_input: &ParseResult<Input>,
+) -> TokenStream {
+    let read_opt_impl = match binread_input {
+        ParseResult::Ok(binread_input) => {
+            read_options::generate(&derive_input.ident, binread_input, Mode::Borrowed)
+        }
+        ParseResult::Partial(_, error) | ParseResult::Err(error) => error.to_compile_error(),
+    };
+
+    let ArgsType {
+        lifetime,
+        arg_type,
+        named_args,
+    } = get_args_type(derive_input, binread_input, "BinReadArgs");
+
+    // The first lifetime of the type is the one borrowing from the input
+    let mut generics = derive_input.generics.clone();
+    let input_lifetime = if let Some(param) = generics.lifetimes().next() {
+        param.lifetime.clone()
+    } else {
+        let lifetime = Lifetime::new(INPUT_LIFETIME, Span::call_site());
+        generics.params.insert(
+            0,
+            GenericParam::Lifetime(LifetimeDef::new(lifetime.clone())),
+        );
+        lifetime
+    };
+
+    let name = &derive_input.ident;
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = derive_input.generics.split_for_impl();
+    quote! {
+        #named_args
+
+        #[allow(non_snake_case)]
+        impl #impl_generics #BORROWED_TRAIT_NAME<#input_lifetime> for #name #ty_generics #where_clause {
+            type Args<#lifetime> = #arg_type;
+
+            fn read_options_borrowed
+                (#READER: &mut #SLICE_READER<#input_lifetime>, #OPT: &#OPTIONS, #ARGS: Self::Args<'_>)
                 -> #BIN_RESULT<Self>
             {
                 #read_opt_impl
@@ -42,3 +133,166 @@ pub(crate) fn generate_impl(
         }
     }
 }
+
+pub(crate) fn generate_write_impl(
+    derive_input: &syn::DeriveInput,
+    binread_input: &ParseResult<Input>,
+) -> TokenStream {
+    // As with `generate_impl`, an impl is always generated so that a parse
+    // error does not cascade into unrelated errors at use sites
+    let write_opt_impl = match binread_input {
+        ParseResult::Ok(binread_input) => write_options::generate(binread_input),
+        ParseResult::Partial(_, error) | ParseResult::Err(error) => error.to_compile_error(),
+    };
+
+    let ArgsType {
+        lifetime,
+        arg_type,
+        named_args,
+    } = get_args_type(derive_input, binread_input, "BinWriteArgs");
+
+    let name = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+    quote! {
+        #named_args
+
+        #[allow(non_snake_case, unused_variables)]
+        impl #impl_generics #WRITE_TRAIT_NAME for #name #ty_generics #where_clause {
+            type Args<#lifetime> = #arg_type;
+
+            fn write_options<W: #WRITE_TRAIT + #SEEK_TRAIT>
+                (&self, #WRITER: &mut W, #OPT: &#WRITE_OPTIONS, #ARGS: Self::Args<'_>)
+                -> #BIN_RESULT<()>
+            {
+                #write_opt_impl
+            }
+        }
+    }
+}
+
+struct ArgsType {
+    lifetime: Lifetime,
+    arg_type: TokenStream,
+    named_args: Option<TokenStream>,
+}
+
+fn get_args_type(
+    derive_input: &syn::DeriveInput,
+    binread_input: &ParseResult<Input>,
+    named_args_suffix: &str,
+) -> ArgsType {
+    let imports = match binread_input {
+        ParseResult::Ok(binread_input) | ParseResult::Partial(binread_input, _) => {
+            binread_input.imports()
+        }
+        ParseResult::Err(_) => {
+            return ArgsType {
+                lifetime: Imports::None.lifetime(),
+                arg_type: quote! { () },
+                named_args: None,
+            }
+        }
+    };
+
+    let lifetime = imports.lifetime();
+    let borrowed_lifetime = imports.is_borrowed().then_some(&lifetime);
+    let named_args_ident = format_ident!("{}{}", derive_input.ident, named_args_suffix);
+    let named_args = imports.named_fields().map(|fields| {
+        named_args::generate(
+            &derive_input.vis,
+            &named_args_ident,
+            fields,
+            borrowed_lifetime,
+        )
+    });
+
+    ArgsType {
+        arg_type: imports.types(&quote! { #named_args_ident<#borrowed_lifetime> }),
+        lifetime,
+        named_args,
+    }
+}
+
+fn get_bit_order(bit_order: BitOrder) -> TokenStream {
+    match bit_order {
+        BitOrder::Inherited | BitOrder::MsbFirst => quote! { #BIT_ORDER::Msb },
+        BitOrder::LsbFirst => quote! { #BIT_ORDER::Lsb },
+    }
+}
+
+/// Wraps `value`, which reads or writes the byte order marker of an
+/// `endian_from` directive, so that the fields after it use the byte order
+/// it selects. `stream_pos` must return a `BinResult` of the position.
+fn wrap_endian_from(
+    endian: &CondEndian,
+    value: TokenStream,
+    stream_pos: &TokenStream,
+    marker: &TokenStream,
+) -> TokenStream {
+    let CondEndian::Marker { big, little, .. } = endian else {
+        return value;
+    };
+
+    quote! {
+        let #MARKER_POS = #stream_pos?;
+        #value
+        let #OPT = &{
+            let mut #TEMP = *#OPT;
+            #TEMP.endian = #ENDIAN_FROM(#MARKER_POS, #marker, #big, #little)?;
+            #TEMP
+        };
+    }
+}
+
+fn get_endian(endian: &CondEndian) -> Option<TokenStream> {
+    Some(match endian {
+        CondEndian::Inherited | CondEndian::Marker { .. } => return None,
+        CondEndian::Fixed(Endian::Big) => quote! { #ENDIAN_ENUM::Big },
+        CondEndian::Fixed(Endian::Little) => quote! { #ENDIAN_ENUM::Little },
+        CondEndian::Cond(endian, condition) => {
+            let (true_cond, false_cond) = match endian {
+                Endian::Big => (
+                    quote! { #ENDIAN_ENUM::Big },
+                    quote! { #ENDIAN_ENUM::Little },
+                ),
+                Endian::Little => (
+                    quote! { #ENDIAN_ENUM::Little },
+                    quote! { #ENDIAN_ENUM::Big },
+                ),
+            };
+
+            quote! {
+                if (#condition) {
+                    #true_cond
+                } else {
+                    #false_cond
+                }
+            }
+        }
+    })
+}
+
+/// Generates the arguments passed to a field. `named_args_builder` is only
+/// called for named arguments, and returns an expression creating the builder
+/// for the field’s arguments type. When no arguments are given, the default
+/// arguments are used if `typed` is true, since the arguments type is then
+/// known, and `()` otherwise.
+fn get_passed_args(
+    args: &PassedArgs,
+    typed: bool,
+    named_args_builder: impl FnOnce() -> TokenStream,
+) -> TokenStream {
+    match args {
+        PassedArgs::List(list) => quote! { (#(#list,)*) },
+        PassedArgs::Named(fields) => {
+            let builder = named_args_builder();
+            let setters = fields
+                .iter()
+                .map(|(ident, value)| quote! { .#ident(#value) });
+            quote! { #builder #(#setters)*.finalize() }
+        }
+        PassedArgs::Tuple(tuple) => tuple.clone(),
+        PassedArgs::None if typed => quote! { #DEFAULT_ARGS::default_args() },
+        PassedArgs::None => quote! { () },
+    }
+}
@@ -0,0 +1,139 @@
+#[allow(clippy::wildcard_imports)]
+use crate::codegen::sanitization::*;
+use crate::parser::meta_types::IdentTypeMaybeDefault;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Ident, Lifetime, Visibility};
+
+/// Generates the arguments struct for named imports, along with a builder
+/// which tracks whether each required argument has been set in its type.
+/// `lifetime` is the lifetime of any borrowed arguments.
+pub(crate) fn generate(
+    vis: &Visibility,
+    name: &Ident,
+    fields: &[IdentTypeMaybeDefault],
+    lifetime: Option<&Lifetime>,
+) -> TokenStream {
+    let builder = format_ident!("{}Builder", name);
+    let lifetime = lifetime.into_iter().collect::<Vec<_>>();
+    let idents = fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+    let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+    let markers = fields
+        .iter()
+        .filter(|field| field.default.is_none())
+        .map(|field| make_ident(&field.ident, "marker"))
+        .collect::<Vec<_>>();
+
+    let setters = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let ty = &field.ty;
+        if field.default.is_some() {
+            quote! {
+                pub fn #ident(mut self, #ident: #ty) -> Self {
+                    self.#ident = Some(#ident);
+                    self
+                }
+            }
+        } else {
+            let marker = make_ident(ident, "marker");
+            let out_markers = markers.iter().map(|other| {
+                if *other == marker {
+                    quote! { #NAMED_ARGS_SATISFIED }
+                } else {
+                    quote! { #other }
+                }
+            });
+            let others = idents.iter().filter(|other| **other != ident);
+            quote! {
+                pub fn #ident(self, #ident: #ty) -> #builder<#(#lifetime,)* #(#out_markers),*> {
+                    #builder {
+                        #ident: Some(#ident),
+                        #(#others: self.#others,)*
+                        #MARKERS: core::marker::PhantomData,
+                    }
+                }
+            }
+        }
+    });
+
+    let finalize_fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        if let Some(default) = &field.default {
+            quote! { #ident: self.#ident.unwrap_or_else(|| #default) }
+        } else {
+            quote! { #ident: self.#ident.unwrap() }
+        }
+    });
+
+    let needed = markers.iter().map(|_| quote! { #NAMED_ARGS_NEEDED });
+    let satisfied = markers.iter().map(|_| quote! { #NAMED_ARGS_SATISFIED });
+    let needed_builder = quote! { #builder<#(#lifetime,)* #(#needed),*> };
+    let name_ty = quote! { #name<#(#lifetime),*> };
+
+    let default_impl = markers
+        .is_empty()
+        .then(|| generate_default_impl(&name_ty, &lifetime));
+
+    quote! {
+        #[derive(Clone)]
+        #vis struct #name_ty {
+            #(#vis #idents: #types,)*
+        }
+
+        #[allow(non_camel_case_types)]
+        #vis struct #builder<#(#lifetime,)* #(#markers),*> {
+            #(#idents: core::option::Option<#types>,)*
+            #MARKERS: core::marker::PhantomData<(#(#markers,)*)>,
+        }
+
+        impl<#(#lifetime),*> #name_ty {
+            pub fn builder() -> #needed_builder {
+                #builder {
+                    #(#idents: None,)*
+                    #MARKERS: core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl<#(#lifetime),*> #NAMED_ARGS for #name_ty {
+            type Builder = #needed_builder;
+
+            fn builder() -> Self::Builder {
+                Self::builder()
+            }
+        }
+
+        #default_impl
+
+        #[allow(non_camel_case_types)]
+        impl<#(#lifetime,)* #(#markers),*> #builder<#(#lifetime,)* #(#markers),*> {
+            #(#setters)*
+        }
+
+        impl<#(#lifetime),*> #builder<#(#lifetime,)* #(#satisfied),*> {
+            pub fn finalize(self) -> #name_ty {
+                #name {
+                    #(#finalize_fields,)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates the `Default` and `DefaultArgs` impls used when every argument
+/// has a default value.
+fn generate_default_impl(name_ty: &TokenStream, lifetime: &[&Lifetime]) -> TokenStream {
+    quote! {
+        impl<#(#lifetime),*> core::default::Default for #name_ty {
+            fn default() -> Self {
+                Self::builder().finalize()
+            }
+        }
+
+        impl<#(#lifetime),*> #DEFAULT_ARGS for #name_ty {
+            fn default_args() -> Self {
+                Self::builder().finalize()
+            }
+        }
+    }
+}
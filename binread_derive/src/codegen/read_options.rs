@@ -2,56 +2,157 @@ mod debug_template;
 mod r#enum;
 mod r#struct;
 
+use super::get_endian;
 #[allow(clippy::wildcard_imports)]
 use crate::codegen::sanitization::*;
-use crate::parser::{Assert, AssertionError, CondEndian, Endian, Input, Magic, Map};
+use crate::parser::{Assert, AssertionError, CondEndian, Input, Magic, Map, StructField};
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use quote::{quote, quote_spanned, ToTokens};
 use r#enum::{generate_data_enum, generate_unit_enum};
 use r#struct::{generate_struct, generate_unit_struct};
 use syn::Ident;
 
-pub(crate) fn generate(ident: &Ident, input: &Input) -> TokenStream {
+/// Whether the generated code reads synchronously, for `BinRead`,
+/// asynchronously, for `BinReadAsync`, or from a `SliceReader`, for
+/// `BinReadBorrowed`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Mode {
+    Sync,
+    Async,
+    Borrowed,
+}
+
+impl Mode {
+    /// Returns `.await` for async code, to follow calls to async functions
+    fn await_suffix(self) -> Option<TokenStream> {
+        (self == Mode::Async).then(|| quote! { .await })
+    }
+
+    fn read_method(self) -> IdentStr {
+        match self {
+            Mode::Sync => READ_METHOD,
+            Mode::Async => READ_METHOD_ASYNC,
+            Mode::Borrowed => READ_METHOD_BORROWED,
+        }
+    }
+
+    /// The type of the reader, for naming the arguments type of a custom
+    /// parser
+    fn reader_type(self) -> TokenStream {
+        match self {
+            Mode::Sync | Mode::Async => quote! { R },
+            Mode::Borrowed => quote! { #SLICE_READER<'_> },
+        }
+    }
+
+    /// The trait whose `Args` type gives the arguments of a field
+    fn trait_name(self) -> TokenStream {
+        match self {
+            Mode::Sync | Mode::Async => TRAIT_NAME.into_token_stream(),
+            Mode::Borrowed => quote! { #BORROWED_TRAIT_NAME<'_> },
+        }
+    }
+
+    fn seek(self, pos: &TokenStream) -> TokenStream {
+        match self {
+            Mode::Sync | Mode::Borrowed => quote! { #SEEK_TRAIT::seek(#READER, #pos) },
+            Mode::Async => quote! { #SEEK_ASYNC(#READER, #pos).await },
+        }
+    }
+
+    fn stream_pos(self) -> TokenStream {
+        match self {
+            Mode::Sync | Mode::Borrowed => quote! { #POS_TRAIT::stream_pos(#READER) },
+            Mode::Async => quote! { #STREAM_POS_ASYNC(#READER).await },
+        }
+    }
+
+    /// Evaluates `body` in its own scope, so that `?` and `return` inside of
+    /// it produce the result of type `ty` instead of returning from the
+    /// function.
+    fn immediate(self, ty: &TokenStream, body: &TokenStream) -> TokenStream {
+        match self {
+            Mode::Sync | Mode::Borrowed => quote! {
+                (|| -> #ty {
+                    #body
+                })()
+            },
+            // An async block can not be given a return type, so it is
+            // inferred from the variable the result is assigned to
+            Mode::Async => quote! {{
+                let #TEMP: #ty = async {
+                    #body
+                }.await;
+                #TEMP
+            }},
+        }
+    }
+}
+
+pub(crate) fn generate(ident: &Ident, input: &Input, mode: Mode) -> TokenStream {
     let inner = match input.map() {
         Map::None => match input {
-            Input::UnitStruct(_) => generate_unit_struct(input, None),
-            Input::Struct(s) => generate_struct(ident, input, s),
-            Input::Enum(e) => generate_data_enum(e),
-            Input::UnitOnlyEnum(e) => generate_unit_enum(input, e),
+            Input::UnitStruct(_) => generate_unit_struct(input, None, mode),
+            Input::Struct(s) => generate_struct(ident, input, s, mode),
+            Input::Enum(e) => generate_data_enum(ident, e, mode),
+            Input::UnitOnlyEnum(e) => generate_unit_enum(input, e, mode),
         },
         Map::Try(map) => {
+            let read_method = mode.read_method();
+            let await_suffix = mode.await_suffix();
             let map_err = get_map_err(POS);
             quote! {
-                #READ_METHOD(#READER, #OPT, #ARGS).and_then(|value| {
+                #read_method(#READER, #OPT, #ARGS)#await_suffix.and_then(|value| {
                     #map(value)#map_err
                 })
             }
         }
-        Map::Map(map) => quote! {
-            #READ_METHOD(#READER, #OPT, #ARGS).map(#map)
-        },
+        Map::Map(map) => {
+            let read_method = mode.read_method();
+            let await_suffix = mode.await_suffix();
+            quote! {
+                #read_method(#READER, #OPT, #ARGS)#await_suffix.map(#map)
+            }
+        }
     };
 
-    quote! {
-        let #POS = #POS_TRAIT::stream_pos(#READER)?;
-        (|| {
-            #inner
-        })().or_else(|error| {
-            #SEEK_TRAIT::seek(#READER, #SEEK_FROM::Start(#POS))?;
-            Err(error)
-        })
+    let stream_pos = mode.stream_pos();
+    match mode {
+        Mode::Sync | Mode::Borrowed => quote! {
+            let #POS = #stream_pos?;
+            (|| {
+                #inner
+            })().or_else(|error| {
+                #SEEK_TRAIT::seek(#READER, #SEEK_FROM::Start(#POS))?;
+                Err(error)
+            })
+        },
+        Mode::Async => {
+            let result = mode.immediate(&quote! { #BIN_RESULT<Self> }, &inner);
+            let seek = mode.seek(&quote! { #SEEK_FROM::Start(#POS) });
+            quote! {
+                let #POS = #stream_pos?;
+                let #TEMP = #result;
+                if #TEMP.is_err() {
+                    #seek?;
+                }
+                #TEMP
+            }
+        }
     }
 }
 
 struct PreludeGenerator<'input> {
     input: &'input Input,
+    mode: Mode,
     out: TokenStream,
 }
 
 impl<'input> PreludeGenerator<'input> {
-    fn new(input: &'input Input) -> Self {
+    fn new(input: &'input Input, mode: Mode) -> Self {
         Self {
             input,
+            mode,
             out: TokenStream::new(),
         }
     }
@@ -61,11 +162,11 @@ impl<'input> PreludeGenerator<'input> {
     }
 
     fn add_imports(mut self) -> Self {
-        if let Some(imports) = self.input.imports().idents() {
+        if let Some(imports) = self.input.imports().destructure(&ARGS) {
             let head = self.out;
             self.out = quote! {
                 #head
-                let #imports = #ARGS;
+                #imports
             };
         }
 
@@ -74,7 +175,7 @@ impl<'input> PreludeGenerator<'input> {
 
     fn add_options(mut self) -> Self {
         let options = ReadOptionsGenerator::new(OPT)
-            .endian(&self.input.endian())
+            .endian(self.input.endian())
             .finish();
 
         if !options.is_empty() {
@@ -89,8 +190,8 @@ impl<'input> PreludeGenerator<'input> {
     }
 
     fn add_magic_pre_assertion(mut self) -> Self {
-        let magic = get_magic(self.input.magic(), &OPT);
-        let pre_assertions = get_assertions(&self.input.pre_assertions());
+        let magic = get_magic(self.input.magic(), &OPT, self.mode);
+        let pre_assertions = get_assertions(self.input.pre_assertions());
         let head = self.out;
 
         self.out = quote! {
@@ -131,16 +232,41 @@ fn get_assertions(assertions: &[Assert]) -> impl Iterator<Item = TokenStream> +
     )
 }
 
-fn get_magic(magic: &Magic, options_var: &impl ToTokens) -> Option<TokenStream> {
+fn get_magic(magic: &Magic, options_var: &impl ToTokens, mode: Mode) -> Option<TokenStream> {
     magic.as_ref().map(|magic| {
         let handle_error = debug_template::handle_error();
         let magic = magic.deref_value();
-        quote! {
-            #ASSERT_MAGIC(#READER, #magic, #options_var)#handle_error?;
+        match mode {
+            Mode::Sync | Mode::Borrowed => quote! {
+                #ASSERT_MAGIC(#READER, #magic, #options_var)#handle_error?;
+            },
+            Mode::Async => quote! {
+                #ASSERT_MAGIC_ASYNC(#READER, #magic, #options_var).await#handle_error?;
+            },
         }
     })
 }
 
+/// Adds the field being read to any error from `value`, which is a
+/// `BinResult`.
+fn add_field_context(
+    type_name: &str,
+    field: &StructField,
+    value: &TokenStream,
+    mode: Mode,
+) -> TokenStream {
+    let field_name = field.source_name();
+    let line = quote_spanned! {field.attr_span=> line!() };
+    match mode {
+        Mode::Sync | Mode::Borrowed => quote! {
+            #value.map_err(|e| #FIELD_CONTEXT(#READER, e, #type_name, #field_name, #line))
+        },
+        Mode::Async => quote! {
+            #FIELD_CONTEXT_ASYNC(#value, #READER, #type_name, #field_name, #line).await
+        },
+    }
+}
+
 fn get_map_err(pos: IdentStr) -> TokenStream {
     quote! {
         .map_err(|e| {
@@ -165,8 +291,8 @@ impl ReadOptionsGenerator {
         }
     }
 
-    fn count(mut self, count: &Option<TokenStream>) -> Self {
-        if let Some(count) = &count {
+    fn count(mut self, count: Option<&TokenStream>) -> Self {
+        if let Some(count) = count {
             let head = self.out;
             self.out = quote! {
                 #head
@@ -178,30 +304,8 @@ impl ReadOptionsGenerator {
     }
 
     fn endian(mut self, endian: &CondEndian) -> Self {
-        let endian = match endian {
-            CondEndian::Inherited => return self,
-            CondEndian::Fixed(Endian::Big) => quote! { #ENDIAN_ENUM::Big },
-            CondEndian::Fixed(Endian::Little) => quote! { #ENDIAN_ENUM::Little },
-            CondEndian::Cond(endian, condition) => {
-                let (true_cond, false_cond) = match endian {
-                    Endian::Big => (
-                        quote! { #ENDIAN_ENUM::Big },
-                        quote! { #ENDIAN_ENUM::Little },
-                    ),
-                    Endian::Little => (
-                        quote! { #ENDIAN_ENUM::Little },
-                        quote! { #ENDIAN_ENUM::Big },
-                    ),
-                };
-
-                quote! {
-                    if (#condition) {
-                        #true_cond
-                    } else {
-                        #false_cond
-                    }
-                }
-            }
+        let Some(endian) = get_endian(endian) else {
+            return self;
         };
 
         let head = self.out;
@@ -231,8 +335,8 @@ impl ReadOptionsGenerator {
         }
     }
 
-    fn offset(mut self, offset: &Option<TokenStream>) -> Self {
-        if let Some(offset) = &offset {
+    fn offset(mut self, offset: Option<&TokenStream>) -> Self {
+        if let Some(offset) = offset {
             let head = self.out;
             self.out = quote! {
                 #head
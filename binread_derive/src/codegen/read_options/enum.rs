@@ -1,18 +1,19 @@
 use super::{
     get_assertions,
     r#struct::{generate_unit_struct, StructGenerator},
-    PreludeGenerator,
+    Mode, PreludeGenerator,
 };
 #[allow(clippy::wildcard_imports)]
 use crate::codegen::sanitization::*;
 use crate::parser::{Enum, EnumErrorMode, EnumVariant, Input, UnitEnumField, UnitOnlyEnum};
 use proc_macro2::TokenStream;
 use quote::quote;
+use syn::Ident;
 
-pub(super) fn generate_unit_enum(input: &Input, en: &UnitOnlyEnum) -> TokenStream {
+pub(super) fn generate_unit_enum(input: &Input, en: &UnitOnlyEnum, mode: Mode) -> TokenStream {
     match &en.repr {
-        Some(repr) => generate_unit_enum_repr(input, repr, &en.fields),
-        None => generate_unit_enum_magic(input, en, &en.fields),
+        Some(repr) => generate_unit_enum_repr(input, repr, &en.fields, mode),
+        None => generate_unit_enum_magic(input, en, &en.fields, mode),
     }
 }
 
@@ -20,6 +21,7 @@ fn generate_unit_enum_repr(
     input: &Input,
     repr: &TokenStream,
     variants: &[UnitEnumField],
+    mode: Mode,
 ) -> TokenStream {
     let clauses = variants.iter().map(|variant| {
         let ident = &variant.ident;
@@ -30,11 +32,13 @@ fn generate_unit_enum_repr(
         }
     });
 
-    let prelude = PreludeGenerator::new(input).add_options().finish();
+    let prelude = PreludeGenerator::new(input, mode).add_options().finish();
+    let read_method = mode.read_method();
+    let await_suffix = mode.await_suffix();
 
     quote! {
         #prelude
-        let #TEMP: #repr = #READ_METHOD(#READER, #OPT, ())?;
+        let #TEMP: #repr = #read_method(#READER, #OPT, ())#await_suffix?;
         #(#clauses else)* {
             Err(#BIN_ERROR::NoVariantMatch {
                 pos: #POS,
@@ -47,8 +51,9 @@ fn generate_unit_enum_magic(
     input: &Input,
     en: &UnitOnlyEnum,
     variants: &[UnitEnumField],
+    mode: Mode,
 ) -> TokenStream {
-    let prelude = PreludeGenerator::new(input)
+    let prelude = PreludeGenerator::new(input, mode)
         .add_imports()
         .add_options()
         .finish();
@@ -74,16 +79,19 @@ fn generate_unit_enum_magic(
         .as_ref()
         .map(|magic| magic.add_ref());
 
+    let read_method = mode.read_method();
+    let await_suffix = mode.await_suffix();
+
     quote! {
         #prelude
-        match #amp#READ_METHOD(#READER, #OPT, ())? {
+        match #amp#read_method(#READER, #OPT, ())#await_suffix? {
             #(#matches,)*
             _ => Err(#BIN_ERROR::NoVariantMatch { pos: #POS })
         }
     }
 }
 
-pub(super) fn generate_data_enum(en: &Enum) -> TokenStream {
+pub(super) fn generate_data_enum(ident: &Ident, en: &Enum, mode: Mode) -> TokenStream {
     let return_all_errors = en.error_mode != EnumErrorMode::ReturnUnexpectedError;
 
     let (create_error_basket, return_error) = if return_all_errors {
@@ -111,7 +119,7 @@ pub(super) fn generate_data_enum(en: &Enum) -> TokenStream {
     };
 
     let try_each_variant = en.variants.iter().map(|variant| {
-        let body = generate_variant_impl(en, variant);
+        let body = generate_variant_impl(ident, en, variant, mode);
 
         let handle_error = if return_all_errors {
             let name = variant.ident().to_string();
@@ -122,16 +130,24 @@ pub(super) fn generate_data_enum(en: &Enum) -> TokenStream {
             TokenStream::new()
         };
 
+        let result = match mode {
+            Mode::Sync | Mode::Borrowed => quote! {
+                (|| {
+                    #body
+                })()
+            },
+            Mode::Async => mode.immediate(&quote! { #BIN_RESULT<Self> }, &body),
+        };
+        let seek = mode.seek(&quote! { #SEEK_FROM::Start(#POS) });
+
         quote! {
-            let #TEMP = (|| {
-                #body
-            })();
+            let #TEMP = #result;
 
             if #TEMP.is_ok() {
                 return #TEMP;
             } else {
                 #handle_error
-                #SEEK_TRAIT::seek(#READER, #SEEK_FROM::Start(#POS))?;
+                #seek?;
             }
         }
     });
@@ -143,18 +159,23 @@ pub(super) fn generate_data_enum(en: &Enum) -> TokenStream {
     }
 }
 
-fn generate_variant_impl(en: &Enum, variant: &EnumVariant) -> TokenStream {
+fn generate_variant_impl(
+    enum_ident: &Ident,
+    en: &Enum,
+    variant: &EnumVariant,
+    mode: Mode,
+) -> TokenStream {
     // TODO: Kind of expensive since the enum is containing all the fields
     // and this is a clone.
     let input = Input::Enum(en.with_variant(variant));
 
     match variant {
-        EnumVariant::Variant { ident, options } => StructGenerator::new(&input, &options)
-            .read_fields()
+        EnumVariant::Variant { ident, options } => StructGenerator::new(&input, options, mode)
+            .read_fields(&format!("{enum_ident}::{ident}"))
             .add_assertions(get_assertions(&en.assertions))
             .return_value(Some(ident))
             .finish(),
 
-        EnumVariant::Unit(options) => generate_unit_struct(&input, Some(&options.ident)),
+        EnumVariant::Unit(options) => generate_unit_struct(&input, Some(&options.ident), mode),
     }
 }
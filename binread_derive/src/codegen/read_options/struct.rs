@@ -1,13 +1,20 @@
-use super::{debug_template, get_assertions, get_magic, PreludeGenerator, ReadOptionsGenerator};
+use super::{
+    add_field_context, debug_template, get_assertions, get_magic, Mode, PreludeGenerator,
+    ReadOptionsGenerator,
+};
 #[allow(clippy::wildcard_imports)]
-use crate::codegen::sanitization::*;
-use crate::parser::{Input, Map, PassedArgs, ReadMode, Struct, StructField};
+use crate::codegen::{get_bit_order, get_passed_args, sanitization::*, wrap_endian_from};
+use crate::parser::{Input, Map, ReadMode, Struct, StructField};
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::Ident;
-
-pub(super) fn generate_unit_struct(input: &Input, variant_ident: Option<&Ident>) -> TokenStream {
-    let prelude = get_prelude(input);
+use quote::{quote, ToTokens};
+use syn::{spanned::Spanned, Ident};
+
+pub(super) fn generate_unit_struct(
+    input: &Input,
+    variant_ident: Option<&Ident>,
+    mode: Mode,
+) -> TokenStream {
+    let prelude = get_prelude(input, mode);
     let return_type = get_return_type(variant_ident);
     quote! {
         #prelude
@@ -15,9 +22,14 @@ pub(super) fn generate_unit_struct(input: &Input, variant_ident: Option<&Ident>)
     }
 }
 
-pub(super) fn generate_struct(ident: &Ident, input: &Input, st: &Struct) -> TokenStream {
-    StructGenerator::new(input, st)
-        .read_fields()
+pub(super) fn generate_struct(
+    ident: &Ident,
+    input: &Input,
+    st: &Struct,
+    mode: Mode,
+) -> TokenStream {
+    StructGenerator::new(input, st, mode)
+        .read_fields(&ident.to_string())
         .wrap_debug(ident)
         .add_assertions(core::iter::empty())
         .return_value(None)
@@ -27,14 +39,16 @@ pub(super) fn generate_struct(ident: &Ident, input: &Input, st: &Struct) -> Toke
 pub(super) struct StructGenerator<'input> {
     input: &'input Input,
     st: &'input Struct,
+    mode: Mode,
     out: TokenStream,
 }
 
 impl<'input> StructGenerator<'input> {
-    pub(super) fn new(input: &'input Input, st: &'input Struct) -> Self {
+    pub(super) fn new(input: &'input Input, st: &'input Struct, mode: Mode) -> Self {
         Self {
             input,
             st,
+            mode,
             out: TokenStream::new(),
         }
     }
@@ -57,19 +71,42 @@ impl<'input> StructGenerator<'input> {
         self
     }
 
-    pub(super) fn read_fields(mut self) -> Self {
-        let prelude = get_prelude(self.input);
-        let read_fields = self.st.fields.iter().map(|field| generate_field(field));
+    /// Reads every field. `type_name` is the name of the struct or enum
+    /// variant, which is used for the context added to errors.
+    pub(super) fn read_fields(mut self, type_name: &str) -> Self {
+        let prelude = get_prelude(self.input, self.mode);
+        let has_bits = self.st.has_bits();
+        let bit_reader = has_bits.then(|| {
+            let bit_order = get_bit_order(self.input.bit_order());
+            quote! { let mut #BIT_READER = #BIT_READER_TYPE::new(#bit_order); }
+        });
+        let read_fields = self.st.fields.iter().map(|field| {
+            let read_field = generate_field(type_name, field, has_bits, self.mode);
+            if self.st.endian.marker_field() == Some(&field.ident) {
+                let handle_error = debug_template::handle_error();
+                let stream_pos = self.mode.stream_pos();
+                let ident = &field.ident;
+                wrap_endian_from(
+                    &self.st.endian,
+                    read_field,
+                    &quote! { #stream_pos#handle_error },
+                    &quote! { &#ident },
+                )
+            } else {
+                read_field
+            }
+        });
         let after_parse = {
             let after_parse = self
                 .st
                 .fields
                 .iter()
-                .map(|field| generate_after_parse(field));
-            wrap_save_restore(quote!(#(#after_parse)*))
+                .map(|field| generate_after_parse(type_name, field, self.mode));
+            wrap_save_restore(quote!(#(#after_parse)*), self.mode)
         };
         self.out = quote! {
             #prelude
+            #bit_reader
             #(#read_fields)*
             #after_parse
         };
@@ -97,7 +134,7 @@ impl<'input> StructGenerator<'input> {
 
     pub(super) fn wrap_debug(mut self, ident: &Ident) -> Self {
         if cfg!(feature = "debug_template") {
-            let debug_tpl_start = debug_template::start(&ident);
+            let debug_tpl_start = debug_template::start(ident);
             let debug_tpl_end = debug_template::end();
             let body = self.out;
             self.out = quote! {
@@ -111,15 +148,17 @@ impl<'input> StructGenerator<'input> {
     }
 }
 
-fn generate_after_parse(field: &StructField) -> Option<TokenStream> {
+fn generate_after_parse(type_name: &str, field: &StructField, mode: Mode) -> Option<TokenStream> {
     if field.should_use_after_parse() {
-        get_after_parse_handler(&field).map(|after_parse_fn| {
+        get_after_parse_handler(field, mode).map(|after_parse_fn| {
             let args_var = make_ident(&field.ident, "args");
             let options_var = make_ident(&field.ident, "options");
-            AfterParseGenerator::new(field)
+            AfterParseGenerator::new(field, mode)
                 .get_value_from_ident()
                 .call_after_parse(after_parse_fn, &options_var, &args_var)
+                .add_context(type_name)
                 .prefix_offset_options(&options_var)
+                .prefix_args(&args_var)
                 .finish()
         })
     } else {
@@ -127,33 +166,38 @@ fn generate_after_parse(field: &StructField) -> Option<TokenStream> {
     }
 }
 
-fn generate_field(field: &StructField) -> TokenStream {
+fn generate_field(type_name: &str, field: &StructField, has_bits: bool, mode: Mode) -> TokenStream {
     let args_var = make_ident(&field.ident, "args");
     let options_var = make_ident(&field.ident, "options");
-    FieldGenerator::new(&field)
+    FieldGenerator::new(field, mode)
         .read_value(&options_var, &args_var)
         .try_conversion()
         .map_value()
         .deref_now(&options_var, &args_var)
+        .wrap_size()
         .wrap_seek()
         .wrap_condition()
+        .wrap_context(type_name)
         .assign_to_var()
         .append_assertions()
         .wrap_restore_position()
         .prefix_magic(&options_var)
         .prefix_args_and_options(&options_var, &args_var)
+        .prefix_bit_align(has_bits)
         .finish()
 }
 
 struct AfterParseGenerator<'field> {
     field: &'field StructField,
+    mode: Mode,
     out: TokenStream,
 }
 
 impl<'field> AfterParseGenerator<'field> {
-    fn new(field: &'field StructField) -> Self {
+    fn new(field: &'field StructField, mode: Mode) -> Self {
         Self {
             field,
+            mode,
             out: TokenStream::new(),
         }
     }
@@ -165,16 +209,27 @@ impl<'field> AfterParseGenerator<'field> {
         args_var: &Ident,
     ) -> Self {
         let handle_error = debug_template::handle_error();
+        let await_suffix = self.mode.await_suffix();
         let value = self.out;
         self.out = quote! {
-            #after_parse_fn(#value, #READER, #options_var, #args_var.clone())#handle_error?;
+            #after_parse_fn(#value, #READER, #options_var, #args_var.clone())#await_suffix#handle_error
         };
 
         self
     }
 
+    /// Adds the field to any error from `after_parse`. This is not needed
+    /// when `after_parse` is called while reading the field, since the field
+    /// read is already wrapped by `FieldGenerator::wrap_context`.
+    fn add_context(mut self, type_name: &str) -> Self {
+        self.out = add_field_context(type_name, self.field, &self.out, self.mode);
+
+        self
+    }
+
     fn finish(self) -> TokenStream {
-        self.out
+        let out = self.out;
+        quote! { #out?; }
     }
 
     fn get_value_from_ident(mut self) -> Self {
@@ -190,6 +245,20 @@ impl<'field> AfterParseGenerator<'field> {
         self
     }
 
+    /// Evaluates the arguments again rather than reusing the ones from when
+    /// the field was read, since those may still borrow earlier fields which
+    /// are mutably borrowed by their own `after_parse`.
+    fn prefix_args(mut self, args_var: &Ident) -> Self {
+        let args = get_args(self.field, args_var, self.mode);
+        let tail = self.out;
+        self.out = quote! {
+            #args
+            #tail
+        };
+
+        self
+    }
+
     fn prefix_offset_options(mut self, options_var: &Ident) -> Self {
         if let Some(offset) = &self.field.offset_after {
             let tail = self.out;
@@ -210,16 +279,18 @@ impl<'field> AfterParseGenerator<'field> {
 
 struct FieldGenerator<'field> {
     field: &'field StructField,
+    mode: Mode,
     out: TokenStream,
     emit_options_vars: bool,
 }
 
 impl<'field> FieldGenerator<'field> {
-    fn new(field: &'field StructField) -> Self {
+    fn new(field: &'field StructField, mode: Mode) -> Self {
         Self {
             field,
+            mode,
             out: TokenStream::new(),
-            emit_options_vars: get_after_parse_handler(field).is_some(),
+            emit_options_vars: get_after_parse_handler(field, mode).is_some(),
         }
     }
 
@@ -248,8 +319,8 @@ impl<'field> FieldGenerator<'field> {
             return self;
         }
 
-        if let Some(after_parse) = get_after_parse_handler(&self.field) {
-            let after_parse = AfterParseGenerator::new(self.field)
+        if let Some(after_parse) = get_after_parse_handler(self.field, self.mode) {
+            let after_parse = AfterParseGenerator::new(self.field, self.mode)
                 .get_value_from_temp()
                 .call_after_parse(after_parse, options_var, args_var)
                 .finish();
@@ -282,8 +353,9 @@ impl<'field> FieldGenerator<'field> {
                 // TODO: Position should always just be saved once for a field if used
                 let value = self.out;
                 let map_err = super::get_map_err(SAVED_POSITION);
+                let stream_pos = self.mode.stream_pos();
                 quote! {{
-                    let #SAVED_POSITION = #POS_TRAIT::stream_pos(#READER)?;
+                    let #SAVED_POSITION = #stream_pos?;
 
                     (#COERCE_FN::<::core::result::Result<#ty, _>, _, _>(#try_map))(#value)#map_err?
                 }}
@@ -295,16 +367,23 @@ impl<'field> FieldGenerator<'field> {
 
     fn prefix_args_and_options(mut self, options_var: &Ident, args_var: &Ident) -> Self {
         if self.emit_options_vars {
-            let args = get_passed_args(&self.field.args);
+            let parser = get_parser(self.field, self.mode)
+                .filter(|_| !self.field.size.is_some())
+                .map(|parser| {
+                    let parser_var = make_ident(&self.field.ident, "parse_with");
+                    quote! { let #parser_var = #parser; }
+                });
+            let args = get_args(self.field, args_var, self.mode);
             let options = ReadOptionsGenerator::new(options_var)
                 .endian(&self.field.endian)
-                .offset(&self.field.offset)
+                .offset(self.field.offset.as_ref())
                 .variable_name(&self.field.ident)
-                .count(&self.field.count)
+                .count(self.field.count.as_ref())
                 .finish();
             let tail = self.out;
             self.out = quote! {
-                let #args_var = #args;
+                #parser
+                #args
                 #options
                 #tail
             };
@@ -313,8 +392,22 @@ impl<'field> FieldGenerator<'field> {
         self
     }
 
+    /// Discards the rest of a partially read byte when a field after a run
+    /// of `bits` fields reads whole bytes again.
+    fn prefix_bit_align(mut self, has_bits: bool) -> Self {
+        if has_bits && self.field.ends_bit_run() {
+            let tail = self.out;
+            self.out = quote! {
+                #BIT_READER.align();
+                #tail
+            };
+        }
+
+        self
+    }
+
     fn prefix_magic(mut self, options_var: &Ident) -> Self {
-        if let Some(magic) = get_magic(&self.field.magic, options_var) {
+        if let Some(magic) = get_magic(&self.field.magic, options_var, self.mode) {
             let tail = self.out;
             self.out = quote! {
                 #magic
@@ -326,20 +419,33 @@ impl<'field> FieldGenerator<'field> {
     }
 
     fn read_value(mut self, options_var: &Ident, args_var: &Ident) -> Self {
+        let await_suffix = self.mode.await_suffix();
         self.out = match &self.field.read_mode {
             ReadMode::Default => quote! { <_>::default() },
             ReadMode::Calc(calc) => quote! { #calc },
+            ReadMode::Normal if self.field.bits.is_some() => {
+                let bits = self.field.bits.as_deref();
+                match self.mode {
+                    Mode::Sync | Mode::Borrowed => quote! { #BIT_READER.read(#READER, #bits) },
+                    Mode::Async => quote! { #BIT_READER.read_async(#READER, #bits).await },
+                }
+            }
             ReadMode::Normal | ReadMode::ParseWith(_) => {
-                let read_method = if let ReadMode::ParseWith(parser) = &self.field.read_mode {
-                    parser.clone()
+                let read_method = if let Some(parser) = get_parser_ref(self.field, self.mode) {
+                    parser
+                } else if let ReadMode::ParseWith(parser) = &self.field.read_mode {
+                    return self.error(
+                        parser,
+                        "`parse_with` requires a matching `parse_with_async` to derive BinReadAsync",
+                    );
                 } else {
-                    quote! { #READ_METHOD }
+                    self.mode.read_method().into_token_stream()
                 };
 
                 self.emit_options_vars = true;
 
                 quote! {
-                    #read_method(#READER, #options_var, #args_var.clone())
+                    #read_method(#READER, #options_var, #args_var.clone())#await_suffix
                 }
             }
         };
@@ -347,6 +453,14 @@ impl<'field> FieldGenerator<'field> {
         self
     }
 
+    /// Replaces the value being read with a compile error, leaving any
+    /// further steps to wrap the error.
+    fn error(mut self, span: &impl Spanned, message: &str) -> Self {
+        self.out = syn::Error::new(span.span(), message).to_compile_error();
+
+        self
+    }
+
     fn try_conversion(mut self) -> Self {
         if self.field.generated_value() {
             if self.field.do_try {
@@ -383,17 +497,66 @@ impl<'field> FieldGenerator<'field> {
         self
     }
 
+    fn wrap_context(mut self, type_name: &str) -> Self {
+        if !self.field.generated_value() {
+            let ty = &self.field.ty;
+            let value = self.out;
+            let result = self.mode.immediate(
+                &quote! { #BIN_RESULT<#ty> },
+                &quote! {
+                    let #TEMP = #value;
+                    Ok(#TEMP)
+                },
+            );
+            let result = add_field_context(type_name, self.field, &result, self.mode);
+            self.out = quote! { #result? };
+        }
+
+        self
+    }
+
     fn wrap_restore_position(mut self) -> Self {
         if self.field.restore_position {
-            self.out = wrap_save_restore(self.out);
+            self.out = wrap_save_restore(self.out, self.mode);
+        }
+
+        self
+    }
+
+    /// Reads the field through a reader which can not read past its `size`.
+    /// The position and size are taken, and the rest of the size skipped, by
+    /// `wrap_seek`.
+    fn wrap_size(mut self) -> Self {
+        if self.field.size.is_some() {
+            let limited_reader = match self.mode {
+                Mode::Sync => quote! {
+                    #TAKE_SEEK::with_pos(&mut *#READER as &mut dyn #READ_SEEK, #POS, #SIZE_LIMIT)
+                },
+                Mode::Async => quote! { #TAKE_SEEK::with_pos(&mut *#READER, #POS, #SIZE_LIMIT) },
+                Mode::Borrowed => quote! { #READER.limit(#SIZE_LIMIT) },
+            };
+            let check_size = self.field.size.is_exact().then(|| {
+                let handle_error = debug_template::handle_error();
+                let stream_pos = self.mode.stream_pos();
+                quote! {
+                    #CHECK_SIZE(#POS, #stream_pos#handle_error?, #SIZE_LIMIT, true)?;
+                }
+            });
+            let value = self.out;
+            self.out = quote! {{
+                let #READER = &mut #limited_reader;
+                let #TEMP = #value;
+                #check_size
+                #TEMP
+            }};
         }
 
         self
     }
 
     fn wrap_seek(mut self) -> Self {
-        let seek_before = generate_seek_before(self.field);
-        let seek_after = generate_seek_after(self.field);
+        let seek_before = generate_seek_before(self.field, self.mode);
+        let seek_after = generate_seek_after(self.field, self.mode);
         if !seek_before.is_empty() || !seek_after.is_empty() {
             let value = self.out;
             self.out = quote! {{
@@ -408,55 +571,146 @@ impl<'field> FieldGenerator<'field> {
     }
 }
 
-fn get_passed_args(args: &PassedArgs) -> TokenStream {
-    match args {
-        PassedArgs::List(list) => quote! { (#(#list,)*) },
-        PassedArgs::Tuple(tuple) => tuple.clone(),
-        PassedArgs::None => quote! { () },
+/// Generates the statement binding the arguments passed to a field.
+fn get_args(field: &StructField, args_var: &Ident, mode: Mode) -> TokenStream {
+    let args_type = get_args_type(field, mode);
+    let args = get_passed_args(&field.args, args_type.is_some(), || {
+        get_named_args_builder(field, mode)
+    });
+    quote! {
+        let #args_var #args_type = #args;
     }
 }
 
-fn get_prelude(input: &Input) -> TokenStream {
-    PreludeGenerator::new(input)
+/// Returns a type annotation for the arguments variable when the type
+/// being read is known, so that borrowed arguments are coerced to the
+/// expected types (e.g. `&Vec<T>` to `&[T]`).
+fn get_args_type(field: &StructField, mode: Mode) -> Option<TokenStream> {
+    let ty = &field.ty;
+    match (&field.read_mode, &field.map) {
+        _ if get_parser(field, mode).is_some() => None,
+        (ReadMode::Normal, Map::None) => {
+            let trait_name = mode.trait_name();
+            Some(quote! { : <#ty as #trait_name>::Args<'_> })
+        }
+        _ => None,
+    }
+}
+
+fn get_named_args_builder(field: &StructField, mode: Mode) -> TokenStream {
+    let ty = &field.ty;
+    match (&field.read_mode, &field.map) {
+        // The arguments type can not be inferred from an async function,
+        // since its return type depends on the lifetimes of its parameters
+        _ if mode == Mode::Async && field.parse_with_async.is_some() => syn::Error::new(
+            field.attr_span,
+            "named arguments can not be used with `parse_with_async`; use `args_tuple` instead",
+        )
+        .to_compile_error(),
+        (ReadMode::ParseWith(_), _) => {
+            let parser = get_parser_ref(field, mode);
+            let reader_type = mode.reader_type();
+            quote! { #PARSE_FN_BUILDER::<#reader_type, _, _, _>(&#parser) }
+        }
+        (_, Map::None) => {
+            let trait_name = mode.trait_name();
+            quote! { <<#ty as #trait_name>::Args<'_> as #NAMED_ARGS>::builder() }
+        }
+        (_, Map::Map(map) | Map::Try(map)) => syn::Error::new(
+            map.span(),
+            "named arguments can not be used with `map` or `try_map` because the type \
+             being read is unknown; use `parse_with` or `args_tuple` instead",
+        )
+        .to_compile_error(),
+    }
+}
+
+/// Returns the custom parser used to read a field, if it has one.
+fn get_parser(field: &StructField, mode: Mode) -> Option<&TokenStream> {
+    match (mode, &field.read_mode) {
+        (Mode::Sync | Mode::Borrowed, ReadMode::ParseWith(parser)) => Some(parser),
+        (Mode::Sync | Mode::Borrowed, _) => None,
+        (Mode::Async, _) => field.parse_with_async.as_ref(),
+    }
+}
+
+/// Returns the expression for calling the custom parser of a field. This is
+/// usually a variable holding the parser, but fields with a `size` read from
+/// a reader which borrows the outer one, so their parser is used directly to
+/// keep the type of that reader from leaking into the variable.
+fn get_parser_ref(field: &StructField, mode: Mode) -> Option<TokenStream> {
+    get_parser(field, mode).map(|parser| {
+        if field.size.is_some() {
+            quote! { (#parser) }
+        } else {
+            make_ident(&field.ident, "parse_with").into_token_stream()
+        }
+    })
+}
+
+fn get_prelude(input: &Input, mode: Mode) -> TokenStream {
+    PreludeGenerator::new(input, mode)
         .add_imports()
         .add_options()
         .add_magic_pre_assertion()
         .finish()
 }
 
-fn generate_seek_after(field: &StructField) -> TokenStream {
+fn generate_seek_after(field: &StructField, mode: Mode) -> TokenStream {
     let handle_error = debug_template::handle_error();
     let pad_size_to = field.pad_size_to.as_ref().map(|pad| {
+        let stream_pos = mode.stream_pos();
+        let seek = mode.seek(&quote! { #SEEK_FROM::Current(pad - size) });
         quote! {{
             let pad = (#pad) as i64;
-            let size = (#POS_TRAIT::stream_pos(#READER)#handle_error? - #POS) as i64;
+            let size = (#stream_pos#handle_error? - #POS) as i64;
             if size < pad {
-                #SEEK_TRAIT::seek(#READER, #SEEK_FROM::Current(pad - size))#handle_error?;
+                #seek#handle_error?;
             }
         }}
     });
-    let pad_after = field.pad_after.as_ref().map(map_pad);
-    let align_after = field.align_after.as_ref().map(map_align);
+    let size = field.size.is_some().then(|| {
+        let seek = mode.seek(&quote! { #SEEK_FROM::Start(#POS + #SIZE_LIMIT) });
+        quote! {
+            #seek#handle_error?;
+        }
+    });
+    let pad_after = field.pad_after.as_ref().map(|pad| map_pad(pad, mode));
+    let align_after = field
+        .align_after
+        .as_ref()
+        .map(|align| map_align(align, mode));
 
     quote! {
+        #size
         #pad_size_to
         #pad_after
         #align_after
     }
 }
 
-fn generate_seek_before(field: &StructField) -> TokenStream {
+fn generate_seek_before(field: &StructField, mode: Mode) -> TokenStream {
     let handle_error = debug_template::handle_error();
     let seek_before = field.seek_before.as_ref().map(|seek| {
+        let seek = mode.seek(seek);
         quote! {
-            #SEEK_TRAIT::seek(#READER, #seek)#handle_error?;
+            #seek#handle_error?;
         }
     });
-    let pad_before = field.pad_before.as_ref().map(map_pad);
-    let align_before = field.align_before.as_ref().map(map_align);
-    let pad_size_to_before = field.pad_size_to.as_ref().map(|_| {
+    let pad_before = field.pad_before.as_ref().map(|pad| map_pad(pad, mode));
+    let align_before = field
+        .align_before
+        .as_ref()
+        .map(|align| map_align(align, mode));
+    let pad_size_to_before = (field.pad_size_to.is_some() || field.size.is_some()).then(|| {
+        let stream_pos = mode.stream_pos();
         quote! {
-            let #POS = #POS_TRAIT::stream_pos(#READER)#handle_error?;
+            let #POS = #stream_pos#handle_error?;
+        }
+    });
+    let size_before = field.size.expr().map(|size| {
+        quote! {
+            let #SIZE_LIMIT = (#size) as u64;
         }
     });
 
@@ -465,16 +719,22 @@ fn generate_seek_before(field: &StructField) -> TokenStream {
         #pad_before
         #align_before
         #pad_size_to_before
+        #size_before
     }
 }
 
-fn get_after_parse_handler(field: &StructField) -> Option<IdentStr> {
-    if !field.can_call_after_parse() {
+fn get_after_parse_handler(field: &StructField, mode: Mode) -> Option<IdentStr> {
+    if !field.can_call_after_parse() || get_parser(field, mode).is_some() {
         None
-    } else if field.do_try {
-        Some(TRY_AFTER_PARSE)
     } else {
-        Some(AFTER_PARSE)
+        Some(match (mode, field.do_try) {
+            (Mode::Sync, false) => AFTER_PARSE,
+            (Mode::Sync, true) => TRY_AFTER_PARSE,
+            (Mode::Async, false) => AFTER_PARSE_ASYNC,
+            (Mode::Async, true) => TRY_AFTER_PARSE_ASYNC,
+            (Mode::Borrowed, false) => AFTER_PARSE_BORROWED,
+            (Mode::Borrowed, true) => TRY_AFTER_PARSE_BORROWED,
+        })
     }
 }
 
@@ -482,31 +742,36 @@ fn get_return_type(variant_ident: Option<&Ident>) -> TokenStream {
     variant_ident.map_or_else(|| quote! { Self }, |ident| quote! { Self::#ident })
 }
 
-fn map_align(align: &TokenStream) -> TokenStream {
+fn map_align(align: &TokenStream, mode: Mode) -> TokenStream {
     let handle_error = debug_template::handle_error();
+    let stream_pos = mode.stream_pos();
+    let seek = mode.seek(&quote! { #SEEK_FROM::Current((align - (pos % align)) % align) });
     quote! {{
         let align = (#align) as i64;
-        let pos = #POS_TRAIT::stream_pos(#READER)#handle_error? as i64;
-        #SEEK_TRAIT::seek(#READER, #SEEK_FROM::Current((align - (pos % align)) % align))#handle_error?;
+        let pos = #stream_pos#handle_error? as i64;
+        #seek#handle_error?;
     }}
 }
 
-fn map_pad(pad: &TokenStream) -> TokenStream {
+fn map_pad(pad: &TokenStream, mode: Mode) -> TokenStream {
     let handle_error = debug_template::handle_error();
+    let seek = mode.seek(&quote! { #SEEK_FROM::Current(#pad) });
     quote! {
-        #SEEK_TRAIT::seek(#READER, #SEEK_FROM::Current(#pad))#handle_error?;
+        #seek#handle_error?;
     }
 }
 
-fn wrap_save_restore(value: TokenStream) -> TokenStream {
+fn wrap_save_restore(value: TokenStream, mode: Mode) -> TokenStream {
     if value.is_empty() {
         value
     } else {
         let handle_error = debug_template::handle_error();
+        let stream_pos = mode.stream_pos();
+        let seek = mode.seek(&quote! { #SEEK_FROM::Start(#SAVED_POSITION) });
         quote! {
-            let #SAVED_POSITION = #POS_TRAIT::stream_pos(#READER)#handle_error?;
+            let #SAVED_POSITION = #stream_pos#handle_error?;
             #value
-            #SEEK_TRAIT::seek(#READER, #SEEK_FROM::Start(#SAVED_POSITION))#handle_error?;
+            #seek#handle_error?;
         }
     }
 }
@@ -1,4 +1,4 @@
-///! Utilities for helping sanitize macro
+//! Utilities for helping sanitize macro
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote, ToTokens, TokenStreamExt};
 
@@ -52,9 +52,56 @@ ident_str! {
     pub(super) ASSERT_ERROR_FN = from_crate!(__private::AssertErrorFn);
     pub(super) COERCE_FN = from_crate!(__private::coerce_fn);
     pub(super) TRY_AFTER_PARSE = from_crate!(__private::try_after_parse);
+    pub(super) FIELD_CONTEXT = from_crate!(__private::field_context);
     pub(super) TEMP = "__binread_temp";
     pub(super) POS = "__binread_generated_position_temp";
+    pub(super) SIZE_LIMIT = "__binread_generated_size_limit";
+    pub(super) TAKE_SEEK = from_crate!(io::TakeSeek);
+    pub(super) READ_SEEK = from_crate!(__private::ReadSeek);
+    pub(super) CHECK_SIZE = from_crate!(__private::check_size);
+    pub(super) CHECK_COUNT = from_crate!(__private::check_count);
+    pub(super) COUNT_REF = from_crate!(__private::CountRef);
+    pub(super) KNOWN_COUNT = from_crate!(__private::KnownCount);
+    pub(super) UNKNOWN_COUNT = from_crate!(__private::UnknownCount);
+    pub(super) ENDIAN_FROM = from_crate!(__private::endian_from);
+    pub(super) MARKER_POS = "__binread_generated_marker_position";
     pub(super) ERROR_BASKET = "__binread_generated_error_basket";
+    pub(super) WRITE_TRAIT_NAME = from_crate!(BinWrite);
+    pub(super) WRITE_OPTIONS = from_crate!(WriteOptions);
+    pub(super) WRITE_TRAIT = from_crate!(io::Write);
+    pub(super) WRITE_METHOD = from_crate!(BinWrite::write_options);
+    pub(super) WRITE_ZEROES = from_crate!(__private::write_zeroes);
+    pub(super) WRITER = "__binread_generated_var_writer";
+    pub(super) FIELD_REF = from_crate!(__private::FieldRef);
+    pub(super) COPY_FIELD = from_crate!(__private::CopyField);
+    pub(super) BORROW_FIELD = from_crate!(__private::BorrowField);
+    pub(super) NAMED_ARGS = from_crate!(NamedArgs);
+    pub(super) NAMED_ARGS_NEEDED = from_crate!(named_args::Needed);
+    pub(super) NAMED_ARGS_SATISFIED = from_crate!(named_args::Satisfied);
+    pub(super) DEFAULT_ARGS = from_crate!(DefaultArgs);
+    pub(super) PARSE_FN_BUILDER = from_crate!(__private::parse_fn_builder);
+    pub(super) WRITE_FN_BUILDER = from_crate!(__private::write_fn_builder);
+    pub(super) MARKERS = "__binread_generated_markers";
+    pub(super) BIT_ORDER = from_crate!(bits::BitOrder);
+    pub(super) BIT_READER_TYPE = from_crate!(bits::BitReader);
+    pub(super) BIT_WRITER_TYPE = from_crate!(bits::BitWriter);
+    pub(super) BIT_READER = "__binread_generated_bit_reader";
+    pub(super) BIT_WRITER = "__binread_generated_bit_writer";
+    pub(super) ASYNC_TRAIT_NAME = from_crate!(BinReadAsync);
+    pub(super) ASYNC_READ_TRAIT = from_crate!(io::AsyncRead);
+    pub(super) ASYNC_SEEK_TRAIT = from_crate!(io::AsyncSeek);
+    pub(super) READ_METHOD_ASYNC = from_crate!(__private::read_options_async);
+    pub(super) AFTER_PARSE_ASYNC = from_crate!(__private::after_parse_async);
+    pub(super) TRY_AFTER_PARSE_ASYNC = from_crate!(__private::try_after_parse_async);
+    pub(super) SEEK_ASYNC = from_crate!(__private::seek_async);
+    pub(super) STREAM_POS_ASYNC = from_crate!(__private::stream_pos_async);
+    pub(super) ASSERT_MAGIC_ASYNC = from_crate!(__private::magic_async);
+    pub(super) FIELD_CONTEXT_ASYNC = from_crate!(__private::field_context_async);
+    pub(super) BORROWED_TRAIT_NAME = from_crate!(BinReadBorrowed);
+    pub(super) SLICE_READER = from_crate!(io::SliceReader);
+    pub(super) READ_METHOD_BORROWED = from_crate!(BinReadBorrowed::read_options_borrowed);
+    pub(super) AFTER_PARSE_BORROWED = from_crate!(BinReadBorrowed::after_parse_borrowed);
+    pub(super) TRY_AFTER_PARSE_BORROWED = from_crate!(__private::try_after_parse_borrowed);
 }
 
 pub(crate) fn make_ident(ident: &Ident, kind: &str) -> Ident {
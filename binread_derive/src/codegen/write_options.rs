@@ -0,0 +1,159 @@
+mod r#enum;
+mod r#struct;
+
+use super::get_endian;
+#[allow(clippy::wildcard_imports)]
+use crate::codegen::sanitization::*;
+use crate::parser::{CondEndian, Input, Magic, Map};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use r#enum::{generate_data_enum, generate_unit_enum};
+use r#struct::{generate_struct, generate_unit_struct};
+use syn::spanned::Spanned;
+
+pub(crate) fn generate(input: &Input) -> TokenStream {
+    let inner = match input.map() {
+        Map::None => match input {
+            Input::UnitStruct(_) => generate_unit_struct(input, None),
+            Input::Struct(s) => generate_struct(input, s),
+            Input::Enum(e) => generate_data_enum(e),
+            Input::UnitOnlyEnum(e) => generate_unit_enum(input, e),
+        },
+        Map::Map(map) | Map::Try(map) => match input.write_map() {
+            Some(write_map) => quote! {
+                #WRITE_METHOD(&(#write_map)(self), #WRITER, #OPT, #ARGS)
+            },
+            None => missing_write_map(map),
+        },
+    };
+
+    quote! {
+        #[allow(unused_imports)]
+        use #COPY_FIELD as _;
+        #[allow(unused_imports)]
+        use #BORROW_FIELD as _;
+        #[allow(unused_imports)]
+        use #KNOWN_COUNT as _;
+        #[allow(unused_imports)]
+        use #UNKNOWN_COUNT as _;
+        #inner
+    }
+}
+
+fn missing_write_map(map: &TokenStream) -> TokenStream {
+    syn::Error::new(
+        map.span(),
+        "`map` and `try_map` require a matching `write_map` to derive BinWrite",
+    )
+    .to_compile_error()
+}
+
+struct PreludeGenerator<'input> {
+    input: &'input Input,
+    out: TokenStream,
+}
+
+impl<'input> PreludeGenerator<'input> {
+    fn new(input: &'input Input) -> Self {
+        Self {
+            input,
+            out: TokenStream::new(),
+        }
+    }
+
+    fn finish(self) -> TokenStream {
+        self.out
+    }
+
+    fn add_imports(mut self) -> Self {
+        if let Some(imports) = self.input.imports().destructure(&ARGS) {
+            let head = self.out;
+            self.out = quote! {
+                #head
+                #imports
+            };
+        }
+
+        self
+    }
+
+    fn add_options(mut self) -> Self {
+        let options = WriteOptionsGenerator::new(OPT)
+            .endian(self.input.endian())
+            .finish();
+
+        if !options.is_empty() {
+            let head = self.out;
+            self.out = quote! {
+                #head
+                #options
+            };
+        }
+
+        self
+    }
+
+    fn add_magic(mut self) -> Self {
+        let magic = get_magic(self.input.magic(), &OPT);
+        let head = self.out;
+        self.out = quote! {
+            #head
+            #magic
+        };
+
+        self
+    }
+}
+
+fn get_magic(magic: &Magic, options_var: &impl ToTokens) -> Option<TokenStream> {
+    magic.as_ref().map(|magic| {
+        let magic = magic.deref_value();
+        quote! {
+            #WRITE_METHOD(&#magic, #WRITER, #options_var, ())?;
+        }
+    })
+}
+
+struct WriteOptionsGenerator {
+    out: TokenStream,
+    options_var: TokenStream,
+}
+
+impl WriteOptionsGenerator {
+    fn new(options_var: impl quote::ToTokens) -> Self {
+        Self {
+            out: TokenStream::new(),
+            options_var: options_var.into_token_stream(),
+        }
+    }
+
+    fn endian(mut self, endian: &CondEndian) -> Self {
+        if let Some(endian) = get_endian(endian) {
+            let head = self.out;
+            self.out = quote! {
+                #head
+                #TEMP.endian = #endian;
+            };
+        }
+
+        self
+    }
+
+    fn finish(self) -> TokenStream {
+        let options_var = self.options_var;
+        if self.out.is_empty() {
+            quote! {
+                let #options_var = #OPT;
+            }
+        } else {
+            let setters = self.out;
+            quote! {
+                let #options_var = &{
+                    let mut #TEMP = *#OPT;
+                    #setters
+                    #TEMP
+                };
+            }
+        }
+    }
+}
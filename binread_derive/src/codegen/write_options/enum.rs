@@ -0,0 +1,95 @@
+use super::{
+    r#struct::{generate_unit_struct, StructGenerator},
+    PreludeGenerator,
+};
+#[allow(clippy::wildcard_imports)]
+use crate::codegen::sanitization::*;
+use crate::parser::{Enum, EnumVariant, Input, UnitEnumField, UnitOnlyEnum};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub(super) fn generate_unit_enum(input: &Input, en: &UnitOnlyEnum) -> TokenStream {
+    match &en.repr {
+        Some(repr) => generate_unit_enum_repr(input, repr, &en.fields),
+        None => generate_unit_enum_magic(input, &en.fields),
+    }
+}
+
+fn generate_unit_enum_repr(
+    input: &Input,
+    repr: &TokenStream,
+    variants: &[UnitEnumField],
+) -> TokenStream {
+    let clauses = variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        quote! {
+            Self::#ident => Self::#ident as #repr
+        }
+    });
+
+    let prelude = PreludeGenerator::new(input).add_options().finish();
+
+    quote! {
+        #prelude
+        let #TEMP: #repr = match self {
+            #(#clauses,)*
+        };
+        #WRITE_METHOD(&#TEMP, #WRITER, #OPT, ())
+    }
+}
+
+fn generate_unit_enum_magic(input: &Input, variants: &[UnitEnumField]) -> TokenStream {
+    let prelude = PreludeGenerator::new(input)
+        .add_imports()
+        .add_options()
+        .finish();
+
+    let matches = variants.iter().map(|field| {
+        let ident = &field.ident;
+        if let Some(magic) = &field.magic {
+            let magic = magic.deref_value();
+            quote! { Self::#ident => #WRITE_METHOD(&#magic, #WRITER, #OPT, ()) }
+        } else {
+            // Variants without magic can never be read, so there is nothing
+            // that could be written for them either
+            quote! {
+                Self::#ident => Err(#BIN_ERROR::NoVariantMatch {
+                    pos: #POS_TRAIT::stream_pos(#WRITER)?,
+                })
+            }
+        }
+    });
+
+    quote! {
+        #prelude
+        match self {
+            #(#matches,)*
+        }
+    }
+}
+
+pub(super) fn generate_data_enum(en: &Enum) -> TokenStream {
+    let variants = en
+        .variants
+        .iter()
+        .map(|variant| generate_variant_impl(en, variant));
+
+    quote! {
+        match self {
+            #(#variants)*
+        }
+    }
+}
+
+fn generate_variant_impl(en: &Enum, variant: &EnumVariant) -> TokenStream {
+    let input = Input::Enum(en.with_variant(variant));
+
+    match variant {
+        EnumVariant::Variant { ident, options } => StructGenerator::new(&input, options)
+            .write_fields()
+            .destructure(Some(ident))
+            .finish(),
+
+        EnumVariant::Unit(options) => generate_unit_struct(&input, Some(&options.ident)),
+    }
+}
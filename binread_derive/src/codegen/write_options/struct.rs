@@ -0,0 +1,446 @@
+use super::{get_magic, PreludeGenerator, WriteOptionsGenerator};
+#[allow(clippy::wildcard_imports)]
+use crate::codegen::{get_bit_order, get_passed_args, sanitization::*, wrap_endian_from};
+use crate::parser::{Input, Map, ReadMode, Struct, StructField};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{spanned::Spanned, Ident};
+
+pub(super) fn generate_unit_struct(input: &Input, variant_ident: Option<&Ident>) -> TokenStream {
+    let prelude = get_prelude(input);
+    let body = quote! {
+        #prelude
+        Ok(())
+    };
+
+    match variant_ident {
+        Some(ident) => quote! { Self::#ident => { #body } },
+        None => body,
+    }
+}
+
+pub(super) fn generate_struct(input: &Input, st: &Struct) -> TokenStream {
+    StructGenerator::new(input, st)
+        .write_fields()
+        .destructure(None)
+        .finish()
+}
+
+pub(super) struct StructGenerator<'input> {
+    input: &'input Input,
+    st: &'input Struct,
+    out: TokenStream,
+}
+
+impl<'input> StructGenerator<'input> {
+    pub(super) fn new(input: &'input Input, st: &'input Struct) -> Self {
+        Self {
+            input,
+            st,
+            out: TokenStream::new(),
+        }
+    }
+
+    pub(super) fn finish(self) -> TokenStream {
+        self.out
+    }
+
+    /// Binds a reference to every field of `self` and wraps the generated
+    /// body so that it only runs for the matching variant, if any.
+    pub(super) fn destructure(mut self, variant_ident: Option<&Ident>) -> Self {
+        let path = variant_ident.map_or_else(|| quote! { Self }, |ident| quote! { Self::#ident });
+        let refs = self
+            .st
+            .fields
+            .iter()
+            .map(|field| make_ident(&field.ident, "ref"));
+        let pattern = if self.st.is_tuple() {
+            quote! { #path(#(#refs),*) }
+        } else {
+            let idents = self.st.fields.iter().map(|field| &field.ident);
+            quote! { #path { #(#idents: #refs),* } }
+        };
+
+        let body = self.out;
+        self.out = if variant_ident.is_some() {
+            quote! { #pattern => { #body } }
+        } else {
+            quote! {
+                let #pattern = self;
+                #body
+            }
+        };
+
+        self
+    }
+
+    pub(super) fn write_fields(mut self) -> Self {
+        let prelude = get_prelude(self.input);
+        let bind_fields = self.st.fields.iter().map(|field| {
+            let ident = &field.ident;
+            let ref_ident = make_ident(ident, "ref");
+            quote! {
+                let #ident = (&#FIELD_REF(#ref_ident)).field_value();
+            }
+        });
+        let has_bits = self.st.has_bits();
+        let (bit_writer, bit_flush) = if has_bits {
+            let bit_order = get_bit_order(self.input.bit_order());
+            (
+                Some(quote! { let mut #BIT_WRITER = #BIT_WRITER_TYPE::new(#bit_order); }),
+                Some(quote! { #BIT_WRITER.flush(#WRITER)?; }),
+            )
+        } else {
+            (None, None)
+        };
+        let write_fields = self.st.fields.iter().map(|field| {
+            let write_field = generate_field(field, has_bits);
+            if self.st.endian.marker_field() == Some(&field.ident) {
+                let ref_ident = make_ident(&field.ident, "ref");
+                wrap_endian_from(
+                    &self.st.endian,
+                    write_field,
+                    &quote! { #POS_TRAIT::stream_pos(#WRITER) },
+                    &quote! { #ref_ident },
+                )
+            } else {
+                write_field
+            }
+        });
+
+        self.out = quote! {
+            #prelude
+            #(#bind_fields)*
+            #bit_writer
+            #(#write_fields)*
+            #bit_flush
+            Ok(())
+        };
+
+        self
+    }
+}
+
+fn generate_field(field: &StructField, has_bits: bool) -> TokenStream {
+    let args_var = make_ident(&field.ident, "args");
+    let options_var = make_ident(&field.ident, "options");
+    FieldGenerator::new(field)
+        .write_value(&options_var, &args_var)
+        .prefix_count_check()
+        .map_value()
+        .wrap_condition()
+        .wrap_seek()
+        .wrap_restore_position()
+        .prefix_magic(&options_var)
+        .prefix_args_and_options(&options_var, &args_var)
+        .prefix_bit_flush(has_bits)
+        .finish()
+}
+
+struct FieldGenerator<'field> {
+    field: &'field StructField,
+    out: TokenStream,
+}
+
+impl<'field> FieldGenerator<'field> {
+    fn new(field: &'field StructField) -> Self {
+        Self {
+            field,
+            out: TokenStream::new(),
+        }
+    }
+
+    fn finish(self) -> TokenStream {
+        let out = self.out;
+        quote! {{
+            #out
+        }}
+    }
+
+    /// Checks that a field read with `count` has that many items, so that it
+    /// reads back the same.
+    fn prefix_count_check(mut self) -> Self {
+        if let (Some(count), false) = (&self.field.count, self.out.is_empty()) {
+            let write = self.out;
+            self.out = quote! {
+                #CHECK_COUNT(
+                    #POS_TRAIT::stream_pos(#WRITER)?,
+                    (&#COUNT_REF(#TEMP)).written_count(),
+                    (#count) as usize,
+                )?;
+                #write
+            };
+        }
+
+        self
+    }
+
+    fn map_value(mut self) -> Self {
+        if self.out.is_empty() {
+            return self;
+        }
+
+        let ref_ident = make_ident(&self.field.ident, "ref");
+        let value = match (&self.field.write_map, &self.field.map) {
+            (Some(write_map), _) => quote! { &(#write_map)(#ref_ident) },
+            (None, Map::None) => quote! { #ref_ident },
+            (None, Map::Map(map) | Map::Try(map)) => {
+                self.out = super::missing_write_map(map);
+                return self;
+            }
+        };
+
+        let write = self.out;
+        self.out = quote! {
+            let #TEMP = #value;
+            #write
+        };
+
+        self
+    }
+
+    fn prefix_args_and_options(mut self, options_var: &Ident, args_var: &Ident) -> Self {
+        if self.field.bits.is_some() {
+            return self;
+        }
+
+        let writer = self.field.write_with.as_ref().map(|writer| {
+            let writer_var = make_ident(&self.field.ident, "write_with");
+            quote! { let #writer_var = #writer; }
+        });
+        let args_type = self.args_type();
+        let args = get_passed_args(&self.field.args, args_type.is_some(), || {
+            self.named_args_builder()
+        });
+        let options = WriteOptionsGenerator::new(options_var)
+            .endian(&self.field.endian)
+            .finish();
+        let tail = self.out;
+        self.out = quote! {
+            #writer
+            let #args_var #args_type = #args;
+            #options
+            #tail
+        };
+
+        self
+    }
+
+    /// Returns a type annotation for the arguments variable when the type
+    /// being written is known, so that borrowed arguments are coerced to the
+    /// expected types.
+    fn args_type(&self) -> Option<TokenStream> {
+        let ty = &self.field.ty;
+        match (
+            &self.field.read_mode,
+            &self.field.write_with,
+            &self.field.write_map,
+        ) {
+            (ReadMode::Normal, None, None) => {
+                Some(quote! { : <#ty as #WRITE_TRAIT_NAME>::Args<'_> })
+            }
+            _ => None,
+        }
+    }
+
+    fn named_args_builder(&self) -> TokenStream {
+        let ty = &self.field.ty;
+        match (&self.field.write_with, &self.field.write_map) {
+            (Some(_), _) => {
+                let writer_var = make_ident(&self.field.ident, "write_with");
+                quote! { #WRITE_FN_BUILDER::<W, _, _, _>(&#writer_var) }
+            }
+            (None, None) => {
+                quote! { <<#ty as #WRITE_TRAIT_NAME>::Args<'_> as #NAMED_ARGS>::builder() }
+            }
+            (None, Some(write_map)) => syn::Error::new(
+                write_map.span(),
+                "named arguments can not be used with `write_map` because the type \
+                 being written is unknown; use `write_with` or `args_tuple` instead",
+            )
+            .to_compile_error(),
+        }
+    }
+
+    /// Writes out the rest of a partially written byte when a field after a
+    /// run of `bits` fields writes whole bytes again.
+    fn prefix_bit_flush(mut self, has_bits: bool) -> Self {
+        if has_bits && self.field.ends_bit_run() {
+            let tail = self.out;
+            self.out = quote! {
+                #BIT_WRITER.flush(#WRITER)?;
+                #tail
+            };
+        }
+
+        self
+    }
+
+    fn prefix_magic(mut self, options_var: &Ident) -> Self {
+        if let Some(magic) = get_magic(&self.field.magic, options_var) {
+            let tail = self.out;
+            self.out = quote! {
+                #magic
+                #tail
+            };
+        }
+
+        self
+    }
+
+    fn wrap_condition(mut self) -> Self {
+        if let Some(cond) = &self.field.if_cond {
+            if !self.out.is_empty() {
+                let condition = &cond.condition;
+                let consequent = self.out;
+                self.out = quote! {
+                    if #condition {
+                        #consequent
+                    }
+                };
+            }
+        }
+
+        self
+    }
+
+    fn wrap_restore_position(mut self) -> Self {
+        if self.field.restore_position {
+            self.out = wrap_save_restore(&self.out);
+        }
+
+        self
+    }
+
+    fn wrap_seek(mut self) -> Self {
+        let seek_before = generate_seek_before(self.field);
+        let seek_after = generate_seek_after(self.field);
+        let value = self.out;
+        self.out = quote! {
+            #seek_before
+            #value
+            #seek_after
+        };
+
+        self
+    }
+
+    fn write_value(mut self, options_var: &Ident, args_var: &Ident) -> Self {
+        self.out = match &self.field.read_mode {
+            // Values which are not read from the stream are not written back
+            ReadMode::Default | ReadMode::Calc(_) => return self,
+            ReadMode::Normal if self.field.bits.is_some() => {
+                let bits = self.field.bits.as_deref();
+                quote! {
+                    #BIT_WRITER.write(#WRITER, #bits, #TEMP)?;
+                }
+            }
+            ReadMode::Normal | ReadMode::ParseWith(_) => {
+                let write_method = match (&self.field.write_with, &self.field.read_mode) {
+                    (Some(_), _) => make_ident(&self.field.ident, "write_with").into_token_stream(),
+                    (None, ReadMode::ParseWith(parser)) => {
+                        self.out = syn::Error::new(
+                            parser.span(),
+                            "`parse_with` requires a matching `write_with` to derive BinWrite",
+                        )
+                        .to_compile_error();
+                        return self;
+                    }
+                    (None, _) => quote! { #WRITE_METHOD },
+                };
+
+                quote! {
+                    #write_method(#TEMP, #WRITER, #options_var, #args_var.clone())?;
+                }
+            }
+        };
+
+        self
+    }
+}
+
+fn get_prelude(input: &Input) -> TokenStream {
+    PreludeGenerator::new(input)
+        .add_imports()
+        .add_options()
+        .add_magic()
+        .finish()
+}
+
+fn generate_seek_after(field: &StructField) -> TokenStream {
+    let size = field.size.is_some().then(|| {
+        quote! {{
+            let end = #POS_TRAIT::stream_pos(#WRITER)?;
+            #CHECK_SIZE(#POS, end, #SIZE_LIMIT, false)?;
+            #WRITE_ZEROES(#WRITER, #POS + #SIZE_LIMIT - end)?;
+        }}
+    });
+    let pad_size_to = field.pad_size_to.as_ref().map(|pad| {
+        quote! {{
+            let pad = (#pad) as u64;
+            let size = #POS_TRAIT::stream_pos(#WRITER)? - #POS;
+            if size < pad {
+                #WRITE_ZEROES(#WRITER, pad - size)?;
+            }
+        }}
+    });
+    let pad_after = field.pad_after.as_ref().map(map_pad);
+    let align_after = field.align_after.as_ref().map(map_align);
+
+    quote! {
+        #size
+        #pad_size_to
+        #pad_after
+        #align_after
+    }
+}
+
+fn generate_seek_before(field: &StructField) -> TokenStream {
+    let seek_before = field.seek_before.as_ref().map(|seek| {
+        quote! {
+            #SEEK_TRAIT::seek(#WRITER, #seek)?;
+        }
+    });
+    let pad_before = field.pad_before.as_ref().map(map_pad);
+    let align_before = field.align_before.as_ref().map(map_align);
+    let pad_size_to_before = (field.pad_size_to.is_some() || field.size.is_some()).then(|| {
+        quote! {
+            let #POS = #POS_TRAIT::stream_pos(#WRITER)?;
+        }
+    });
+    let size_before = field.size.expr().map(|size| {
+        quote! {
+            let #SIZE_LIMIT = (#size) as u64;
+        }
+    });
+
+    quote! {
+        #seek_before
+        #pad_before
+        #align_before
+        #pad_size_to_before
+        #size_before
+    }
+}
+
+fn map_align(align: &TokenStream) -> TokenStream {
+    quote! {{
+        let align = (#align) as u64;
+        let pos = #POS_TRAIT::stream_pos(#WRITER)?;
+        #WRITE_ZEROES(#WRITER, (align - (pos % align)) % align)?;
+    }}
+}
+
+fn map_pad(pad: &TokenStream) -> TokenStream {
+    quote! {
+        #WRITE_ZEROES(#WRITER, (#pad) as u64)?;
+    }
+}
+
+fn wrap_save_restore(value: &TokenStream) -> TokenStream {
+    quote! {
+        let #SAVED_POSITION = #POS_TRAIT::stream_pos(#WRITER)?;
+        #value
+        #SEEK_TRAIT::seek(#WRITER, #SEEK_FROM::Start(#SAVED_POSITION))?;
+    }
+}
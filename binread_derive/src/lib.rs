@@ -4,7 +4,7 @@
 mod codegen;
 mod parser;
 
-use codegen::generate_impl;
+use codegen::{generate_async_impl, generate_borrowed_impl, generate_impl, generate_write_impl};
 use parser::{is_binread_attr, Input};
 use proc_macro::TokenStream;
 use quote::quote;
@@ -15,11 +15,49 @@ pub fn derive_binread_trait(input: TokenStream) -> TokenStream {
     derive_binread_internal(parse_macro_input!(input as DeriveInput)).into()
 }
 
+// Taken by value to match the signature expected by `runtime-macros-derive`
+#[allow(clippy::needless_pass_by_value)]
 fn derive_binread_internal(input: DeriveInput) -> proc_macro2::TokenStream {
     let binread_input = Input::from_input(&input);
     generate_impl(&input, &binread_input)
 }
 
+#[proc_macro_derive(BinReadAsync, attributes(binread, br))]
+pub fn derive_binread_async_trait(input: TokenStream) -> TokenStream {
+    derive_binread_async_internal(parse_macro_input!(input as DeriveInput)).into()
+}
+
+// Taken by value to match the signature expected by `runtime-macros-derive`
+#[allow(clippy::needless_pass_by_value)]
+fn derive_binread_async_internal(input: DeriveInput) -> proc_macro2::TokenStream {
+    let binread_input = Input::from_input(&input);
+    generate_async_impl(&input, &binread_input)
+}
+
+#[proc_macro_derive(BinReadBorrowed, attributes(binread, br))]
+pub fn derive_binread_borrowed_trait(input: TokenStream) -> TokenStream {
+    derive_binread_borrowed_internal(parse_macro_input!(input as DeriveInput)).into()
+}
+
+// Taken by value to match the signature expected by `runtime-macros-derive`
+#[allow(clippy::needless_pass_by_value)]
+fn derive_binread_borrowed_internal(input: DeriveInput) -> proc_macro2::TokenStream {
+    let binread_input = Input::from_input(&input);
+    generate_borrowed_impl(&input, &binread_input)
+}
+
+#[proc_macro_derive(BinWrite, attributes(binread, br))]
+pub fn derive_binwrite_trait(input: TokenStream) -> TokenStream {
+    derive_binwrite_internal(parse_macro_input!(input as DeriveInput)).into()
+}
+
+// Taken by value to match the signature expected by `runtime-macros-derive`
+#[allow(clippy::needless_pass_by_value)]
+fn derive_binwrite_internal(input: DeriveInput) -> proc_macro2::TokenStream {
+    let binread_input = Input::from_input(&input);
+    generate_write_impl(&input, &binread_input)
+}
+
 #[proc_macro_attribute]
 pub fn derive_binread(_: TokenStream, input: TokenStream) -> TokenStream {
     let mut derive_input = parse_macro_input!(input as DeriveInput);
@@ -31,16 +69,16 @@ pub fn derive_binread(_: TokenStream, input: TokenStream) -> TokenStream {
 
     match &mut derive_input.data {
         syn::Data::Struct(input_struct) => {
-            clean_field_attrs(&binread_input, 0, &mut input_struct.fields);
+            clean_field_attrs(binread_input.as_ref(), 0, &mut input_struct.fields);
         }
         syn::Data::Enum(input_enum) => {
             for (index, variant) in input_enum.variants.iter_mut().enumerate() {
                 clean_struct_attrs(&mut variant.attrs);
-                clean_field_attrs(&binread_input, index, &mut variant.fields);
+                clean_field_attrs(binread_input.as_ref(), index, &mut variant.fields);
             }
         }
         syn::Data::Union(union) => {
-            for field in union.fields.named.iter_mut() {
+            for field in &mut union.fields.named {
                 clean_struct_attrs(&mut field.attrs);
             }
         }
@@ -54,7 +92,7 @@ pub fn derive_binread(_: TokenStream, input: TokenStream) -> TokenStream {
 }
 
 fn clean_field_attrs(
-    binread_input: &Option<Input>,
+    binread_input: Option<&Input>,
     variant_index: usize,
     fields: &mut syn::Fields,
 ) {
@@ -110,8 +148,32 @@ mod tests {
                 emulate_derive_expansion_fallible(file, "BinRead", super::derive_binread_internal)
                     .is_ok();
             run_success &= is_ok;
+            let file = fs::File::open(entry.path()).unwrap();
+            let is_ok = emulate_derive_expansion_fallible(
+                file,
+                "BinReadAsync",
+                super::derive_binread_async_internal,
+            )
+            .is_ok();
+            run_success &= is_ok;
+            let file = fs::File::open(entry.path()).unwrap();
+            let is_ok = emulate_derive_expansion_fallible(
+                file,
+                "BinReadBorrowed",
+                super::derive_binread_borrowed_internal,
+            )
+            .is_ok();
+            run_success &= is_ok;
+            let file = fs::File::open(entry.path()).unwrap();
+            let is_ok = emulate_derive_expansion_fallible(
+                file,
+                "BinWrite",
+                super::derive_binwrite_internal,
+            )
+            .is_ok();
+            run_success &= is_ok;
         }
 
-        assert!(run_success)
+        assert!(run_success);
     }
 }
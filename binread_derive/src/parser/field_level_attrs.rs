@@ -1,5 +1,6 @@
 use super::{
-    types::{Assert, CondEndian, Condition, Magic, Map, PassedArgs, ReadMode},
+    is_binread_attr,
+    types::{Assert, CondEndian, Condition, Magic, Map, PassedArgs, ReadMode, Size},
     FromAttrs, FromField, FromInput, ParseResult, SpannedValue, Struct, TrySet,
 };
 use proc_macro2::TokenStream;
@@ -12,6 +13,10 @@ attr_struct! {
         pub(crate) ident: syn::Ident,
         pub(crate) generated_ident: bool,
         pub(crate) ty: syn::Type,
+        // The span of the first `#[br]` attribute of the field, or of the
+        // field itself if it has none. Used to report the source line of the
+        // field in error backtraces.
+        pub(crate) attr_span: proc_macro2::Span,
         #[from(Big, Little, IsBig, IsLittle)]
         pub(crate) endian: CondEndian,
         #[from(Map, TryMap)]
@@ -22,6 +27,10 @@ attr_struct! {
         pub(crate) args: PassedArgs,
         #[from(Calc, Default, Ignore, ParseWith)]
         pub(crate) read_mode: ReadMode,
+        #[from(ParseWithAsync)]
+        pub(crate) parse_with_async: Option<TokenStream>,
+        #[from(Bits)]
+        pub(crate) bits: Option<SpannedValue<TokenStream>>,
         #[from(Count)]
         pub(crate) count: Option<TokenStream>,
         #[from(Offset)]
@@ -52,23 +61,84 @@ attr_struct! {
         pub(crate) seek_before: Option<TokenStream>,
         #[from(PadSizeTo)]
         pub(crate) pad_size_to: Option<TokenStream>,
+        #[from(Size, SizeExact)]
+        pub(crate) size: Size,
+        #[from(WriteMap)]
+        pub(crate) write_map: Option<TokenStream>,
+        #[from(WriteWith)]
+        pub(crate) write_with: Option<TokenStream>,
     }
 }
 
 impl StructField {
     pub(crate) fn can_call_after_parse(&self) -> bool {
-        matches!(self.read_mode, ReadMode::Normal) && !self.map.is_some()
+        matches!(self.read_mode, ReadMode::Normal) && !self.map.is_some() && self.bits.is_none()
     }
 
     pub(crate) fn should_use_after_parse(&self) -> bool {
         !*self.deref_now
     }
 
+    /// The name of the field as written in the source, which is its index for
+    /// tuple fields
+    pub(crate) fn source_name(&self) -> String {
+        let name = self.ident.to_string();
+        if self.generated_ident {
+            name.trim_start_matches("self_").to_owned()
+        } else {
+            name
+        }
+    }
+
     pub(crate) fn generated_value(&self) -> bool {
         matches!(self.read_mode, ReadMode::Calc(_) | ReadMode::Default)
     }
 
+    /// Returns `true` if the field reads whole bytes from the stream, which
+    /// ends any run of `bits` fields before it.
+    pub(crate) fn ends_bit_run(&self) -> bool {
+        self.bits.is_none() && !self.generated_value()
+    }
+
     fn validate(&self) -> syn::Result<()> {
+        if let Some(bits) = &self.bits {
+            if !matches!(self.read_mode, ReadMode::Normal) {
+                return Err(syn::Error::new(
+                    bits.span(),
+                    "`bits` can not be used with `calc`, `default`, `ignore`, or `parse_with`",
+                ));
+            }
+
+            if !matches!(self.args, PassedArgs::None) || self.magic.is_some() {
+                return Err(syn::Error::new(
+                    bits.span(),
+                    "`bits` can not be used with `args`, `args_tuple`, or `magic`",
+                ));
+            }
+
+            if self.size.is_some() {
+                return Err(syn::Error::new(
+                    bits.span(),
+                    "`bits` can not be used with `size` or `size_exact`",
+                ));
+            }
+
+            if self.seek_before.is_some()
+                || self.pad_before.is_some()
+                || self.pad_after.is_some()
+                || self.align_before.is_some()
+                || self.align_after.is_some()
+                || self.pad_size_to.is_some()
+                || self.restore_position
+            {
+                return Err(syn::Error::new(
+                    bits.span(),
+                    "`bits` can not be used with `seek_before`, `pad_before`, `pad_after`, \
+                     `align_before`, `align_after`, `pad_size_to`, or `restore_position`",
+                ));
+            }
+        }
+
         if let (Some(offset_after), true) = (&self.offset_after, *self.deref_now) {
             let offset_after_span = offset_after.span();
             let span = offset_after_span
@@ -96,11 +166,18 @@ impl FromField for StructField {
                     .unwrap_or_else(|| quote::format_ident!("self_{}", index)),
                 generated_ident: field.ident.is_none(),
                 ty: field.ty.clone(),
+                attr_span: field
+                    .attrs
+                    .iter()
+                    .find(|attr| is_binread_attr(attr))
+                    .map_or_else(|| field.span(), Spanned::span),
                 endian: <_>::default(),
                 map: <_>::default(),
                 magic: <_>::default(),
                 args: <_>::default(),
                 read_mode: <_>::default(),
+                parse_with_async: <_>::default(),
+                bits: <_>::default(),
                 count: <_>::default(),
                 offset: <_>::default(),
                 offset_after: <_>::default(),
@@ -116,6 +193,9 @@ impl FromField for StructField {
                 align_after: <_>::default(),
                 seek_before: <_>::default(),
                 pad_size_to: <_>::default(),
+                size: <_>::default(),
+                write_map: <_>::default(),
+                write_with: <_>::default(),
             },
             &field.attrs,
         );
@@ -168,14 +248,17 @@ impl FromField for UnitEnumField {
 
 #[derive(Clone, Debug)]
 pub(crate) enum EnumVariant {
-    Variant { ident: syn::Ident, options: Struct },
+    Variant {
+        ident: syn::Ident,
+        options: Box<Struct>,
+    },
     Unit(UnitEnumField),
 }
 
 impl EnumVariant {
     pub(crate) fn ident(&self) -> &syn::Ident {
         match self {
-            EnumVariant::Variant { ident, .. } => &ident,
+            EnumVariant::Variant { ident, .. } => ident,
             EnumVariant::Unit(field) => &field.ident,
         }
     }
@@ -190,7 +273,7 @@ impl FromField for EnumVariant {
                 Struct::from_input(&variant.attrs, variant.fields.iter()).map(|options| {
                     Self::Variant {
                         ident: variant.ident.clone(),
-                        options,
+                        options: Box::new(options),
                     }
                 })
             }
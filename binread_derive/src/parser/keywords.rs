@@ -14,25 +14,30 @@ define_keywords! {
     assert,
     big,
     binread,
+    bits,
     br,
     calc,
     count,
     default,
     deref_now,
+    endian_from,
     ignore,
     import,
     import_tuple,
     is_big,
     is_little,
     little,
+    lsb_first,
     magic,
     map,
+    msb_first,
     offset,
     offset_after,
     pad_after,
     pad_before,
     pad_size_to,
     parse_with,
+    parse_with_async,
     postprocess_now,
     pre_assert,
     repr,
@@ -40,6 +45,10 @@ define_keywords! {
     return_all_errors,
     return_unexpected_error,
     seek_before,
+    size,
+    size_exact,
     temp,
     try_map,
+    write_map,
+    write_with,
 }
@@ -1,11 +1,11 @@
-use super::KeywordToken;
+use super::{keywords as kw, KeywordToken};
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{
-    parenthesized,
+    braced, parenthesized,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
-    token, Expr, Lit, Token, Type,
+    token, Expr, Lit, LitStr, Token, Type,
 };
 
 type Fields<T> = Punctuated<T, Token![,]>;
@@ -14,21 +14,24 @@ type Fields<T> = Punctuated<T, Token![,]>;
 /// Takes two forms:
 /// * ident(expr)
 /// * ident = expr
-/// both are always allowed
+///
+/// Both are always allowed.
 pub(crate) type MetaExpr<Keyword> = MetaValue<Keyword, Expr>;
 
 /// `MetaType` represents a key/ty pair
 /// Takes two forms:
 /// * ident(ty)
 /// * ident = ty
-/// both are always allowed
+///
+/// Both are always allowed.
 pub(crate) type MetaType<Keyword> = MetaValue<Keyword, Type>;
 
 /// `MetaLit` represents a key/lit pair
 /// Takes two forms:
 /// * ident(lit)
 /// * ident = lit
-/// both are always allowed
+///
+/// Both are always allowed.
 pub(crate) type MetaLit<Keyword> = MetaValue<Keyword, Lit>;
 
 #[derive(Debug, Clone)]
@@ -76,7 +79,6 @@ impl<Keyword: syn::token::Token + KeywordToken, Value> KeywordToken for MetaValu
 #[derive(Debug, Clone)]
 pub(crate) struct MetaList<Keyword, ItemType> {
     pub(crate) ident: Keyword,
-    pub(crate) parens: token::Paren,
     pub(crate) fields: Fields<ItemType>,
 }
 
@@ -84,10 +86,9 @@ impl<Keyword: Parse, ItemType: Parse> Parse for MetaList<Keyword, ItemType> {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
         let ident = input.parse()?;
         let content;
-        let parens = parenthesized!(content in input);
+        parenthesized!(content in input);
         Ok(MetaList {
             ident,
-            parens,
             fields: content.parse_terminated::<_, Token![,]>(ItemType::parse)?,
         })
     }
@@ -103,6 +104,53 @@ impl<Keyword: syn::token::Token + KeywordToken, ItemType> KeywordToken
     }
 }
 
+/// `MetaEnclosedList` represents a keyword followed by a list whose item type
+/// depends on how the list is enclosed
+/// Takes two forms:
+/// * ident(item, ...)
+/// * ident { item, ... }
+#[derive(Debug, Clone)]
+pub(crate) struct MetaEnclosedList<Keyword, ParenItem, BraceItem> {
+    pub(crate) ident: Keyword,
+    pub(crate) list: Enclosure<ParenItem, BraceItem>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Enclosure<ParenItem, BraceItem> {
+    Paren(Fields<ParenItem>),
+    Brace(Fields<BraceItem>),
+}
+
+impl<Keyword: Parse, ParenItem: Parse, BraceItem: Parse> Parse
+    for MetaEnclosedList<Keyword, ParenItem, BraceItem>
+{
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        let content;
+        let list = if input.peek(token::Paren) {
+            parenthesized!(content in input);
+            Enclosure::Paren(content.parse_terminated::<_, Token![,]>(ParenItem::parse)?)
+        } else if input.peek(token::Brace) {
+            braced!(content in input);
+            Enclosure::Brace(content.parse_terminated::<_, Token![,]>(BraceItem::parse)?)
+        } else {
+            return Err(input.error("expected `(` or `{`"));
+        };
+
+        Ok(MetaEnclosedList { ident, list })
+    }
+}
+
+impl<Keyword: syn::token::Token + KeywordToken, ParenItem, BraceItem> KeywordToken
+    for MetaEnclosedList<Keyword, ParenItem, BraceItem>
+{
+    type Token = Keyword;
+
+    fn keyword_span(&self) -> proc_macro2::Span {
+        self.ident.keyword_span()
+    }
+}
+
 // This is like `syn::PatType` except:
 // (1) Implements `Parse`;
 // (2) No attributes;
@@ -110,20 +158,116 @@ impl<Keyword: syn::token::Token + KeywordToken, ItemType> KeywordToken
 #[derive(Debug, Clone)]
 pub(crate) struct IdentPatType {
     pub(crate) ident: syn::Ident,
-    pub(crate) colon_token: Token![:],
     pub(crate) ty: syn::Type,
 }
 
 impl Parse for IdentPatType {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
         Ok(IdentPatType {
-            ident: input.parse()?,
-            colon_token: input.parse()?,
+            ident,
             ty: input.parse()?,
         })
     }
 }
 
+// This is like `IdentPatType` except it also accepts a default value, which is
+// used by named arguments.
+#[derive(Debug, Clone)]
+pub(crate) struct IdentTypeMaybeDefault {
+    pub(crate) ident: syn::Ident,
+    pub(crate) ty: syn::Type,
+    pub(crate) default: Option<Box<Expr>>,
+}
+
+impl Parse for IdentTypeMaybeDefault {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        let default = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(Box::new(input.parse()?))
+        } else {
+            None
+        };
+
+        Ok(IdentTypeMaybeDefault { ident, ty, default })
+    }
+}
+
+// This is like `syn::FieldValue` except:
+// (1) No attributes;
+// (2) Only allows an ident on the LHS instead of any `syn::Member`.
+#[derive(Debug, Clone)]
+pub(crate) struct IdentExpr {
+    pub(crate) ident: syn::Ident,
+    pub(crate) expr: Expr,
+}
+
+impl Parse for IdentExpr {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident: syn::Ident = input.parse()?;
+        let expr = if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            input.parse()?
+        } else {
+            syn::parse_quote! { #ident }
+        };
+
+        Ok(IdentExpr { ident, expr })
+    }
+}
+
+/// `EndianMarker` represents the field holding a byte order marker and the
+/// values of it for each byte order
+/// Takes two forms, with the values in either order:
+/// * field, big = expr, little = expr
+/// * "field", big = expr, little = expr
+///
+/// The values are parsed greedily, so that `endian_from = "field", big = ...`
+/// also works where they look like separate directives.
+#[derive(Debug, Clone)]
+pub(crate) struct EndianMarker {
+    pub(crate) field: syn::Ident,
+    pub(crate) big: Box<Expr>,
+    pub(crate) little: Box<Expr>,
+}
+
+impl Parse for EndianMarker {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let field: syn::Ident = if input.peek(LitStr) {
+            input.parse::<LitStr>()?.parse()?
+        } else {
+            input.parse()?
+        };
+
+        let mut big = None;
+        let mut little = None;
+        while input.peek(Token![,]) && (input.peek2(kw::big) || input.peek2(kw::little)) {
+            input.parse::<Token![,]>()?;
+            let (span, value) = if input.peek(kw::big) {
+                (input.parse::<kw::big>()?.span, &mut big)
+            } else {
+                (input.parse::<kw::little>()?.span, &mut little)
+            };
+            input.parse::<Token![=]>()?;
+            if value.replace(Box::new(input.parse()?)).is_some() {
+                return Err(syn::Error::new(span, "conflicting byte order marker value"));
+            }
+        }
+
+        match (big, little) {
+            (Some(big), Some(little)) => Ok(EndianMarker { field, big, little }),
+            _ => Err(syn::Error::new(
+                field.span(),
+                "expected a `big = ...` and a `little = ...` value for the byte order marker",
+            )),
+        }
+    }
+}
+
 pub(crate) struct MetaAttrList<P>(Fields<P>);
 
 impl<P> MetaAttrList<P> {
@@ -141,6 +285,7 @@ impl<P: Parse> Parse for MetaAttrList<P> {
 }
 
 #[cfg(test)]
+#[allow(clippy::should_panic_without_expect)]
 mod tests {
     use super::*;
 
@@ -152,6 +297,7 @@ mod tests {
     type MetaValueTest = MetaValue<kw::test, Lit>;
     type MetaListTest = MetaList<kw::test_list, Lit>;
     type MetaAttrListTest = MetaAttrList<Lit>;
+    type MetaEnclosedListTest = MetaEnclosedList<kw::test_list, Lit, IdentPatType>;
 
     macro_rules! try_parse {
         ($name:ident, $ty:ty, $tt:tt) => {
@@ -232,11 +378,40 @@ mod tests {
         );
     }
 
+    try_parse!(meta_enclosed_list_paren, MetaEnclosedListTest, {
+        test_list(3u8, 3u8)
+    });
+    try_parse!(meta_enclosed_list_brace, MetaEnclosedListTest, {
+        test_list { a: u8, b: u8 }
+    });
+    try_parse_fail!(meta_enclosed_list_missing_value, MetaEnclosedListTest, {
+        test_list
+    });
+    try_parse_fail!(meta_enclosed_list_wrong_paren_type, MetaEnclosedListTest, {
+        test_list(a: u8)
+    });
+    try_parse_fail!(meta_enclosed_list_wrong_brace_type, MetaEnclosedListTest, {
+        test_list { 3u8 }
+    });
+
     try_parse!(ident_pat_type, IdentPatType, { foo: u8 });
     try_parse_fail!(ident_pat_type_missing_ident, IdentPatType, { : 3u8 });
     try_parse_fail!(ident_pat_type_missing_ty, IdentPatType, { foo: });
     try_parse_fail!(ident_pat_type_wrong_ty_type, IdentPatType, { foo: 3u8 });
 
+    try_parse!(ident_type_maybe_default, IdentTypeMaybeDefault, { foo: u8 });
+    try_parse!(ident_type_maybe_default_with_default, IdentTypeMaybeDefault, {
+        foo: u8 = 1 + 2
+    });
+    try_parse_fail!(ident_type_maybe_default_missing_default, IdentTypeMaybeDefault, {
+        foo: u8 =
+    });
+
+    try_parse!(ident_expr, IdentExpr, { foo: 1 + 2 });
+    try_parse!(ident_expr_shorthand, IdentExpr, { foo });
+    try_parse_fail!(ident_expr_unnamed, IdentExpr, { 0: 1 });
+    try_parse_fail!(ident_expr_missing_expr, IdentExpr, { foo: });
+
     try_parse!(meta_attr_list, MetaAttrListTest, { (1u8, 2u8, 3u8) });
     try_parse!(meta_attr_list_empty, MetaAttrListTest, { () });
     try_parse_fail!(meta_attr_list_wrong_type, MetaAttrListTest, { (i32) });
@@ -38,7 +38,6 @@ pub(crate) trait FromAttrs<Attr: syn::parse::Parse> {
     where
         Self: Sized,
     {
-        #[allow(clippy::filter_map)]
         let attrs = attrs
             .iter()
             .filter(|attr| is_binread_attr(attr))
@@ -273,6 +272,7 @@ impl<T: core::convert::TryInto<To, Error = syn::Error> + KeywordToken, To> TrySe
 }
 
 #[cfg(test)]
+#[allow(clippy::should_panic_without_expect)]
 mod tests {
     use super::*;
     use proc_macro2::TokenStream;
@@ -299,6 +299,55 @@ mod tests {
         };
     );
 
+    try_error!(bits_seek_before: "`bits` can not be used with" {
+        struct Foo {
+            #[br(bits = 4, seek_before(SeekFrom::Current(1)))]
+            a: u8,
+        }
+    });
+
+    try_error!(bits_pad_before: "`bits` can not be used with" {
+        struct Foo {
+            #[br(bits = 4, pad_before = 1)]
+            a: u8,
+        }
+    });
+
+    try_error!(bits_pad_after: "`bits` can not be used with" {
+        struct Foo {
+            #[br(bits = 4, pad_after = 1)]
+            a: u8,
+        }
+    });
+
+    try_error!(bits_align_before: "`bits` can not be used with" {
+        struct Foo {
+            #[br(bits = 4, align_before = 2)]
+            a: u8,
+        }
+    });
+
+    try_error!(bits_align_after: "`bits` can not be used with" {
+        struct Foo {
+            #[br(bits = 4, align_after = 2)]
+            a: u8,
+        }
+    });
+
+    try_error!(bits_restore_position: "`bits` can not be used with" {
+        struct Foo {
+            #[br(bits = 4, restore_position)]
+            a: u8,
+        }
+    });
+
+    try_error!(bits_pad_size_to: "`bits` can not be used with" {
+        struct Foo {
+            #[br(bits = 4, pad_size_to = 2)]
+            a: u8,
+        }
+    });
+
     try_error!(conflicting_keyword_bool: "conflicting `restore_position` keyword" {
         struct Foo {
             #[br(restore_position, restore_position)]
@@ -352,6 +401,27 @@ mod tests {
         }
     });
 
+    try_error!(endian_from_conflict: "conflicting endianness keyword" {
+        #[br(big, endian_from(bom, big = 0xFEFF, little = 0xFFFE))]
+        struct Foo {
+            bom: u16,
+        }
+    });
+
+    try_error!(endian_from_missing_field: "`endian_from` field `bom` does not exist" {
+        #[br(endian_from = "bom", big = 0xFEFF, little = 0xFFFE)]
+        struct Foo {
+            a: u16,
+        }
+    });
+
+    try_error!(endian_from_missing_value: "expected a `big = ...` and a `little = ...` value" {
+        #[br(endian_from = "bom", big = 0xFEFF)]
+        struct Foo {
+            bom: u16,
+        }
+    });
+
     try_error!(enum_missing_magic_repr {
         enum UnitEnum {
             A,
@@ -1,5 +1,5 @@
 use super::{
-    types::{Assert, CondEndian, EnumErrorMode, Imports, Magic, Map},
+    types::{Assert, BitOrder, CondEndian, EnumErrorMode, Imports, Magic, Map},
     EnumVariant, FromInput, ParseResult, SpannedValue, StructField, TrySet, UnitEnumField,
 };
 use proc_macro2::TokenStream;
@@ -53,6 +53,14 @@ impl Input {
         }
     }
 
+    pub(crate) fn bit_order(&self) -> BitOrder {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.bit_order,
+            Input::Enum(e) => e.bit_order,
+            Input::UnitOnlyEnum(_) => BitOrder::Inherited,
+        }
+    }
+
     pub(crate) fn imports(&self) -> &Imports {
         match self {
             Input::Struct(s) | Input::UnitStruct(s) => &s.imports,
@@ -63,10 +71,10 @@ impl Input {
 
     pub(crate) fn is_temp_field(&self, variant_index: usize, index: usize) -> bool {
         match self {
-            Input::Struct(s) => s.fields.get(index).map_or(false, |field| field.temp),
-            Input::Enum(e) => e.variants.get(variant_index).map_or(false, |variant| {
+            Input::Struct(s) => s.fields.get(index).is_some_and(|field| field.temp),
+            Input::Enum(e) => e.variants.get(variant_index).is_some_and(|variant| {
                 if let EnumVariant::Variant { options, .. } = variant {
-                    options.fields.get(index).map_or(false, |field| field.temp)
+                    options.fields.get(index).is_some_and(|field| field.temp)
                 } else {
                     false
                 }
@@ -83,6 +91,14 @@ impl Input {
         }
     }
 
+    pub(crate) fn write_map(&self) -> Option<&TokenStream> {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.write_map.as_ref(),
+            Input::Enum(e) => e.write_map.as_ref(),
+            Input::UnitOnlyEnum(e) => e.write_map.as_ref(),
+        }
+    }
+
     pub(crate) fn magic(&self) -> &Magic {
         match self {
             Input::Struct(s) | Input::UnitStruct(s) => &s.magic,
@@ -104,10 +120,14 @@ attr_struct! {
     #[from(StructAttr)]
     #[derive(Clone, Debug, Default)]
     pub(crate) struct Struct {
-        #[from(Big, Little)]
+        #[from(Big, Little, EndianFrom)]
         pub(crate) endian: CondEndian,
+        #[from(MsbFirst, LsbFirst)]
+        pub(crate) bit_order: BitOrder,
         #[from(Map, TryMap)]
         pub(crate) map: Map,
+        #[from(WriteMap)]
+        pub(crate) write_map: Option<TokenStream>,
         #[from(Magic)]
         pub(crate) magic: Magic,
         #[from(Import, ImportTuple)]
@@ -123,8 +143,12 @@ attr_struct! {
 impl Struct {
     pub(crate) fn is_tuple(&self) -> bool {
         self.fields
-            .get(0)
-            .map_or(false, |field| field.generated_ident)
+            .first()
+            .is_some_and(|field| field.generated_ident)
+    }
+
+    pub(crate) fn has_bits(&self) -> bool {
+        self.fields.iter().any(|field| field.bits.is_some())
     }
 
     pub(crate) fn iter_permanent_idents(&self) -> impl Iterator<Item = &syn::Ident> + '_ {
@@ -141,6 +165,18 @@ impl FromInput<StructAttr> for Struct {
         self.fields.push(field);
         Ok(())
     }
+
+    fn validate(&self) -> syn::Result<()> {
+        match self.endian.marker_field() {
+            Some(marker) if !self.fields.iter().any(|field| field.ident == *marker) => {
+                Err(syn::Error::new(
+                    marker.span(),
+                    format!("`endian_from` field `{marker}` does not exist"),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 attr_struct! {
@@ -149,8 +185,12 @@ attr_struct! {
     pub(crate) struct Enum {
         #[from(Big, Little)]
         pub(crate) endian: CondEndian,
+        #[from(MsbFirst, LsbFirst)]
+        pub(crate) bit_order: BitOrder,
         #[from(Map, TryMap)]
         pub(crate) map: Map,
+        #[from(WriteMap)]
+        pub(crate) write_map: Option<TokenStream>,
         #[from(Magic)]
         pub(crate) magic: Magic,
         #[from(Import, ImportTuple)]
@@ -176,12 +216,18 @@ impl Enum {
 
         match variant {
             EnumVariant::Variant { options, .. } => {
-                if options.endian.is_some() {
-                    out.endian = options.endian.clone();
+                // A variant which reads its byte order from a field uses the
+                // one of the enum until then
+                if options.endian.is_some() && options.endian.marker_field().is_none() {
+                    out.endian.clone_from(&options.endian);
+                }
+
+                if options.bit_order.is_some() {
+                    out.bit_order = options.bit_order;
                 }
 
                 if options.magic.is_some() {
-                    out.magic = options.magic.clone();
+                    out.magic.clone_from(&options.magic);
                 }
 
                 out.pre_assertions
@@ -191,7 +237,7 @@ impl Enum {
 
             EnumVariant::Unit(options) => {
                 if options.magic.is_some() {
-                    out.magic = options.magic.clone();
+                    out.magic.clone_from(&options.magic);
                 }
 
                 out.pre_assertions
@@ -220,6 +266,8 @@ attr_struct! {
         pub(crate) endian: CondEndian,
         #[from(Map, TryMap)]
         pub(crate) map: Map,
+        #[from(WriteMap)]
+        pub(crate) write_map: Option<TokenStream>,
         #[from(Magic)]
         pub(crate) magic: Magic,
         #[from(Import, ImportTuple)]
@@ -251,22 +299,20 @@ impl FromInput<UnitEnumAttr> for UnitOnlyEnum {
         } else {
             let expected_magic = self.expected_field_magic.as_ref();
             match (expected_magic, field.magic.as_ref()) {
-                (Some(expected_magic), Some(magic)) => {
-                    if expected_magic.kind() != magic.kind() {
-                        let magic_span = magic.match_value().span();
-                        let span = magic_span
-                            .join(expected_magic.match_value().span())
-                            .unwrap_or(magic_span);
-                        return Err(syn::Error::new(
-                            span,
-                            format!(
-                                "conflicting magic types; expected {}",
-                                expected_magic.kind()
-                            ),
-                        ));
-                    }
+                (Some(expected_magic), Some(magic)) if expected_magic.kind() != magic.kind() => {
+                    let magic_span = magic.match_value().span();
+                    let span = magic_span
+                        .join(expected_magic.match_value().span())
+                        .unwrap_or(magic_span);
+                    return Err(syn::Error::new(
+                        span,
+                        format!(
+                            "conflicting magic types; expected {}",
+                            expected_magic.kind()
+                        ),
+                    ));
                 }
-                (None, Some(_)) => self.expected_field_magic = field.magic.clone(),
+                (None, Some(_)) => self.expected_field_magic.clone_from(&field.magic),
                 _ => {}
             }
 
@@ -0,0 +1,41 @@
+use crate::parser::{attrs, KeywordToken, TrySet};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum BitOrder {
+    #[default]
+    Inherited,
+    MsbFirst,
+    LsbFirst,
+}
+
+impl BitOrder {
+    pub(crate) fn is_some(self) -> bool {
+        !matches!(self, BitOrder::Inherited)
+    }
+}
+
+impl From<attrs::MsbFirst> for BitOrder {
+    fn from(_: attrs::MsbFirst) -> Self {
+        Self::MsbFirst
+    }
+}
+
+impl From<attrs::LsbFirst> for BitOrder {
+    fn from(_: attrs::LsbFirst) -> Self {
+        Self::LsbFirst
+    }
+}
+
+impl<T: Into<BitOrder> + KeywordToken> TrySet<BitOrder> for T {
+    fn try_set(self, to: &mut BitOrder) -> syn::Result<()> {
+        if to.is_some() {
+            Err(syn::Error::new(
+                self.keyword_span(),
+                "conflicting bit order keyword",
+            ))
+        } else {
+            *to = self.into();
+            Ok(())
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::parser::{attrs, KeywordToken, TrySet};
 use proc_macro2::TokenStream;
-use quote::ToTokens;
+use quote::{quote, ToTokens};
+use syn::{Expr, Ident, Lit};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub(crate) enum Endian {
@@ -8,22 +9,32 @@ pub(crate) enum Endian {
     Little,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub(crate) enum CondEndian {
+    #[default]
     Inherited,
     Fixed(Endian),
     Cond(Endian, TokenStream),
+    /// Inherited until `field` is read, then big-endian if it equals `big`
+    /// or little-endian if it equals `little`.
+    Marker {
+        field: Ident,
+        big: TokenStream,
+        little: TokenStream,
+    },
 }
 
 impl CondEndian {
     pub(crate) fn is_some(&self) -> bool {
         !matches!(self, CondEndian::Inherited)
     }
-}
 
-impl Default for CondEndian {
-    fn default() -> Self {
-        Self::Inherited
+    /// Returns the field the byte order is read from, if there is one.
+    pub(crate) fn marker_field(&self) -> Option<&Ident> {
+        match self {
+            CondEndian::Marker { field, .. } => Some(field),
+            _ => None,
+        }
     }
 }
 
@@ -51,6 +62,29 @@ impl From<attrs::IsLittle> for CondEndian {
     }
 }
 
+impl From<attrs::EndianFrom> for CondEndian {
+    fn from(endian_from: attrs::EndianFrom) -> Self {
+        let marker = endian_from.value;
+        Self::Marker {
+            field: marker.field,
+            big: marker_value(&marker.big),
+            little: marker_value(&marker.little),
+        }
+    }
+}
+
+/// Byte strings are dereferenced so that they compare equal to byte arrays,
+/// like they are for `magic`
+fn marker_value(value: &Expr) -> TokenStream {
+    match value {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::ByteStr(_),
+            ..
+        }) => quote! { *#value },
+        _ => value.to_token_stream(),
+    }
+}
+
 impl<T: Into<CondEndian> + KeywordToken> TrySet<CondEndian> for T {
     fn try_set(self, to: &mut CondEndian) -> syn::Result<()> {
         if matches!(*to, CondEndian::Inherited) {
@@ -1,18 +1,13 @@
 use crate::parser::{attrs, KeywordToken, TrySet};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
 pub(crate) enum EnumErrorMode {
+    #[default]
     Default,
     ReturnAllErrors,
     ReturnUnexpectedError,
 }
 
-impl Default for EnumErrorMode {
-    fn default() -> Self {
-        Self::Default
-    }
-}
-
 impl From<attrs::ReturnAllErrors> for EnumErrorMode {
     fn from(_: attrs::ReturnAllErrors) -> Self {
         Self::ReturnAllErrors
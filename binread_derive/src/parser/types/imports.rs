@@ -1,23 +1,31 @@
-use crate::parser::{attrs, KeywordToken, TrySet};
-use proc_macro2::TokenStream;
+use crate::parser::{
+    attrs,
+    meta_types::{Enclosure, IdentTypeMaybeDefault},
+    KeywordToken, TrySet,
+};
+use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
-use syn::{Ident, Type};
+use syn::{
+    visit::Visit,
+    visit_mut::{self, VisitMut},
+    Ident, Lifetime, Type,
+};
 
-#[derive(Debug, Clone)]
+/// The lifetime given to borrowed arguments when the imports do not name one
+const DEFAULT_LIFETIME: &str = "'__binread_args";
+
+#[derive(Debug, Clone, Default)]
 pub(crate) enum Imports {
+    #[default]
     None,
     List(Vec<Ident>, Vec<Type>),
+    Named(Vec<IdentTypeMaybeDefault>),
     Tuple(Ident, Box<Type>),
 }
 
-impl Default for Imports {
-    fn default() -> Self {
-        Imports::None
-    }
-}
-
 impl Imports {
-    pub fn idents(&self) -> Option<TokenStream> {
+    /// Returns a statement binding each import from `args` to its own variable
+    pub fn destructure(&self, args: &impl ToTokens) -> Option<TokenStream> {
         match self {
             Imports::None => None,
             Imports::List(idents, _) => {
@@ -26,17 +34,49 @@ impl Imports {
                 } else {
                     let idents = idents.iter();
                     Some(quote! {
-                        (#(mut #idents,)*)
+                        let (#(mut #idents,)*) = #args;
+                    })
+                }
+            }
+            Imports::Named(fields) => {
+                if fields.is_empty() {
+                    None
+                } else {
+                    let idents = fields.iter().map(|field| &field.ident);
+                    let values = fields.iter().map(|field| &field.ident);
+                    Some(quote! {
+                        let (#(mut #idents,)*) = (#(#args.#values,)*);
                     })
                 }
             }
             Imports::Tuple(ident, _) => Some(quote! {
-                mut #ident
+                let mut #ident = #args;
             }),
         }
     }
 
-    pub fn types(&self) -> TokenStream {
+    /// Returns the lifetime of the references in the imported arguments. If
+    /// the arguments do not borrow anything, a placeholder lifetime is used.
+    pub fn lifetime(&self) -> Lifetime {
+        self.borrowed_lifetime()
+            .unwrap_or_else(|| Lifetime::new(DEFAULT_LIFETIME, Span::call_site()))
+    }
+
+    /// Returns `true` if any of the imported arguments borrows data.
+    pub fn is_borrowed(&self) -> bool {
+        self.borrowed_lifetime().is_some()
+    }
+
+    pub fn named_fields(&self) -> Option<&[IdentTypeMaybeDefault]> {
+        match self {
+            Imports::Named(fields) => Some(fields),
+            _ => None,
+        }
+    }
+
+    /// Returns the type of the arguments. Named imports use the generated
+    /// `named_args` struct.
+    pub fn types(&self, named_args: &impl ToTokens) -> TokenStream {
         match self {
             Imports::None => quote! { () },
             Imports::List(_, types) => {
@@ -45,20 +85,109 @@ impl Imports {
                     (#(#types,)*)
                 }
             }
+            Imports::Named(_) => named_args.to_token_stream(),
             Imports::Tuple(_, ty) => ty.to_token_stream(),
         }
     }
+
+    fn borrowed_lifetime(&self) -> Option<Lifetime> {
+        self.find_lifetimes().into_iter().next()
+    }
+
+    fn find_lifetimes(&self) -> Vec<Lifetime> {
+        let mut finder = FindLifetimes::default();
+        for ty in self.types_iter() {
+            finder.visit_type(ty);
+        }
+        finder.lifetimes
+    }
+
+    fn types_iter(&self) -> Box<dyn Iterator<Item = &Type> + '_> {
+        match self {
+            Imports::None => Box::new(core::iter::empty()),
+            Imports::List(_, types) => Box::new(types.iter()),
+            Imports::Named(fields) => Box::new(fields.iter().map(|field| &field.ty)),
+            Imports::Tuple(_, ty) => Box::new(core::iter::once(&**ty)),
+        }
+    }
+
+    fn types_iter_mut(&mut self) -> Box<dyn Iterator<Item = &mut Type> + '_> {
+        match self {
+            Imports::None => Box::new(core::iter::empty()),
+            Imports::List(_, types) => Box::new(types.iter_mut()),
+            Imports::Named(fields) => Box::new(fields.iter_mut().map(|field| &mut field.ty)),
+            Imports::Tuple(_, ty) => Box::new(core::iter::once(&mut **ty)),
+        }
+    }
+
+    /// Gives every elided lifetime in the imported types the lifetime of the
+    /// arguments, so that the types can be used in `BinRead::Args<'a>`.
+    /// Arguments may only borrow using a single named lifetime.
+    fn resolve_lifetimes(&mut self) -> syn::Result<()> {
+        let mut lifetimes = self.find_lifetimes().into_iter();
+        let lifetime = lifetimes
+            .next()
+            .unwrap_or_else(|| Lifetime::new(DEFAULT_LIFETIME, Span::call_site()));
+        if let Some(extra) = lifetimes.find(|other| *other != lifetime) {
+            return Err(syn::Error::new(
+                extra.span(),
+                "imported arguments may only borrow using a single lifetime",
+            ));
+        }
+
+        let mut filler = FillLifetimes(lifetime);
+        for ty in self.types_iter_mut() {
+            filler.visit_type_mut(ty);
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects the named, non-`'static` lifetimes used by a type
+#[derive(Default)]
+struct FindLifetimes {
+    lifetimes: Vec<Lifetime>,
+}
+
+impl<'ast> Visit<'ast> for FindLifetimes {
+    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+        if lifetime.ident != "static" && lifetime.ident != "_" {
+            self.lifetimes.push(lifetime.clone());
+        }
+    }
+}
+
+/// Replaces elided and anonymous lifetimes of a type with the given lifetime
+struct FillLifetimes(Lifetime);
+
+impl VisitMut for FillLifetimes {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.0.clone();
+        }
+    }
+
+    fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = Some(self.0.clone());
+        }
+        visit_mut::visit_type_reference_mut(self, reference);
+    }
 }
 
 impl From<attrs::Import> for Imports {
     fn from(value: attrs::Import) -> Self {
-        let (idents, tys): (Vec<_>, Vec<_>) = value
-            .fields
-            .iter()
-            .cloned()
-            .map(|import_arg| (import_arg.ident, import_arg.ty))
-            .unzip();
-        Self::List(idents, tys)
+        match value.list {
+            Enclosure::Paren(fields) => {
+                let (idents, tys): (Vec<_>, Vec<_>) = fields
+                    .into_iter()
+                    .map(|import_arg| (import_arg.ident, import_arg.ty))
+                    .unzip();
+                Self::List(idents, tys)
+            }
+            Enclosure::Brace(fields) => Self::Named(fields.into_iter().collect()),
+        }
     }
 }
 
@@ -72,7 +201,7 @@ impl<T: Into<Imports> + KeywordToken> TrySet<Imports> for T {
     fn try_set(self, to: &mut Imports) -> syn::Result<()> {
         if matches!(*to, Imports::None) {
             *to = self.into();
-            Ok(())
+            to.resolve_lifetimes()
         } else {
             Err(syn::Error::new(
                 self.keyword_span(),
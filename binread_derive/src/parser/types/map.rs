@@ -2,8 +2,11 @@ use crate::parser::{attrs, KeywordToken, TrySet};
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 
-#[derive(Clone, Debug)]
+// `Map::Map` reads better at use sites than any alternative name
+#[allow(clippy::enum_variant_names)]
+#[derive(Clone, Debug, Default)]
 pub(crate) enum Map {
+    #[default]
     None,
     Map(TokenStream),
     Try(TokenStream),
@@ -15,12 +18,6 @@ impl Map {
     }
 }
 
-impl Default for Map {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 impl From<attrs::Map> for Map {
     fn from(map: attrs::Map) -> Self {
         Self::Map(map.value.to_token_stream())
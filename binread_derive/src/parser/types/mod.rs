@@ -1,4 +1,5 @@
 mod assert;
+mod bit_order;
 mod cond_endian;
 mod condition;
 mod enum_error_mode;
@@ -7,9 +8,11 @@ mod magic;
 mod map;
 mod passed_args;
 mod read_mode;
+mod size;
 mod spanned_value;
 
 pub(crate) use assert::{Assert, Error as AssertionError};
+pub(crate) use bit_order::BitOrder;
 pub(crate) use cond_endian::{CondEndian, Endian};
 pub(crate) use condition::Condition;
 pub(crate) use enum_error_mode::EnumErrorMode;
@@ -18,6 +21,7 @@ pub(crate) use magic::Magic;
 pub(crate) use map::Map;
 pub(crate) use passed_args::PassedArgs;
 pub(crate) use read_mode::ReadMode;
+pub(crate) use size::Size;
 pub(crate) use spanned_value::SpannedValue;
 
 fn assert_all_args_consumed<Iter, IterItem>(
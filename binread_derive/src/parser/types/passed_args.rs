@@ -1,28 +1,29 @@
-use crate::parser::{attrs, KeywordToken, TrySet};
+use crate::parser::{attrs, meta_types::Enclosure, KeywordToken, TrySet};
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub(crate) enum PassedArgs {
+    #[default]
     None,
     List(Vec<TokenStream>),
+    Named(Vec<(syn::Ident, TokenStream)>),
     Tuple(TokenStream),
 }
 
-impl Default for PassedArgs {
-    fn default() -> Self {
-        PassedArgs::None
-    }
-}
-
 impl From<attrs::Args> for PassedArgs {
     fn from(args: attrs::Args) -> Self {
-        Self::List(
-            args.fields
-                .iter()
-                .map(ToTokens::into_token_stream)
-                .collect(),
-        )
+        match args.list {
+            Enclosure::Paren(fields) => {
+                Self::List(fields.iter().map(ToTokens::into_token_stream).collect())
+            }
+            Enclosure::Brace(fields) => Self::Named(
+                fields
+                    .into_iter()
+                    .map(|field| (field.ident, field.expr.into_token_stream()))
+                    .collect(),
+            ),
+        }
     }
 }
 
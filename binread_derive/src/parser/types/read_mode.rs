@@ -2,20 +2,15 @@ use crate::parser::{attrs, KeywordToken, TrySet};
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub(crate) enum ReadMode {
+    #[default]
     Normal,
     Default,
     Calc(TokenStream),
     ParseWith(TokenStream),
 }
 
-impl Default for ReadMode {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
-
 impl From<attrs::Ignore> for ReadMode {
     fn from(_: attrs::Ignore) -> Self {
         Self::Default
@@ -0,0 +1,57 @@
+use crate::parser::{attrs, KeywordToken, TrySet};
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+
+#[derive(Clone, Debug, Default)]
+pub(crate) enum Size {
+    #[default]
+    None,
+    /// The field is limited to the size, and any bytes it does not read are
+    /// skipped
+    Limit(TokenStream),
+    /// The field is limited to the size and must read all of it
+    Exact(TokenStream),
+}
+
+impl Size {
+    pub(crate) fn is_some(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+
+    pub(crate) fn is_exact(&self) -> bool {
+        matches!(self, Self::Exact(_))
+    }
+
+    pub(crate) fn expr(&self) -> Option<&TokenStream> {
+        match self {
+            Self::None => None,
+            Self::Limit(size) | Self::Exact(size) => Some(size),
+        }
+    }
+}
+
+impl From<attrs::Size> for Size {
+    fn from(size: attrs::Size) -> Self {
+        Self::Limit(size.value.to_token_stream())
+    }
+}
+
+impl From<attrs::SizeExact> for Size {
+    fn from(size: attrs::SizeExact) -> Self {
+        Self::Exact(size.value.to_token_stream())
+    }
+}
+
+impl<T: Into<Size> + KeywordToken> TrySet<Size> for T {
+    fn try_set(self, to: &mut Size) -> syn::Result<()> {
+        if to.is_some() {
+            Err(syn::Error::new(
+                self.keyword_span(),
+                "conflicting size keyword",
+            ))
+        } else {
+            *to = self.into();
+            Ok(())
+        }
+    }
+}